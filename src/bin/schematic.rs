@@ -0,0 +1,225 @@
+//! Converts schemas exported as JSON into the formats supported by Schematic.
+//!
+//! The input is a `Schema` serialized with `serde_json`, e.g. written by a test or
+//! a `build.rs` script with `serde_json::to_string(&schematic::describe::<T>())`.
+//! This binary is only built with the `cli` feature.
+
+use schematic::{doc, snapshot, Schema};
+use std::{
+    error::Error,
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(about = "Converts schemas exported as JSON into other formats")]
+enum Command {
+    /// Converts a schema into one or more formats.
+    Convert {
+        /// The schema JSON file, or `-` to read from stdin.
+        input: PathBuf,
+
+        /// The format to generate. Can be given more than once with `--out-dir`.
+        #[structopt(
+            short,
+            long = "format",
+            required = true,
+            number_of_values = 1,
+            possible_values = Format::NAMES
+        )]
+        formats: Vec<Format>,
+
+        /// Writes each format to `<out-dir>/<input name>.<extension>` instead of
+        /// stdout.
+        #[structopt(short, long)]
+        out_dir: Option<PathBuf>,
+    },
+
+    /// Checks that a schema converts to the contents of a committed snapshot.
+    ///
+    /// Prints the differences and exits with status 1 if the snapshot is out of
+    /// date.
+    #[structopt(alias = "diff")]
+    Check {
+        /// The schema JSON file, or `-` to read from stdin.
+        input: PathBuf,
+
+        /// The snapshot to compare against.
+        snapshot: PathBuf,
+
+        /// The format of the snapshot.
+        #[structopt(short, long, possible_values = Format::NAMES)]
+        format: Format,
+    },
+}
+
+/// An output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// The schema itself, as pretty-printed JSON.
+    Json,
+    JsonSchema,
+    CHeader,
+    Dot,
+    Markdown,
+    Html,
+    Rust,
+    Toml,
+    Yaml,
+}
+
+impl Format {
+    const NAMES: &'static [&'static str] = &[
+        "json",
+        "json-schema",
+        "c-header",
+        "dot",
+        "markdown",
+        "html",
+        "rust",
+        "toml",
+        "yaml",
+    ];
+
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::JsonSchema => "schema.json",
+            Format::CHeader => "h",
+            Format::Dot => "dot",
+            Format::Markdown => "md",
+            Format::Html => "html",
+            Format::Rust => "rs",
+            Format::Toml => "toml",
+            Format::Yaml => "yaml",
+        }
+    }
+
+    fn generate(self, schema: &Schema) -> Result<String, Box<dyn Error>> {
+        let output = match self {
+            Format::Json => serde_json::to_string_pretty(schema)? + "\n",
+            Format::JsonSchema => serde_json::to_string_pretty(&schema.to_json_schema()?)? + "\n",
+            Format::CHeader => schematic::c_header::generate(schema)?,
+            Format::Dot => schematic::dot::generate(schema),
+            Format::Markdown => doc::generate(schema, doc::Format::Markdown),
+            Format::Html => doc::generate(schema, doc::Format::Html),
+            Format::Rust => schematic::rust::generate(schema)?,
+            Format::Toml => schematic::skeleton::to_toml(schema, &schematic::skeleton(schema)?)?,
+            Format::Yaml => schematic::skeleton::to_yaml(schema, &schematic::skeleton(schema)?)?,
+        };
+
+        Ok(output)
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let format = match name {
+            "json" => Format::Json,
+            "json-schema" => Format::JsonSchema,
+            "c-header" => Format::CHeader,
+            "dot" => Format::Dot,
+            "markdown" => Format::Markdown,
+            "html" => Format::Html,
+            "rust" => Format::Rust,
+            "toml" => Format::Toml,
+            "yaml" => Format::Yaml,
+            _ => return Err(format!("unknown format `{}`", name)),
+        };
+
+        Ok(format)
+    }
+}
+
+fn main() {
+    match run(Command::from_args()) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(error) => {
+            eprintln!("error: {}", error);
+            process::exit(2);
+        }
+    }
+}
+
+/// Runs `command`, returning `false` if a check found differences.
+fn run(command: Command) -> Result<bool, Box<dyn Error>> {
+    match command {
+        Command::Convert {
+            input,
+            formats,
+            out_dir,
+        } => {
+            let schema = read_schema(&input)?;
+
+            let out_dir = match out_dir {
+                Some(out_dir) => out_dir,
+                None if formats.len() == 1 => {
+                    io::stdout().write_all(formats[0].generate(&schema)?.as_bytes())?;
+                    return Ok(true);
+                }
+                None => return Err("`--out-dir` is required for multiple formats".into()),
+            };
+
+            let stem = match input.file_stem() {
+                Some(stem) if input != Path::new("-") => stem.to_owned(),
+                _ => "schema".into(),
+            };
+
+            // The extension is appended rather than set, so that a stem with dots in
+            // it, such as `config.v2`, is kept whole.
+            fs::create_dir_all(&out_dir)?;
+            for format in formats {
+                let mut file_name = stem.clone();
+                file_name.push(".");
+                file_name.push(format.extension());
+                let path = out_dir.join(file_name);
+                fs::write(&path, format.generate(&schema)?)
+                    .map_err(|error| format!("failed to write {}: {}", path.display(), error))?;
+            }
+
+            Ok(true)
+        }
+
+        Command::Check {
+            input,
+            snapshot,
+            format,
+        } => {
+            let schema = read_schema(&input)?;
+            let generated = format.generate(&schema)?;
+            let expected = fs::read_to_string(&snapshot)
+                .map_err(|error| format!("failed to read {}: {}", snapshot.display(), error))?;
+
+            if generated == expected {
+                return Ok(true);
+            }
+
+            let mut stdout = io::stdout();
+            writeln!(stdout, "--- {}", snapshot.display())?;
+            writeln!(stdout, "+++ {}", input.display())?;
+            stdout.write_all(snapshot::diff(&expected, &generated).as_bytes())?;
+
+            Ok(false)
+        }
+    }
+}
+
+fn read_schema(path: &Path) -> Result<Schema, Box<dyn Error>> {
+    let mut json = String::new();
+    if path == Path::new("-") {
+        io::stdin().read_to_string(&mut json)?;
+    } else {
+        json = fs::read_to_string(path)
+            .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+    }
+
+    serde_json::from_str(&json)
+        .map_err(|error| format!("invalid schema in {}: {}", path.display(), error).into())
+}
@@ -0,0 +1,369 @@
+//! Generate C header declarations matching the in-memory layout of Rust types.
+//!
+//! The generated header declares every user-defined type reachable from a root
+//! schema as a C `struct`, `enum`, or tagged union, in dependency order. For types
+//! that describe their [`Layout`], the header also contains `_Static_assert`
+//! checks for the size, alignment, and field offsets of the type, so that any
+//! mismatch between the Rust and C definitions is caught when the header is
+//! compiled. Newtypes have no [`Layout`] in the data model, so they are declared
+//! without checks.
+//!
+//! Only types with a C equivalent can be declared. Strings, sequences, maps,
+//! tuples, and other types without a stable representation are rejected with an
+//! [`Error`], as are enums with data and a primitive `repr`, since the schema
+//! doesn't tell `#[repr(Int)]` and `#[repr(C, Int)]` enums apart.
+//!
+//! [`Layout`]: ../struct.Layout.html
+//! [`Error`]: enum.Error.html
+
+use crate::{Enum, Layout, Primitive, Schema, Struct, TupleStruct, TypeName, Variant};
+use std::fmt::{self, Write};
+
+/// Generates a C header declaring `schema` and all of the types it depends on.
+///
+/// # Examples
+///
+/// ```
+/// use schematic::{NamedField, Schema, Struct, TypeName};
+///
+/// let schema = Schema::Struct(Struct {
+///     name: TypeName::new("Point", "my_crate"),
+///     docs: None,
+///     attributes: Default::default(),
+///     fields: vec![NamedField::new("x", Schema::F32), NamedField::new("y", Schema::F32)],
+///     layout: Some(schematic::Layout {
+///         size: 8,
+///         align: 4,
+///         field_offsets: vec![0, 4],
+///     }),
+/// });
+///
+/// let header = schematic::c_header::generate(&schema).unwrap();
+/// assert!(header.contains("typedef struct Point {\n    float x;\n    float y;\n} Point;"));
+/// assert!(header.contains("_Static_assert(offsetof(Point, y) == 4, \"offset of Point.y\");"));
+/// ```
+pub fn generate(schema: &Schema) -> Result<String, Error> {
+    let mut output =
+        String::from("#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n");
+
+    for ty in schema.named_types() {
+        output.push('\n');
+
+        match ty {
+            Schema::Struct(schema) => write_struct(&mut output, schema)?,
+            Schema::TupleStruct(schema) => write_tuple_struct(&mut output, schema)?,
+            // Newtypes don't carry a layout, so there's nothing to check.
+            Schema::NewtypeStruct(schema) => {
+                let name = c_name(&schema.name);
+                let fields = [("_0".to_string(), &schema.inner)];
+                write_fields(&mut output, &name, &fields)?;
+            }
+            Schema::Enum(schema) => write_enum(&mut output, schema)?,
+
+            _ => return Err(Error::unsupported(ty)),
+        }
+    }
+
+    Ok(output)
+}
+
+/// An error generating a C header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The schema contains a type that cannot be represented in C.
+    Unsupported(Box<Schema>),
+
+    /// The layout of a type doesn't have an offset for each of its fields.
+    InvalidLayout(TypeName),
+
+    /// An enum with data has a primitive representation, which doesn't tell
+    /// whether it uses the `#[repr(Int)]` or the `#[repr(C, Int)]` layout.
+    AmbiguousRepr(TypeName),
+}
+
+impl Error {
+    fn unsupported(schema: &Schema) -> Self {
+        Error::Unsupported(Box::new(schema.clone()))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Unsupported(schema) => match schema.type_name() {
+                Some(name) => write!(f, "type `{}` has no C representation", name.name),
+                None => write!(f, "{:?} has no C representation", schema),
+            },
+
+            Error::InvalidLayout(name) => write!(
+                f,
+                "layout of `{}` does not match the number of fields",
+                name.name,
+            ),
+
+            Error::AmbiguousRepr(name) => write!(
+                f,
+                "layout of `{}` is ambiguous, enums with data must be `#[repr(C)]`",
+                name.name,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+fn write_struct(output: &mut String, schema: &Struct) -> Result<(), Error> {
+    let name = c_name(&schema.name);
+    let fields = schema
+        .fields
+        .iter()
+        .map(|field| (field.name.to_string(), &field.schema))
+        .collect::<Vec<_>>();
+
+    write_fields(output, &name, &fields)?;
+    write_layout_checks(output, &schema.name, &name, &fields, schema.layout.as_ref())
+}
+
+fn write_tuple_struct(output: &mut String, schema: &TupleStruct) -> Result<(), Error> {
+    let name = c_name(&schema.name);
+    let fields = schema
+        .elements
+        .iter()
+        .enumerate()
+        .map(|(index, schema)| (format!("_{}", index), schema))
+        .collect::<Vec<_>>();
+
+    write_fields(output, &name, &fields)?;
+    write_layout_checks(output, &schema.name, &name, &fields, schema.layout.as_ref())
+}
+
+/// Emits enums in the layout Rust uses for `#[repr(C)]` enums.
+///
+/// C-like enums become a plain C enum, using the `repr` of the enum, if any, as
+/// the integer type of the enum. Enums with data become a struct containing the
+/// tag followed by a union of one struct per non-empty variant, which matches the
+/// `#[repr(C)]` layout.
+///
+/// Enums with data and a primitive `repr` are rejected: `#[repr(u8)]` and
+/// `#[repr(C, u8)]` lay out their variants differently, but both are described as
+/// `Some(Primitive::U8)`.
+fn write_enum(output: &mut String, schema: &Enum) -> Result<(), Error> {
+    let name = c_name(&schema.name);
+
+    if !schema.has_data() {
+        write_tag(output, &name, schema)?;
+        return write_layout_checks(output, &schema.name, &name, &[], schema.layout.as_ref());
+    }
+
+    if schema.repr.is_some() {
+        return Err(Error::AmbiguousRepr(schema.name.clone()));
+    }
+
+    let tag = format!("{}_Tag", name);
+    write_tag(output, &tag, schema)?;
+
+    let mut members = Vec::new();
+    for variant in schema.variants.iter().filter(|variant| !variant.is_empty()) {
+        let variant_name = format!("{}_{}_Body", name, variant.name());
+        let fields = variant
+            .fields()
+            .enumerate()
+            .map(|(index, field)| match field.name {
+                Some(name) => (name.to_string(), field.schema),
+                None => (format!("_{}", index), field.schema),
+            })
+            .collect::<Vec<_>>();
+
+        output.push('\n');
+        write_fields(output, &variant_name, &fields)?;
+        members.push((variant.name().to_string(), variant_name));
+    }
+
+    let body = format!("{}_Body", name);
+    writeln!(output).unwrap();
+    writeln!(output, "typedef union {} {{", body).unwrap();
+    for (member, ty) in &members {
+        writeln!(output, "    {} {};", ty, member).unwrap();
+    }
+    writeln!(output, "}} {};", body).unwrap();
+
+    writeln!(output).unwrap();
+    writeln!(output, "typedef struct {} {{", name).unwrap();
+    writeln!(output, "    {} tag;", tag).unwrap();
+    writeln!(output, "    {} body;", body).unwrap();
+    writeln!(output, "}} {};", name).unwrap();
+
+    write_layout_checks(output, &schema.name, &name, &[], schema.layout.as_ref())
+}
+
+fn write_tag(output: &mut String, name: &str, schema: &Enum) -> Result<(), Error> {
+    let prefix = c_name(&schema.name);
+
+    match schema.repr {
+        Some(_) => writeln!(output, "enum {} {{", name).unwrap(),
+        None => writeln!(output, "typedef enum {} {{", name).unwrap(),
+    }
+
+    for variant in &schema.variants {
+        match variant {
+            Variant::Unit {
+                discriminant: Some(discriminant),
+                ..
+            } => writeln!(
+                output,
+                "    {}_{} = {},",
+                prefix,
+                variant.name(),
+                discriminant,
+            )
+            .unwrap(),
+
+            _ => writeln!(output, "    {}_{},", prefix, variant.name()).unwrap(),
+        }
+    }
+
+    match schema.repr {
+        Some(repr) => {
+            writeln!(output, "}};").unwrap();
+            writeln!(output, "typedef {} {};", c_repr(repr)?, name).unwrap();
+        }
+        None => writeln!(output, "}} {};", name).unwrap(),
+    }
+
+    Ok(())
+}
+
+fn write_fields(
+    output: &mut String,
+    name: &str,
+    fields: &[(String, &Schema)],
+) -> Result<(), Error> {
+    writeln!(output, "typedef struct {} {{", name).unwrap();
+    for (field, schema) in fields {
+        writeln!(output, "    {};", declaration(schema, field)?).unwrap();
+    }
+    writeln!(output, "}} {};", name).unwrap();
+
+    Ok(())
+}
+
+fn write_layout_checks(
+    output: &mut String,
+    type_name: &TypeName,
+    name: &str,
+    fields: &[(String, &Schema)],
+    layout: Option<&Layout>,
+) -> Result<(), Error> {
+    let layout = match layout {
+        Some(layout) => layout,
+        None => return Ok(()),
+    };
+
+    if fields.len() != layout.field_offsets.len() {
+        return Err(Error::InvalidLayout(type_name.clone()));
+    }
+
+    writeln!(output).unwrap();
+    writeln!(
+        output,
+        "_Static_assert(sizeof({0}) == {1}, \"size of {0}\");",
+        name, layout.size,
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "_Static_assert(_Alignof({0}) == {1}, \"alignment of {0}\");",
+        name, layout.align,
+    )
+    .unwrap();
+
+    for ((field, _), offset) in fields.iter().zip(&layout.field_offsets) {
+        writeln!(
+            output,
+            "_Static_assert(offsetof({0}, {1}) == {2}, \"offset of {0}.{1}\");",
+            name, field, offset,
+        )
+        .unwrap();
+    }
+
+    Ok(())
+}
+
+/// Returns the C declaration of a variable `name` of type `schema`.
+///
+/// Arrays are the reason this can't simply be `"{type} {name}"`: C puts the array
+/// length after the variable name, e.g. `[[u8; 2]; 3]` is declared as
+/// `uint8_t name[3][2]`.
+fn declaration(schema: &Schema, name: &str) -> Result<String, Error> {
+    match schema {
+        Schema::Array(array) => declaration(&array.element, &format!("{}[{}]", name, array.len)),
+        _ => Ok(format!("{} {}", c_type(schema)?, name)),
+    }
+}
+
+fn c_type(schema: &Schema) -> Result<String, Error> {
+    let ty = match schema {
+        Schema::Bool => "bool",
+        Schema::Char => "uint32_t",
+
+        Schema::I8 => "int8_t",
+        Schema::I16 => "int16_t",
+        Schema::I32 => "int32_t",
+        Schema::I64 => "int64_t",
+        Schema::ISize => "intptr_t",
+
+        Schema::U8 => "uint8_t",
+        Schema::U16 => "uint16_t",
+        Schema::U32 => "uint32_t",
+        Schema::U64 => "uint64_t",
+        Schema::USize => "uintptr_t",
+
+        Schema::F32 => "float",
+        Schema::F64 => "double",
+
+        Schema::Struct(_) | Schema::TupleStruct(_) | Schema::NewtypeStruct(_) | Schema::Enum(_) => {
+            return Ok(c_name(schema.type_name().unwrap()))
+        }
+
+        _ => return Err(Error::unsupported(schema)),
+    };
+
+    Ok(ty.into())
+}
+
+fn c_repr(repr: Primitive) -> Result<&'static str, Error> {
+    Ok(match repr {
+        Primitive::U8 => "uint8_t",
+        Primitive::U16 => "uint16_t",
+        Primitive::U32 => "uint32_t",
+        Primitive::U64 => "uint64_t",
+        Primitive::Usize => "uintptr_t",
+        Primitive::I8 => "int8_t",
+        Primitive::I16 => "int16_t",
+        Primitive::I32 => "int32_t",
+        Primitive::I64 => "int64_t",
+        Primitive::Isize => "intptr_t",
+
+        Primitive::U128 => return Err(Error::unsupported(&Schema::U128)),
+        Primitive::I128 => return Err(Error::unsupported(&Schema::I128)),
+    })
+}
+
+/// Returns the C identifier for a named type.
+///
+/// C has no generics, so the type parameters of generic types are appended to the
+/// name, e.g. `Wrapper<u32>` becomes `Wrapper_u32`.
+fn c_name(name: &TypeName) -> String {
+    let mut result = name
+        .name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+
+    for param in &name.type_params {
+        result.push('_');
+        result.push_str(&c_name(param));
+    }
+
+    result
+}
@@ -0,0 +1,101 @@
+//! Memoized schemas for `'static` types.
+//!
+//! Describing a type walks its entire type tree, which gets expensive for large
+//! message hierarchies that are described over and over, e.g. once per request in
+//! order to validate it. A [`SchemaCache`] describes each type once and then hands
+//! out the same [`Arc`] for every later lookup, so repeated lookups only cost a map
+//! lookup and a reference count increment:
+//!
+//! ```
+//! use schematic::cache::SchemaCache;
+//! use std::sync::Arc;
+//!
+//! let cache = SchemaCache::new();
+//! let first = cache.get::<Vec<u32>>();
+//! let second = cache.get::<Vec<u32>>();
+//! assert!(Arc::ptr_eq(&first, &second));
+//! ```
+//!
+//! Types are keyed by their [`TypeId`], which is why only `'static` types can be
+//! cached. [`describe_cached`] uses a cache shared by the whole program.
+//!
+//! [`SchemaCache`]: struct.SchemaCache.html
+//! [`describe_cached`]: ../fn.describe_cached.html
+//! [`Arc`]: https://doc.rust-lang.org/std/sync/struct.Arc.html
+//! [`TypeId`]: https://doc.rust-lang.org/std/any/struct.TypeId.html
+
+use crate::{Describe, Schema};
+use std::{
+    any::TypeId,
+    collections::BTreeMap,
+    sync::{Arc, RwLock, RwLockReadGuard},
+};
+
+/// A thread-safe map from types to their described schemas.
+#[derive(Debug, Default)]
+pub struct SchemaCache {
+    schemas: RwLock<BTreeMap<TypeId, Arc<Schema>>>,
+}
+
+impl SchemaCache {
+    /// Creates an empty cache.
+    ///
+    /// This is a `const fn`, so a cache can be stored in a `static`.
+    pub const fn new() -> Self {
+        SchemaCache {
+            schemas: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns the schema for `T`, describing it if it isn't cached yet.
+    ///
+    /// The lock is not held while describing `T`. If two threads describe the same
+    /// type at once, both get the schema that was cached first.
+    pub fn get<T: Describe + 'static>(&self) -> Arc<Schema> {
+        let id = TypeId::of::<T>();
+        if let Some(schema) = self.read().get(&id) {
+            return schema.clone();
+        }
+
+        let schema = Arc::new(crate::describe::<T>());
+        self.schemas
+            .write()
+            .unwrap_or_else(|error| error.into_inner())
+            .entry(id)
+            .or_insert(schema)
+            .clone()
+    }
+
+    /// Returns `true` if the schema for `T` has already been cached.
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.read().contains_key(&TypeId::of::<T>())
+    }
+
+    /// Returns the number of cached schemas.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Returns `true` if no schemas have been cached.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Removes every cached schema.
+    ///
+    /// Schemas that were already handed out stay valid.
+    pub fn clear(&self) {
+        self.schemas
+            .write()
+            .unwrap_or_else(|error| error.into_inner())
+            .clear();
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, BTreeMap<TypeId, Arc<Schema>>> {
+        // Schemas are only inserted once fully described, so a poisoned lock still
+        // holds a consistent map.
+        self.schemas
+            .read()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
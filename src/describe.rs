@@ -1,4 +1,5 @@
-use crate::{PrimitiveValue, TypeName};
+use crate::{Attributes, Constraints, FieldDefault, Layout, Primitive, PrimitiveValue, TypeName};
+use core::fmt::{Debug, Display};
 
 // A *data structure* that can be described by schematic.
 pub trait Describe: Sized {
@@ -10,10 +11,40 @@ pub trait Describe: Sized {
         D: Describer;
 }
 
+/// The error type of a [`Describer`].
+///
+/// Like `serde::ser::Error`, this lets `Describe` impls report that a type can't be
+/// described without knowing which describer they're used with:
+///
+/// ```
+/// use schematic::{Describe, DescribeError, Describer, TypeName};
+///
+/// pub struct Handle(*const u8);
+///
+/// impl Describe for Handle {
+///     fn type_name() -> TypeName {
+///         schematic::type_name!(Handle)
+///     }
+///
+///     fn describe<D: Describer>(_: D) -> Result<D::Ok, D::Error> {
+///         Err(D::Error::custom("raw pointers can't be described"))
+///     }
+/// }
+///
+/// let error = schematic::try_describe::<Option<Handle>>().unwrap_err();
+/// assert_eq!("raw pointers can't be described", error.to_string());
+/// ```
+///
+/// [`Describer`]: trait.Describer.html
+pub trait DescribeError: Sized + Debug + Display {
+    /// Creates an error with a message describing why the type can't be described.
+    fn custom<T: Display>(message: T) -> Self;
+}
+
 /// A *schema format* that can describe any data structure supported by schematic.
 pub trait Describer: Sized {
     type Ok;
-    type Error;
+    type Error: DescribeError;
 
     type DescribeStruct: DescribeStruct<Ok = Self::Ok, Error = Self::Error>;
     type DescribeTupleStruct: DescribeTupleStruct<Ok = Self::Ok, Error = Self::Error>;
@@ -89,6 +120,138 @@ pub trait Describer: Sized {
     fn describe_newtype_struct<T>(self, name: TypeName) -> Result<Self::Ok, Self::Error>
     where
         T: Describe;
+
+    /// Describes a placeholder for the type parameter `name` in the definition of a
+    /// generic type.
+    ///
+    /// This is only called by the placeholder types declared by [`type_param!`].
+    ///
+    /// [`type_param!`]: macro.type_param.html
+    fn describe_type_param(self, name: &'static str) -> Result<Self::Ok, Self::Error>;
+
+    /// Describes an enum along with additional information about the type.
+    ///
+    /// The default implementation ignores `info` and forwards to `describe_enum`.
+    fn describe_enum_with(
+        self,
+        name: TypeName,
+        info: TypeInfo,
+    ) -> Result<Self::DescribeEnum, Self::Error> {
+        let _ = info;
+        self.describe_enum(name)
+    }
+
+    /// Describes a unit struct along with additional information about the type.
+    ///
+    /// The default implementation ignores `info` and forwards to `describe_unit_struct`.
+    fn describe_unit_struct_with(
+        self,
+        name: TypeName,
+        info: TypeInfo,
+    ) -> Result<Self::Ok, Self::Error> {
+        let _ = info;
+        self.describe_unit_struct(name)
+    }
+
+    /// Describes a struct along with additional information about the type.
+    ///
+    /// The default implementation ignores `info` and forwards to `describe_struct`.
+    fn describe_struct_with(
+        self,
+        name: TypeName,
+        info: TypeInfo,
+    ) -> Result<Self::DescribeStruct, Self::Error> {
+        let _ = info;
+        self.describe_struct(name)
+    }
+
+    /// Describes a tuple struct along with additional information about the type.
+    ///
+    /// The default implementation ignores `info` and forwards to `describe_tuple_struct`.
+    fn describe_tuple_struct_with(
+        self,
+        name: TypeName,
+        info: TypeInfo,
+    ) -> Result<Self::DescribeTupleStruct, Self::Error> {
+        let _ = info;
+        self.describe_tuple_struct(name)
+    }
+
+    /// Describes a newtype struct along with additional information about the type.
+    ///
+    /// The default implementation ignores `info` and forwards to
+    /// `describe_newtype_struct`.
+    fn describe_newtype_struct_with<T>(
+        self,
+        name: TypeName,
+        info: TypeInfo,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Describe,
+    {
+        let _ = info;
+        self.describe_newtype_struct::<T>(name)
+    }
+}
+
+/// Additional information about a user-defined type.
+///
+/// Passed to the `describe_*_with` methods of [`Describer`]. None of this
+/// information affects the shape of the type, so describers are free to ignore it.
+///
+/// [`Describer`]: trait.Describer.html
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeInfo {
+    /// The documentation for the type, i.e. its `///` doc comment.
+    pub docs: Option<&'static str>,
+
+    /// Free-form metadata about the type, such as hints for editors.
+    ///
+    /// Only kept for structs with named fields.
+    pub attributes: Attributes,
+
+    /// Restrictions on the values of the inner type of a newtype struct.
+    ///
+    /// Ignored for other kinds of types.
+    pub constraints: Constraints,
+}
+
+/// Additional information about an enum variant.
+///
+/// See [`TypeInfo`] for more.
+///
+/// [`TypeInfo`]: struct.TypeInfo.html
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VariantInfo {
+    /// The documentation for the variant, i.e. its `///` doc comment.
+    pub docs: Option<&'static str>,
+
+    /// Free-form metadata about the variant, such as hints for editors.
+    pub attributes: Attributes,
+}
+
+/// Additional information about a named field.
+///
+/// See [`TypeInfo`] for more.
+///
+/// [`TypeInfo`]: struct.TypeInfo.html
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldInfo {
+    /// The documentation for the field, i.e. its `///` doc comment.
+    pub docs: Option<&'static str>,
+
+    /// Free-form metadata about the field, such as hints for editors.
+    pub attributes: Attributes,
+
+    /// Restrictions on the values of the field.
+    pub constraints: Constraints,
+
+    /// The default used when the field is missing, or `None` if it's required.
+    pub default: Option<FieldDefault>,
+
+    /// Whether the field may be left out when serializing, as with
+    /// `#[serde(skip_serializing_if = "...")]`.
+    pub skip_serializing_if: bool,
 }
 
 pub trait DescribeTuple {
@@ -104,6 +267,29 @@ pub trait DescribeStruct {
     type Error;
 
     fn describe_field<T: Describe>(&mut self, name: &'static str) -> Result<(), Self::Error>;
+
+    /// Describes a field along with additional information about the field.
+    ///
+    /// The default implementation ignores `info` and forwards to `describe_field`.
+    fn describe_field_with<T: Describe>(
+        &mut self,
+        name: &'static str,
+        info: FieldInfo,
+    ) -> Result<(), Self::Error> {
+        let _ = info;
+        self.describe_field::<T>(name)
+    }
+
+    /// Describes the in-memory layout of the type.
+    ///
+    /// Only types with a stable representation (e.g. `#[repr(C)]`) should describe
+    /// their layout. Describers that don't care about layout can ignore it, which is
+    /// what the default implementation does.
+    fn describe_layout(&mut self, layout: Layout) -> Result<(), Self::Error> {
+        let _ = layout;
+        Ok(())
+    }
+
     fn end(self) -> Result<Self::Ok, Self::Error>;
 }
 
@@ -112,6 +298,16 @@ pub trait DescribeTupleStruct {
     type Error;
 
     fn describe_element<T: Describe>(&mut self) -> Result<(), Self::Error>;
+
+    /// Describes the in-memory layout of the tuple struct, as for
+    /// [`DescribeStruct::describe_layout`].
+    ///
+    /// [`DescribeStruct::describe_layout`]: trait.DescribeStruct.html#method.describe_layout
+    fn describe_layout(&mut self, layout: Layout) -> Result<(), Self::Error> {
+        let _ = layout;
+        Ok(())
+    }
+
     fn end(self) -> Result<Self::Ok, Self::Error>;
 }
 
@@ -146,6 +342,64 @@ pub trait DescribeEnum {
         variant: Self::DescribeStructVariant,
     ) -> Result<(), Self::Error>;
 
+    /// Describes a unit variant along with additional information about the variant.
+    ///
+    /// The default implementation ignores `info` and forwards to
+    /// `describe_unit_variant`.
+    fn describe_unit_variant_with(
+        &mut self,
+        name: &'static str,
+        discriminant: Option<PrimitiveValue>,
+        info: VariantInfo,
+    ) -> Result<(), Self::Error> {
+        let _ = info;
+        self.describe_unit_variant(name, discriminant)
+    }
+
+    /// Starts a tuple variant along with additional information about the variant.
+    ///
+    /// The default implementation ignores `info` and forwards to
+    /// `start_tuple_variant`.
+    fn start_tuple_variant_with(
+        &mut self,
+        name: &'static str,
+        info: VariantInfo,
+    ) -> Result<Self::DescribeTupleVariant, Self::Error> {
+        let _ = info;
+        self.start_tuple_variant(name)
+    }
+
+    /// Starts a struct variant along with additional information about the variant.
+    ///
+    /// The default implementation ignores `info` and forwards to
+    /// `start_struct_variant`.
+    fn start_struct_variant_with(
+        &mut self,
+        name: &'static str,
+        info: VariantInfo,
+    ) -> Result<Self::DescribeStructVariant, Self::Error> {
+        let _ = info;
+        self.start_struct_variant(name)
+    }
+
+    /// Describes the explicit representation of the enum, as specified by a
+    /// `#[repr(u8)]`-style attribute.
+    ///
+    /// The default implementation ignores the representation.
+    fn describe_repr(&mut self, repr: Primitive) -> Result<(), Self::Error> {
+        let _ = repr;
+        Ok(())
+    }
+
+    /// Describes the in-memory layout of the enum, as for
+    /// [`DescribeStruct::describe_layout`].
+    ///
+    /// [`DescribeStruct::describe_layout`]: trait.DescribeStruct.html#method.describe_layout
+    fn describe_layout(&mut self, layout: Layout) -> Result<(), Self::Error> {
+        let _ = layout;
+        Ok(())
+    }
+
     fn end(self) -> Result<Self::Ok, Self::Error>;
 }
 
@@ -159,4 +413,16 @@ pub trait DescribeStructVariant {
     type Error;
 
     fn describe_field<T: Describe>(&mut self, name: &'static str) -> Result<(), Self::Error>;
+
+    /// Describes a field along with additional information about the field.
+    ///
+    /// The default implementation ignores `info` and forwards to `describe_field`.
+    fn describe_field_with<T: Describe>(
+        &mut self,
+        name: &'static str,
+        info: FieldInfo,
+    ) -> Result<(), Self::Error> {
+        let _ = info;
+        self.describe_field::<T>(name)
+    }
 }
@@ -0,0 +1,325 @@
+use crate::{NamedField, Primitive, Schema, TypeName, Variant};
+use core::fmt::{self, Display, Formatter};
+
+/// Renders the schema as Rust-like pseudo-syntax.
+///
+/// User-defined types are rendered as their definition, e.g. `struct Foo { bar:
+/// Vec<u32>, baz: Option<String> }`, and any other type as a reference to the type.
+/// Types used within a definition are referred to by name, unless the alternate
+/// flag (`{:#}`) is used, in which case user-defined types are expanded inline.
+///
+/// Module paths, documentation and layouts aren't included. Otherwise the syntax
+/// follows Rust, with a few additions to keep it unambiguous: tuple structs with a
+/// single element have a trailing comma to distinguish them from newtype structs,
+/// and the `repr` of enums is written as an attribute before the `enum` keyword.
+///
+/// # Examples
+///
+/// ```
+/// use schematic::{NamedField, Schema, Struct, TypeName};
+///
+/// let schema = Schema::Struct(Struct {
+///     name: TypeName::new("Config", "my_crate"),
+///     docs: None,
+///     attributes: Default::default(),
+///     fields: vec![
+///         NamedField::new("tags", schematic::describe::<Vec<String>>()),
+///         NamedField::new("point", schematic::describe::<Option<(u8, u8)>>()),
+///     ],
+///     layout: None,
+/// });
+///
+/// assert_eq!(
+///     "struct Config { tags: Vec<String>, point: Option<(u8, u8)> }",
+///     schema.to_string(),
+/// );
+/// ```
+impl Display for Schema {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let expand = f.alternate();
+        if self.type_name().is_some() {
+            write_definition(f, self, expand)
+        } else {
+            write_type(f, self, expand)
+        }
+    }
+}
+
+/// Renders the local name of the type, followed by its type parameters.
+///
+/// Tuples, arrays and slices are written with Rust syntax, e.g. `(u8, bool)` and
+/// `[u8; 4]`. The alternate flag (`{:#}`) writes the qualified name instead, see
+/// [`TypeName::qualified`].
+///
+/// # Examples
+///
+/// ```
+/// use schematic::TypeName;
+///
+/// let name = TypeName::generic("Page", "my_crate", vec![TypeName::new("UserId", "my_crate")]);
+/// assert_eq!("Page<UserId>", name.to_string());
+/// assert_eq!("my_crate::Page<my_crate::UserId>", format!("{:#}", name));
+/// ```
+///
+/// [`TypeName::qualified`]: struct.TypeName.html#method.qualified
+impl Display for TypeName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let params = |f: &mut Formatter<'_>, separator| {
+            for (index, param) in self.type_params.iter().enumerate() {
+                if index > 0 {
+                    f.write_str(separator)?;
+                }
+                Display::fmt(param, f)?;
+            }
+            Ok(())
+        };
+
+        if self.module.is_empty() {
+            if self.name == "()" {
+                f.write_str("(")?;
+                params(f, ", ")?;
+                if self.type_params.len() == 1 {
+                    f.write_str(",")?;
+                }
+                return f.write_str(")");
+            }
+
+            if self.name == "[]" && self.type_params.len() == 1 {
+                f.write_str("[")?;
+                params(f, "")?;
+                return f.write_str("]");
+            }
+
+            if let Some(len) = array_len(&self.name) {
+                if self.type_params.len() == 1 {
+                    f.write_str("[")?;
+                    params(f, "")?;
+                    return write!(f, "; {}]", len);
+                }
+            }
+        } else if f.alternate() {
+            write!(f, "{}::", self.module)?;
+        }
+
+        f.write_str(&self.name)?;
+        if !self.type_params.is_empty() {
+            f.write_str("<")?;
+            params(f, ", ")?;
+            f.write_str(">")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the length in the name of an array type, e.g. `[; 4]`.
+pub(crate) fn array_len(name: &str) -> Option<&str> {
+    name.strip_prefix("[; ")?
+        .strip_suffix(']')
+        .filter(|len| !len.is_empty() && len.bytes().all(|byte| byte.is_ascii_digit()))
+}
+
+impl Display for Primitive {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Primitive::U8 => "u8",
+            Primitive::U16 => "u16",
+            Primitive::U32 => "u32",
+            Primitive::U64 => "u64",
+            Primitive::U128 => "u128",
+            Primitive::Usize => "usize",
+            Primitive::I8 => "i8",
+            Primitive::I16 => "i16",
+            Primitive::I32 => "i32",
+            Primitive::I64 => "i64",
+            Primitive::I128 => "i128",
+            Primitive::Isize => "isize",
+        })
+    }
+}
+
+/// Renders a reference to a type, referring to user-defined types by name.
+#[cfg(feature = "std")]
+pub(crate) struct TypeRef<'a>(pub(crate) &'a Schema);
+
+#[cfg(feature = "std")]
+impl Display for TypeRef<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_type(f, self.0, false)
+    }
+}
+
+fn write_type(f: &mut Formatter<'_>, schema: &Schema, expand: bool) -> fmt::Result {
+    match schema {
+        Schema::Unit => f.write_str("()"),
+        Schema::Bool => f.write_str("bool"),
+        Schema::Char => f.write_str("char"),
+
+        Schema::I8 => f.write_str("i8"),
+        Schema::I16 => f.write_str("i16"),
+        Schema::I32 => f.write_str("i32"),
+        Schema::I64 => f.write_str("i64"),
+        Schema::I128 => f.write_str("i128"),
+        Schema::ISize => f.write_str("isize"),
+
+        Schema::U8 => f.write_str("u8"),
+        Schema::U16 => f.write_str("u16"),
+        Schema::U32 => f.write_str("u32"),
+        Schema::U64 => f.write_str("u64"),
+        Schema::U128 => f.write_str("u128"),
+        Schema::USize => f.write_str("usize"),
+
+        Schema::F32 => f.write_str("f32"),
+        Schema::F64 => f.write_str("f64"),
+
+        Schema::Str => f.write_str("&str"),
+        Schema::String(name) => f.write_str(&name.name),
+        Schema::TypeParam(name) => f.write_str(name),
+
+        Schema::Option(inner) => {
+            f.write_str("Option<")?;
+            write_type(f, inner, expand)?;
+            f.write_str(">")
+        }
+
+        Schema::Tuple(elements) => write_elements(f, elements, true, expand),
+
+        Schema::Array(array) => {
+            f.write_str("[")?;
+            write_type(f, &array.element, expand)?;
+            write!(f, "; {}]", array.len)
+        }
+
+        Schema::Slice(element) => {
+            f.write_str("&[")?;
+            write_type(f, element, expand)?;
+            f.write_str("]")
+        }
+
+        Schema::Seq(seq) => {
+            write!(f, "{}<", seq.name.name)?;
+            write_type(f, &seq.element, expand)?;
+            f.write_str(">")
+        }
+
+        Schema::Map(map) => {
+            write!(f, "{}<", map.name.name)?;
+            write_type(f, &map.key, expand)?;
+            f.write_str(", ")?;
+            write_type(f, &map.value, expand)?;
+            f.write_str(">")
+        }
+
+        Schema::UnitStruct(_)
+        | Schema::Struct(_)
+        | Schema::TupleStruct(_)
+        | Schema::NewtypeStruct(_)
+        | Schema::Enum(_) => {
+            if expand {
+                write_definition(f, schema, expand)
+            } else {
+                write!(f, "{}", schema.type_name().unwrap())
+            }
+        }
+    }
+}
+
+fn write_definition(f: &mut Formatter<'_>, schema: &Schema, expand: bool) -> fmt::Result {
+    match schema {
+        Schema::UnitStruct(schema) => write!(f, "struct {}", schema.name),
+
+        Schema::Struct(schema) => {
+            write!(f, "struct {} ", schema.name)?;
+            write_fields(f, &schema.fields, expand)
+        }
+
+        Schema::TupleStruct(schema) => {
+            write!(f, "struct {}", schema.name)?;
+            write_elements(f, &schema.elements, true, expand)
+        }
+
+        Schema::NewtypeStruct(schema) => {
+            write!(f, "struct {}(", schema.name)?;
+            write_type(f, &schema.inner, expand)?;
+            f.write_str(")")
+        }
+
+        Schema::Enum(schema) => {
+            if let Some(repr) = schema.repr {
+                write!(f, "#[repr({})] ", repr)?;
+            }
+            write!(f, "enum {} ", schema.name)?;
+
+            if schema.variants.is_empty() {
+                return f.write_str("{}");
+            }
+
+            f.write_str("{ ")?;
+            for (index, variant) in schema.variants.iter().enumerate() {
+                if index > 0 {
+                    f.write_str(", ")?;
+                }
+
+                f.write_str(variant.name())?;
+                match variant {
+                    Variant::Unit { discriminant, .. } => {
+                        if let Some(discriminant) = discriminant {
+                            write!(f, " = {}", discriminant)?;
+                        }
+                    }
+
+                    Variant::Tuple { elements, .. } => {
+                        write_elements(f, elements, false, expand)?;
+                    }
+
+                    Variant::Struct { fields, .. } => {
+                        f.write_str(" ")?;
+                        write_fields(f, fields, expand)?;
+                    }
+                }
+            }
+            f.write_str(" }")
+        }
+
+        _ => write_type(f, schema, expand),
+    }
+}
+
+fn write_fields(f: &mut Formatter<'_>, fields: &[NamedField], expand: bool) -> fmt::Result {
+    if fields.is_empty() {
+        return f.write_str("{}");
+    }
+
+    f.write_str("{ ")?;
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}: ", field.name)?;
+        write_type(f, &field.schema, expand)?;
+    }
+    f.write_str(" }")
+}
+
+/// Writes a parenthesized list of elements.
+///
+/// If `trailing_comma` is set, a single element is followed by a comma, as in
+/// Rust's syntax for tuples with one element.
+fn write_elements(
+    f: &mut Formatter<'_>,
+    elements: &[Schema],
+    trailing_comma: bool,
+    expand: bool,
+) -> fmt::Result {
+    f.write_str("(")?;
+    for (index, element) in elements.iter().enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        write_type(f, element, expand)?;
+    }
+    if trailing_comma && elements.len() == 1 {
+        f.write_str(",")?;
+    }
+    f.write_str(")")
+}
@@ -0,0 +1,493 @@
+//! Generate human-readable reference documentation for a type tree.
+//!
+//! The generated page has one section for each user-defined type reachable from a
+//! root schema, starting with the root itself. Each section lists the fields (or
+//! variants) of the type in a table along with their documentation, and any
+//! reference to another documented type links to that type's section. This is
+//! intended for generating things like a reference page for a config file format
+//! from a `build.rs` script:
+//!
+//! ```ignore
+//! let schema = schematic::describe::<Config>();
+//! let page = schematic::doc::generate(&schema, Format::Markdown);
+//! std::fs::write(out_dir.join("config.md"), page)?;
+//! ```
+
+use crate::{NamedField, Schema, TypeName, Variant};
+use std::fmt::Write;
+
+/// The output format of the generated documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+/// Generates a reference page documenting `schema` and every type it depends on.
+///
+/// # Examples
+///
+/// ```
+/// use schematic::{doc::Format, NamedField, Schema, Struct, TypeName};
+///
+/// let schema = Schema::Struct(Struct {
+///     name: TypeName::new("Config", "my_crate"),
+///     docs: None,
+///     attributes: Default::default(),
+///     fields: vec![NamedField::new("port", Schema::U16)],
+///     layout: None,
+/// });
+///
+/// let page = schematic::doc::generate(&schema, Format::Markdown);
+/// assert!(page.contains("| `port` | u16 | no |  |"));
+/// ```
+pub fn generate(schema: &Schema, format: Format) -> String {
+    let types = schema.named_types();
+    let sections = types.iter().rev().map(|ty| section(ty)).collect::<Vec<_>>();
+
+    match format {
+        Format::Markdown => render_markdown(&sections),
+        Format::Html => render_html(&sections),
+    }
+}
+
+/// Returns the anchor used to link to the documentation for `name`.
+///
+/// The anchor is derived from the fully-qualified name of the type, so it is stable
+/// across runs and unique within a page. Path separators become `-`, which can't
+/// appear in identifiers, `_` is doubled, and any other character that isn't ASCII
+/// alphanumeric, such as the `<` of a generic type, is escaped as `_{hex}_`. For
+/// example, `a::b_c::X` becomes `a-b__c-X`, and `a_b::c::X` becomes `a__b-c-X`.
+pub fn anchor(name: &TypeName) -> String {
+    let mut anchor = String::new();
+    for c in name.qualified().replace("::", "-").chars() {
+        match c {
+            '_' => anchor.push_str("__"),
+            '-' => anchor.push('-'),
+            c if c.is_ascii_alphanumeric() => anchor.push(c),
+            c => write!(anchor, "_{:x}_", c as u32).unwrap(),
+        }
+    }
+
+    anchor
+}
+
+/// Documentation for a single user-defined type, independent of output format.
+struct Section {
+    anchor: String,
+    title: String,
+    module: String,
+    summary: &'static str,
+    docs: Option<String>,
+    columns: &'static [&'static str],
+    rows: Vec<Vec<Cell>>,
+}
+
+/// The contents of a table cell.
+enum Cell {
+    /// An identifier, i.e. a field or variant name.
+    Code(String),
+
+    /// Plain text.
+    Text(String),
+
+    /// A type expression, which may link to other sections.
+    Type(Vec<Fragment>),
+}
+
+enum Fragment {
+    Text(String),
+    Link { text: String, anchor: String },
+}
+
+fn section(schema: &Schema) -> Section {
+    let name = schema.type_name().expect("only named types are documented");
+
+    let (summary, columns, rows): (_, &'static [&'static str], _) = match schema {
+        Schema::Struct(schema) => (
+            "Struct",
+            &["Field", "Type", "Optional", "Description"],
+            schema
+                .fields
+                .iter()
+                .map(|field| {
+                    vec![
+                        Cell::Code(field.name.to_string()),
+                        Cell::Type(type_ref(&field.schema)),
+                        Cell::Text(optional(field).into()),
+                        Cell::Text(one_line(field.docs.as_deref())),
+                    ]
+                })
+                .collect(),
+        ),
+
+        Schema::TupleStruct(schema) => (
+            "Tuple struct",
+            &["Index", "Type"],
+            schema
+                .elements
+                .iter()
+                .enumerate()
+                .map(|(index, schema)| {
+                    vec![Cell::Code(index.to_string()), Cell::Type(type_ref(schema))]
+                })
+                .collect(),
+        ),
+
+        Schema::NewtypeStruct(schema) => (
+            "Newtype struct",
+            &["Type"],
+            vec![vec![Cell::Type(type_ref(&schema.inner))]],
+        ),
+
+        Schema::UnitStruct(_) => ("Unit struct", &[], Vec::new()),
+
+        Schema::Enum(schema) => (
+            "Enum",
+            &["Variant", "Data", "Description"],
+            schema
+                .variants
+                .iter()
+                .map(|variant| {
+                    vec![
+                        Cell::Code(variant.name().into()),
+                        Cell::Type(variant_data(variant)),
+                        Cell::Text(one_line(variant.docs())),
+                    ]
+                })
+                .collect(),
+        ),
+
+        _ => unreachable!("only named types are documented"),
+    };
+
+    Section {
+        anchor: anchor(name),
+        title: name.to_string(),
+        module: name.module.to_string(),
+        summary,
+        docs: schema.docs().map(trim_docs),
+        columns,
+        rows,
+    }
+}
+
+/// Removes the leading whitespace that doc comments conventionally have on each line.
+pub(crate) fn trim_docs(docs: &str) -> String {
+    docs.lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Collapses documentation to a single line so that it fits in a table cell.
+fn one_line(docs: Option<&str>) -> String {
+    docs.unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Fields are listed as optional if they aren't [`NamedField::is_required`], so that
+/// the tables agree with every other exporter, whatever the type of the field.
+fn optional(field: &NamedField) -> &'static str {
+    if field.is_required() {
+        "no"
+    } else {
+        "yes"
+    }
+}
+
+fn variant_data(variant: &Variant) -> Vec<Fragment> {
+    let mut fragments = Vec::new();
+
+    match variant {
+        Variant::Unit {
+            discriminant: Some(discriminant),
+            ..
+        } => fragments.push(Fragment::Text(format!("= {}", discriminant))),
+
+        Variant::Unit { .. } => {}
+
+        Variant::Tuple { elements, .. } => {
+            fragments.push(Fragment::Text("(".into()));
+            for (index, element) in elements.iter().enumerate() {
+                if index > 0 {
+                    fragments.push(Fragment::Text(", ".into()));
+                }
+                fragments.extend(type_ref(element));
+            }
+            fragments.push(Fragment::Text(")".into()));
+        }
+
+        Variant::Struct { fields, .. } => {
+            fragments.push(Fragment::Text("{ ".into()));
+            for (index, field) in fields.iter().enumerate() {
+                if index > 0 {
+                    fragments.push(Fragment::Text(", ".into()));
+                }
+                fragments.push(Fragment::Text(format!("{}: ", field.name)));
+                fragments.extend(type_ref(&field.schema));
+            }
+            fragments.push(Fragment::Text(" }".into()));
+        }
+    }
+
+    fragments
+}
+
+/// Renders a reference to `schema` as Rust-like type syntax.
+///
+/// User-defined types become links to their section, everything else is rendered
+/// as plain text.
+fn type_ref(schema: &Schema) -> Vec<Fragment> {
+    let mut fragments = Vec::new();
+    write_type_ref(schema, &mut fragments);
+    fragments
+}
+
+fn write_type_ref(schema: &Schema, fragments: &mut Vec<Fragment>) {
+    let text = |text: &str| Fragment::Text(text.into());
+
+    match schema {
+        Schema::Unit => fragments.push(text("()")),
+        Schema::Bool => fragments.push(text("bool")),
+        Schema::Char => fragments.push(text("char")),
+
+        Schema::I8 => fragments.push(text("i8")),
+        Schema::I16 => fragments.push(text("i16")),
+        Schema::I32 => fragments.push(text("i32")),
+        Schema::I64 => fragments.push(text("i64")),
+        Schema::I128 => fragments.push(text("i128")),
+        Schema::ISize => fragments.push(text("isize")),
+
+        Schema::U8 => fragments.push(text("u8")),
+        Schema::U16 => fragments.push(text("u16")),
+        Schema::U32 => fragments.push(text("u32")),
+        Schema::U64 => fragments.push(text("u64")),
+        Schema::U128 => fragments.push(text("u128")),
+        Schema::USize => fragments.push(text("usize")),
+
+        Schema::F32 => fragments.push(text("f32")),
+        Schema::F64 => fragments.push(text("f64")),
+
+        Schema::Str => fragments.push(text("&str")),
+        Schema::String(name) => fragments.push(text(&name.name)),
+        Schema::TypeParam(name) => fragments.push(text(name)),
+
+        Schema::Option(inner) => {
+            fragments.push(text("Option<"));
+            write_type_ref(inner, fragments);
+            fragments.push(text(">"));
+        }
+
+        Schema::Tuple(elements) => {
+            fragments.push(text("("));
+            for (index, element) in elements.iter().enumerate() {
+                if index > 0 {
+                    fragments.push(text(", "));
+                }
+                write_type_ref(element, fragments);
+            }
+            fragments.push(text(")"));
+        }
+
+        Schema::Array(array) => {
+            fragments.push(text("["));
+            write_type_ref(&array.element, fragments);
+            fragments.push(Fragment::Text(format!("; {}]", array.len)));
+        }
+
+        Schema::Slice(element) => {
+            fragments.push(text("&["));
+            write_type_ref(element, fragments);
+            fragments.push(text("]"));
+        }
+
+        Schema::Seq(seq) => {
+            fragments.push(Fragment::Text(format!("{}<", seq.name.name)));
+            write_type_ref(&seq.element, fragments);
+            fragments.push(text(">"));
+        }
+
+        Schema::Map(map) => {
+            fragments.push(Fragment::Text(format!("{}<", map.name.name)));
+            write_type_ref(&map.key, fragments);
+            fragments.push(text(", "));
+            write_type_ref(&map.value, fragments);
+            fragments.push(text(">"));
+        }
+
+        Schema::UnitStruct(_)
+        | Schema::Struct(_)
+        | Schema::TupleStruct(_)
+        | Schema::NewtypeStruct(_)
+        | Schema::Enum(_) => {
+            let name = schema.type_name().unwrap();
+            fragments.push(Fragment::Link {
+                text: name.to_string(),
+                anchor: anchor(name),
+            });
+        }
+    }
+}
+
+fn render_markdown(sections: &[Section]) -> String {
+    let mut output = String::new();
+
+    for section in sections {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+
+        writeln!(output, "<a id=\"{}\"></a>", section.anchor).unwrap();
+        writeln!(output, "## {}", escape_markdown(&section.title)).unwrap();
+        writeln!(output).unwrap();
+        if section.module.is_empty() {
+            writeln!(output, "{}", section.summary).unwrap();
+        } else {
+            writeln!(output, "{} in `{}`", section.summary, section.module).unwrap();
+        }
+
+        if let Some(docs) = &section.docs {
+            writeln!(output).unwrap();
+            writeln!(output, "{}", docs).unwrap();
+        }
+
+        if section.columns.is_empty() {
+            continue;
+        }
+
+        writeln!(output).unwrap();
+        writeln!(output, "| {} |", section.columns.join(" | ")).unwrap();
+        writeln!(output, "|{}", " --- |".repeat(section.columns.len())).unwrap();
+        for row in &section.rows {
+            let cells = row.iter().map(markdown_cell).collect::<Vec<_>>();
+            writeln!(output, "| {} |", cells.join(" | ")).unwrap();
+        }
+    }
+
+    output
+}
+
+fn markdown_cell(cell: &Cell) -> String {
+    match cell {
+        Cell::Code(code) => format!("`{}`", code),
+        Cell::Text(text) => escape_markdown(text),
+        Cell::Type(fragments) => fragments
+            .iter()
+            .map(|fragment| match fragment {
+                Fragment::Text(text) => escape_markdown(text),
+                Fragment::Link { text, anchor } => {
+                    format!("[{}](#{})", escape_markdown(text), anchor)
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Escapes characters that would otherwise be treated as markup inside a table cell.
+fn escape_markdown(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '|' => result.push_str("\\|"),
+            '[' | ']' | '*' | '_' | '`' => {
+                result.push('\\');
+                result.push(c);
+            }
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+fn render_html(sections: &[Section]) -> String {
+    let mut output = String::new();
+
+    for section in sections {
+        writeln!(
+            output,
+            "<h2 id=\"{}\">{}</h2>",
+            section.anchor,
+            escape_html(&section.title),
+        )
+        .unwrap();
+
+        if section.module.is_empty() {
+            writeln!(output, "<p>{}</p>", section.summary).unwrap();
+        } else {
+            writeln!(
+                output,
+                "<p>{} in <code>{}</code></p>",
+                section.summary,
+                escape_html(&section.module),
+            )
+            .unwrap();
+        }
+
+        if let Some(docs) = &section.docs {
+            for paragraph in docs.split("\n\n") {
+                writeln!(output, "<p>{}</p>", escape_html(&one_line(Some(paragraph)))).unwrap();
+            }
+        }
+
+        if section.columns.is_empty() {
+            continue;
+        }
+
+        writeln!(output, "<table>").unwrap();
+        write!(output, "<tr>").unwrap();
+        for column in section.columns {
+            write!(output, "<th>{}</th>", column).unwrap();
+        }
+        writeln!(output, "</tr>").unwrap();
+
+        for row in &section.rows {
+            write!(output, "<tr>").unwrap();
+            for cell in row {
+                write!(output, "<td>{}</td>", html_cell(cell)).unwrap();
+            }
+            writeln!(output, "</tr>").unwrap();
+        }
+        writeln!(output, "</table>").unwrap();
+    }
+
+    output
+}
+
+fn html_cell(cell: &Cell) -> String {
+    match cell {
+        Cell::Code(code) => format!("<code>{}</code>", escape_html(code)),
+        Cell::Text(text) => escape_html(text),
+        Cell::Type(fragments) => fragments
+            .iter()
+            .map(|fragment| match fragment {
+                Fragment::Text(text) => escape_html(text),
+                Fragment::Link { text, anchor } => {
+                    format!("<a href=\"#{}\">{}</a>", anchor, escape_html(text))
+                }
+            })
+            .collect(),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '&' => result.push_str("&amp;"),
+            '"' => result.push_str("&quot;"),
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
@@ -0,0 +1,219 @@
+//! Export the dependency graph of a type tree in Graphviz DOT format.
+//!
+//! Each user-defined type reachable from the root schema becomes a record node
+//! listing its fields (or variants), with one port per field. Each field that
+//! refers to another user-defined type gets an edge from its port to that type's
+//! node. When the reference goes through a container such as `Option<T>`, a
+//! sequence, or a map, the edge is labeled with the containers along the way. The
+//! output can be rendered with the `dot` tool:
+//!
+//! ```text
+//! dot -Tsvg schema.dot -o schema.svg
+//! ```
+
+use crate::{display::TypeRef, Schema, TypeName, Variant};
+use std::fmt::Write;
+
+/// Generates a DOT graph of `schema` and every type it depends on.
+///
+/// # Examples
+///
+/// ```
+/// use schematic::{NamedField, Schema, Struct, TypeName};
+///
+/// let schema = Schema::Struct(Struct {
+///     name: TypeName::new("Point", "my_crate"),
+///     docs: None,
+///     attributes: Default::default(),
+///     fields: vec![NamedField::new("x", Schema::F32)],
+///     layout: None,
+/// });
+///
+/// let dot = schematic::dot::generate(&schema);
+/// assert!(dot.contains(r#""my_crate::Point" [label="{Point|<x> x: f32}"];"#));
+/// ```
+pub fn generate(schema: &Schema) -> String {
+    let mut output = String::from("digraph schema {\n    node [shape=record];\n");
+
+    for ty in schema.named_types() {
+        let name = ty.type_name().unwrap();
+        let id = node_id(name);
+
+        let ports = match ty {
+            Schema::Struct(schema) => schema
+                .fields
+                .iter()
+                .map(|field| Port {
+                    id: field.name.to_string(),
+                    label: format!("{}: {}", field.name, TypeRef(&field.schema)),
+                    targets: vec![&field.schema],
+                })
+                .collect(),
+
+            Schema::TupleStruct(schema) => schema
+                .elements
+                .iter()
+                .enumerate()
+                .map(|(index, element)| Port {
+                    id: index.to_string(),
+                    label: format!("{}: {}", index, TypeRef(element)),
+                    targets: vec![element],
+                })
+                .collect(),
+
+            Schema::NewtypeStruct(schema) => vec![Port {
+                id: "0".into(),
+                label: format!("0: {}", TypeRef(&schema.inner)),
+                targets: vec![&schema.inner],
+            }],
+
+            Schema::Enum(schema) => schema.variants.iter().map(variant_port).collect(),
+
+            _ => Vec::new(),
+        };
+
+        writeln!(output).unwrap();
+
+        let mut label = escape_label(&name.to_string());
+        for port in &ports {
+            write!(label, "|<{}> {}", port.id, escape_label(&port.label)).unwrap();
+        }
+        writeln!(output, "    \"{}\" [label=\"{{{}}}\"];", id, label).unwrap();
+
+        for port in &ports {
+            let mut edges = Vec::new();
+            for target in &port.targets {
+                collect_edges(target, &mut Vec::new(), &mut edges);
+            }
+
+            for (target, containers) in edges {
+                write!(
+                    output,
+                    "    \"{}\":{} -> \"{}\"",
+                    id,
+                    port.id,
+                    node_id(target)
+                )
+                .unwrap();
+                if !containers.is_empty() {
+                    write!(output, " [label=\"{}\"]", containers.join(" / ")).unwrap();
+                }
+                writeln!(output, ";").unwrap();
+            }
+        }
+    }
+
+    output.push_str("}\n");
+    output
+}
+
+/// A field or variant of a record node.
+struct Port<'a> {
+    id: String,
+    label: String,
+
+    /// The schemas of the fields, which determine the edges from the port.
+    targets: Vec<&'a Schema>,
+}
+
+fn variant_port(variant: &Variant) -> Port<'_> {
+    let name = variant.name();
+    let label = match variant {
+        Variant::Unit { .. } => name.to_string(),
+
+        Variant::Tuple { elements, .. } => {
+            let elements = elements
+                .iter()
+                .map(|element| TypeRef(element).to_string())
+                .collect::<Vec<_>>();
+            format!("{}({})", name, elements.join(", "))
+        }
+
+        Variant::Struct { fields, .. } => {
+            let fields = fields
+                .iter()
+                .map(|field| format!("{}: {}", field.name, TypeRef(&field.schema)))
+                .collect::<Vec<_>>();
+            format!("{} {{ {} }}", name, fields.join(", "))
+        }
+    };
+
+    Port {
+        id: name.to_string(),
+        label,
+        targets: variant.fields().map(|field| field.schema).collect(),
+    }
+}
+
+/// Finds the named types referenced by `schema` without going through another named type.
+///
+/// Each edge records the containers that were traversed to reach the named type,
+/// outermost first. Tuples are traversed transparently, since they're structural.
+fn collect_edges<'a>(
+    schema: &'a Schema,
+    containers: &mut Vec<&'static str>,
+    edges: &mut Vec<(&'a TypeName, Vec<&'static str>)>,
+) {
+    let mut nested = |container, schema| {
+        containers.push(container);
+        collect_edges(schema, containers, edges);
+        containers.pop();
+    };
+
+    match schema {
+        Schema::Option(inner) => nested("Option", inner),
+        Schema::Array(array) => nested("Array", &array.element),
+        Schema::Slice(element) => nested("Slice", element),
+        Schema::Seq(seq) => nested("Seq", &seq.element),
+        Schema::Map(map) => {
+            nested("Map key", &map.key);
+            nested("Map value", &map.value);
+        }
+
+        Schema::Tuple(elements) => {
+            for element in elements {
+                collect_edges(element, containers, edges);
+            }
+        }
+
+        _ => {
+            if let Some(name) = schema.type_name() {
+                edges.push((name, containers.clone()));
+            }
+        }
+    }
+}
+
+/// Returns the fully-qualified name of the type, which uniquely identifies its node.
+fn node_id(name: &TypeName) -> String {
+    let mut result = String::new();
+    if !name.module.is_empty() {
+        write!(result, "{}::", name.module).unwrap();
+    }
+    result.push_str(&name.name);
+
+    if !name.type_params.is_empty() {
+        let params = name.type_params.iter().map(node_id).collect::<Vec<_>>();
+        write!(result, "<{}>", params.join(", ")).unwrap();
+    }
+
+    escape_id(&result)
+}
+
+/// Escapes a string for use inside a double-quoted DOT identifier.
+fn escape_id(id: &str) -> String {
+    id.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Escapes the characters that have special meaning in record labels.
+fn escape_label(label: &str) -> String {
+    let mut result = String::with_capacity(label.len());
+    for c in label.chars() {
+        if let '{' | '}' | '|' | '<' | '>' | '"' | '\\' = c {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+
+    result
+}
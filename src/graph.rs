@@ -0,0 +1,693 @@
+//! A compact, shared representation of many schemas.
+//!
+//! A [`Schema`] is a tree that owns every part of it: each field that refers to
+//! `User` holds its own copy of the `User` definition, and every name and doc
+//! comment is a separate string. That's convenient for walking a single schema,
+//! but wasteful when holding thousands of schemas at once, e.g. in a registry.
+//!
+//! A [`SchemaGraph`] stores schemas as nodes in an arena instead:
+//!
+//! * Every distinct node is stored once and referred to by its [`NodeId`], so
+//!   identical subtrees, including named type definitions, are shared by every
+//!   schema that uses them.
+//! * Strings and type names are interned and referred to by [`StrId`] and
+//!   [`NameId`].
+//!
+//! Schemas are inserted with [`SchemaGraph::insert`] and converted back with
+//! [`SchemaGraph::schema`]. The exporters work on [`Schema`], so a schema is
+//! converted back when it's exported:
+//!
+//! ```
+//! use schematic::graph::SchemaGraph;
+//!
+//! let mut graph = SchemaGraph::new();
+//! let first = graph.insert(&schematic::describe::<Vec<Option<u32>>>());
+//! let second = graph.insert(&schematic::describe::<Option<u32>>());
+//!
+//! // `Option<u32>` is only stored once.
+//! assert_eq!(3, graph.len());
+//!
+//! let schema = graph.schema(first);
+//! assert_eq!(schematic::describe::<Vec<Option<u32>>>(), schema);
+//! println!("{}", schematic::dot::generate(&schema));
+//! # let _ = second;
+//! ```
+//!
+//! [`Schema`]: ../enum.Schema.html
+//! [`SchemaGraph`]: struct.SchemaGraph.html
+//! [`SchemaGraph::insert`]: struct.SchemaGraph.html#method.insert
+//! [`SchemaGraph::schema`]: struct.SchemaGraph.html#method.schema
+//! [`NodeId`]: struct.NodeId.html
+//! [`StrId`]: struct.StrId.html
+//! [`NameId`]: struct.NameId.html
+
+use crate::{schema::*, TypeName};
+use std::{
+    borrow::Cow,
+    collections::{hash_map::RandomState, HashMap},
+    convert::TryFrom,
+    hash::{BuildHasher, Hash},
+};
+
+/// Identifies a node in a [`SchemaGraph`].
+///
+/// IDs are only meaningful for the graph that returned them.
+///
+/// [`SchemaGraph`]: struct.SchemaGraph.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(u32);
+
+/// Identifies an interned string in a [`SchemaGraph`].
+///
+/// [`SchemaGraph`]: struct.SchemaGraph.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StrId(u32);
+
+/// Identifies an interned [`TypeName`] in a [`SchemaGraph`].
+///
+/// [`TypeName`]: ../struct.TypeName.html
+/// [`SchemaGraph`]: struct.SchemaGraph.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NameId(u32);
+
+/// A single node in a [`SchemaGraph`].
+///
+/// Mirrors [`Schema`], with child schemas, strings and type names replaced by IDs.
+///
+/// [`SchemaGraph`]: struct.SchemaGraph.html
+/// [`Schema`]: ../enum.Schema.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Node {
+    Unit,
+    Bool,
+    Char,
+
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    ISize,
+
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    USize,
+
+    F32,
+    F64,
+
+    Str,
+    String(NameId),
+
+    Option(NodeId),
+
+    Tuple(Vec<NodeId>),
+
+    Array {
+        element: NodeId,
+        len: usize,
+    },
+    Slice(NodeId),
+    Seq {
+        name: NameId,
+        element: NodeId,
+        len: Option<usize>,
+    },
+
+    Map {
+        name: NameId,
+        key: NodeId,
+        value: NodeId,
+    },
+
+    UnitStruct {
+        name: NameId,
+        docs: Option<StrId>,
+    },
+    Struct {
+        name: NameId,
+        docs: Option<StrId>,
+        attributes: Attributes,
+        fields: Vec<NodeField>,
+        layout: Option<Layout>,
+    },
+    TupleStruct {
+        name: NameId,
+        docs: Option<StrId>,
+        elements: Vec<NodeId>,
+        layout: Option<Layout>,
+    },
+    NewtypeStruct {
+        name: NameId,
+        docs: Option<StrId>,
+        inner: NodeId,
+        constraints: Constraints,
+    },
+
+    Enum {
+        name: NameId,
+        docs: Option<StrId>,
+        repr: Option<Primitive>,
+        variants: Vec<NodeVariant>,
+        layout: Option<Layout>,
+    },
+
+    TypeParam(StrId),
+}
+
+impl Node {
+    /// Returns the type name for user-defined types.
+    pub fn type_name(&self) -> Option<NameId> {
+        match self {
+            Node::UnitStruct { name, .. }
+            | Node::Struct { name, .. }
+            | Node::TupleStruct { name, .. }
+            | Node::NewtypeStruct { name, .. }
+            | Node::Enum { name, .. } => Some(*name),
+
+            _ => None,
+        }
+    }
+}
+
+/// A [`NamedField`] in a [`SchemaGraph`].
+///
+/// [`NamedField`]: ../struct.NamedField.html
+/// [`SchemaGraph`]: struct.SchemaGraph.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NodeField {
+    pub name: StrId,
+    pub docs: Option<StrId>,
+    pub attributes: Attributes,
+    pub schema: NodeId,
+    pub constraints: Constraints,
+    pub default: Option<FieldDefault>,
+    pub skip_serializing_if: bool,
+}
+
+/// A [`Variant`] in a [`SchemaGraph`].
+///
+/// [`Variant`]: ../enum.Variant.html
+/// [`SchemaGraph`]: struct.SchemaGraph.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NodeVariant {
+    Unit {
+        name: StrId,
+        docs: Option<StrId>,
+        attributes: Attributes,
+        discriminant: Option<PrimitiveValue>,
+    },
+
+    Struct {
+        name: StrId,
+        docs: Option<StrId>,
+        attributes: Attributes,
+        fields: Vec<NodeField>,
+    },
+
+    Tuple {
+        name: StrId,
+        docs: Option<StrId>,
+        attributes: Attributes,
+        elements: Vec<NodeId>,
+    },
+}
+
+/// An arena of schemas with shared nodes and interned strings.
+///
+/// See the [module documentation](index.html) for more.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaGraph {
+    nodes: Interner<Node>,
+    strings: Interner<Cow<'static, str>>,
+    names: Interner<TypeName>,
+
+    /// The first definition inserted for each type name.
+    definitions: HashMap<NameId, NodeId>,
+}
+
+impl SchemaGraph {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds `schema` to the graph and returns the ID of its root node.
+    ///
+    /// Parts of `schema` that are already in the graph are reused, so inserting the
+    /// same schema twice returns the same ID.
+    pub fn insert(&mut self, schema: &Schema) -> NodeId {
+        let node = match schema {
+            Schema::Unit => Node::Unit,
+            Schema::Bool => Node::Bool,
+            Schema::Char => Node::Char,
+
+            Schema::I8 => Node::I8,
+            Schema::I16 => Node::I16,
+            Schema::I32 => Node::I32,
+            Schema::I64 => Node::I64,
+            Schema::I128 => Node::I128,
+            Schema::ISize => Node::ISize,
+
+            Schema::U8 => Node::U8,
+            Schema::U16 => Node::U16,
+            Schema::U32 => Node::U32,
+            Schema::U64 => Node::U64,
+            Schema::U128 => Node::U128,
+            Schema::USize => Node::USize,
+
+            Schema::F32 => Node::F32,
+            Schema::F64 => Node::F64,
+
+            Schema::Str => Node::Str,
+            Schema::String(name) => Node::String(self.intern_name(name)),
+
+            Schema::Option(inner) => Node::Option(self.insert(inner)),
+
+            Schema::Tuple(elements) => Node::Tuple(self.insert_all(elements)),
+
+            Schema::Array(array) => Node::Array {
+                element: self.insert(&array.element),
+                len: array.len,
+            },
+            Schema::Slice(element) => Node::Slice(self.insert(element)),
+            Schema::Seq(seq) => Node::Seq {
+                name: self.intern_name(&seq.name),
+                element: self.insert(&seq.element),
+                len: seq.len,
+            },
+
+            Schema::Map(map) => Node::Map {
+                name: self.intern_name(&map.name),
+                key: self.insert(&map.key),
+                value: self.insert(&map.value),
+            },
+
+            Schema::UnitStruct(unit) => Node::UnitStruct {
+                name: self.intern_name(&unit.name),
+                docs: self.intern_docs(&unit.docs),
+            },
+            Schema::Struct(struct_) => Node::Struct {
+                name: self.intern_name(&struct_.name),
+                docs: self.intern_docs(&struct_.docs),
+                attributes: struct_.attributes.clone(),
+                fields: self.insert_fields(&struct_.fields),
+                layout: struct_.layout.clone(),
+            },
+            Schema::TupleStruct(tuple) => Node::TupleStruct {
+                name: self.intern_name(&tuple.name),
+                docs: self.intern_docs(&tuple.docs),
+                elements: self.insert_all(&tuple.elements),
+                layout: tuple.layout.clone(),
+            },
+            Schema::NewtypeStruct(newtype) => Node::NewtypeStruct {
+                name: self.intern_name(&newtype.name),
+                docs: self.intern_docs(&newtype.docs),
+                inner: self.insert(&newtype.inner),
+                constraints: newtype.constraints.clone(),
+            },
+
+            Schema::Enum(enum_) => Node::Enum {
+                name: self.intern_name(&enum_.name),
+                docs: self.intern_docs(&enum_.docs),
+                repr: enum_.repr,
+                variants: enum_
+                    .variants
+                    .iter()
+                    .map(|variant| self.insert_variant(variant))
+                    .collect(),
+                layout: enum_.layout.clone(),
+            },
+
+            Schema::TypeParam(name) => Node::TypeParam(self.intern_str(name)),
+        };
+
+        let name = node.type_name();
+        let id = NodeId(self.nodes.intern(node));
+        if let Some(name) = name {
+            self.definitions.entry(name).or_insert(id);
+        }
+        id
+    }
+
+    /// Rebuilds the schema rooted at `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` doesn't belong to this graph.
+    pub fn schema(&self, id: NodeId) -> Schema {
+        match self.node(id) {
+            Node::Unit => Schema::Unit,
+            Node::Bool => Schema::Bool,
+            Node::Char => Schema::Char,
+
+            Node::I8 => Schema::I8,
+            Node::I16 => Schema::I16,
+            Node::I32 => Schema::I32,
+            Node::I64 => Schema::I64,
+            Node::I128 => Schema::I128,
+            Node::ISize => Schema::ISize,
+
+            Node::U8 => Schema::U8,
+            Node::U16 => Schema::U16,
+            Node::U32 => Schema::U32,
+            Node::U64 => Schema::U64,
+            Node::U128 => Schema::U128,
+            Node::USize => Schema::USize,
+
+            Node::F32 => Schema::F32,
+            Node::F64 => Schema::F64,
+
+            Node::Str => Schema::Str,
+            Node::String(name) => Schema::String(self.type_name(*name).clone()),
+
+            Node::Option(inner) => Schema::Option(Box::new(self.schema(*inner))),
+
+            Node::Tuple(elements) => Schema::Tuple(self.schemas(elements)),
+
+            Node::Array { element, len } => Schema::Array(Box::new(Array {
+                element: self.schema(*element),
+                len: *len,
+            })),
+            Node::Slice(element) => Schema::Slice(Box::new(self.schema(*element))),
+            Node::Seq { name, element, len } => Schema::Seq(Box::new(Sequence {
+                name: self.type_name(*name).clone(),
+                element: self.schema(*element),
+                len: *len,
+            })),
+
+            Node::Map { name, key, value } => Schema::Map(Box::new(Map {
+                name: self.type_name(*name).clone(),
+                key: self.schema(*key),
+                value: self.schema(*value),
+            })),
+
+            Node::UnitStruct { name, docs } => Schema::UnitStruct(UnitStruct {
+                name: self.type_name(*name).clone(),
+                docs: self.docs(*docs),
+            }),
+            Node::Struct {
+                name,
+                docs,
+                attributes,
+                fields,
+                layout,
+            } => Schema::Struct(Struct {
+                name: self.type_name(*name).clone(),
+                docs: self.docs(*docs),
+                attributes: attributes.clone(),
+                fields: self.fields(fields),
+                layout: layout.clone(),
+            }),
+            Node::TupleStruct {
+                name,
+                docs,
+                elements,
+                layout,
+            } => Schema::TupleStruct(TupleStruct {
+                name: self.type_name(*name).clone(),
+                docs: self.docs(*docs),
+                elements: self.schemas(elements),
+                layout: layout.clone(),
+            }),
+            Node::NewtypeStruct {
+                name,
+                docs,
+                inner,
+                constraints,
+            } => Schema::NewtypeStruct(Box::new(NewtypeStruct {
+                name: self.type_name(*name).clone(),
+                docs: self.docs(*docs),
+                inner: self.schema(*inner),
+                constraints: constraints.clone(),
+            })),
+
+            Node::Enum {
+                name,
+                docs,
+                repr,
+                variants,
+                layout,
+            } => Schema::Enum(Enum {
+                name: self.type_name(*name).clone(),
+                docs: self.docs(*docs),
+                repr: *repr,
+                variants: variants
+                    .iter()
+                    .map(|variant| self.variant(variant))
+                    .collect(),
+                layout: layout.clone(),
+            }),
+
+            Node::TypeParam(name) => Schema::TypeParam(self.strings.get(name.0).clone()),
+        }
+    }
+
+    /// Returns the node with the given ID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` doesn't belong to this graph.
+    pub fn node(&self, id: NodeId) -> &Node {
+        self.nodes.get(id.0)
+    }
+
+    /// Returns the interned string with the given ID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` doesn't belong to this graph.
+    pub fn str(&self, id: StrId) -> &str {
+        self.strings.get(id.0)
+    }
+
+    /// Returns the interned type name with the given ID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` doesn't belong to this graph.
+    pub fn type_name(&self, id: NameId) -> &TypeName {
+        self.names.get(id.0)
+    }
+
+    /// Returns the node defining the named type `name`, if it's in the graph.
+    ///
+    /// If different definitions were inserted under the same name, the one inserted
+    /// first is returned.
+    pub fn definition(&self, name: &TypeName) -> Option<NodeId> {
+        let name = NameId(self.names.find(name)?);
+        self.definitions.get(&name).copied()
+    }
+
+    /// Returns the number of distinct nodes in the graph.
+    pub fn len(&self) -> usize {
+        self.nodes.items.len()
+    }
+
+    /// Returns `true` if nothing has been inserted into the graph.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.items.is_empty()
+    }
+
+    fn insert_all(&mut self, schemas: &[Schema]) -> Vec<NodeId> {
+        schemas.iter().map(|schema| self.insert(schema)).collect()
+    }
+
+    fn insert_fields(&mut self, fields: &[NamedField]) -> Vec<NodeField> {
+        fields
+            .iter()
+            .map(|field| NodeField {
+                name: self.intern_str(&field.name),
+                docs: self.intern_docs(&field.docs),
+                attributes: field.attributes.clone(),
+                schema: self.insert(&field.schema),
+                constraints: field.constraints.clone(),
+                default: field.default.clone(),
+                skip_serializing_if: field.skip_serializing_if,
+            })
+            .collect()
+    }
+
+    fn insert_variant(&mut self, variant: &Variant) -> NodeVariant {
+        match variant {
+            Variant::Unit {
+                name,
+                docs,
+                attributes,
+                discriminant,
+            } => NodeVariant::Unit {
+                name: self.intern_str(name),
+                docs: self.intern_docs(docs),
+                attributes: attributes.clone(),
+                discriminant: *discriminant,
+            },
+
+            Variant::Struct {
+                name,
+                docs,
+                attributes,
+                fields,
+            } => NodeVariant::Struct {
+                name: self.intern_str(name),
+                docs: self.intern_docs(docs),
+                attributes: attributes.clone(),
+                fields: self.insert_fields(fields),
+            },
+
+            Variant::Tuple {
+                name,
+                docs,
+                attributes,
+                elements,
+            } => NodeVariant::Tuple {
+                name: self.intern_str(name),
+                docs: self.intern_docs(docs),
+                attributes: attributes.clone(),
+                elements: self.insert_all(elements),
+            },
+        }
+    }
+
+    #[allow(clippy::ptr_arg)] // Interning a `Cow` keeps borrowed strings borrowed.
+    fn intern_str(&mut self, string: &Cow<'static, str>) -> StrId {
+        StrId(self.strings.intern_ref(string))
+    }
+
+    fn intern_docs(&mut self, docs: &Option<Cow<'static, str>>) -> Option<StrId> {
+        docs.as_ref().map(|docs| self.intern_str(docs))
+    }
+
+    fn intern_name(&mut self, name: &TypeName) -> NameId {
+        NameId(self.names.intern_ref(name))
+    }
+
+    fn schemas(&self, ids: &[NodeId]) -> Vec<Schema> {
+        ids.iter().map(|id| self.schema(*id)).collect()
+    }
+
+    fn docs(&self, docs: Option<StrId>) -> Option<Cow<'static, str>> {
+        docs.map(|docs| self.strings.get(docs.0).clone())
+    }
+
+    fn fields(&self, fields: &[NodeField]) -> Vec<NamedField> {
+        fields
+            .iter()
+            .map(|field| NamedField {
+                name: self.strings.get(field.name.0).clone(),
+                docs: self.docs(field.docs),
+                attributes: field.attributes.clone(),
+                schema: self.schema(field.schema),
+                constraints: field.constraints.clone(),
+                default: field.default.clone(),
+                skip_serializing_if: field.skip_serializing_if,
+            })
+            .collect()
+    }
+
+    fn variant(&self, variant: &NodeVariant) -> Variant {
+        match variant {
+            NodeVariant::Unit {
+                name,
+                docs,
+                attributes,
+                discriminant,
+            } => Variant::Unit {
+                name: self.strings.get(name.0).clone(),
+                docs: self.docs(*docs),
+                attributes: attributes.clone(),
+                discriminant: *discriminant,
+            },
+
+            NodeVariant::Struct {
+                name,
+                docs,
+                attributes,
+                fields,
+            } => Variant::Struct {
+                name: self.strings.get(name.0).clone(),
+                docs: self.docs(*docs),
+                attributes: attributes.clone(),
+                fields: self.fields(fields),
+            },
+
+            NodeVariant::Tuple {
+                name,
+                docs,
+                attributes,
+                elements,
+            } => Variant::Tuple {
+                name: self.strings.get(name.0).clone(),
+                docs: self.docs(*docs),
+                attributes: attributes.clone(),
+                elements: self.schemas(elements),
+            },
+        }
+    }
+}
+
+/// Stores each distinct value once and hands out stable indices for them.
+///
+/// Values are only stored in `items`. The map goes from the hash of a value to the
+/// indices of the values with that hash, so lookups compare against `items`.
+#[derive(Debug, Clone)]
+struct Interner<T> {
+    items: Vec<T>,
+    buckets: HashMap<u64, Vec<u32>>,
+    hasher: RandomState,
+}
+
+impl<T> Default for Interner<T> {
+    fn default() -> Self {
+        Interner {
+            items: Vec::new(),
+            buckets: HashMap::new(),
+            hasher: RandomState::new(),
+        }
+    }
+}
+
+impl<T: Eq + Hash> Interner<T> {
+    fn get(&self, index: u32) -> &T {
+        &self.items[index as usize]
+    }
+
+    fn find(&self, item: &T) -> Option<u32> {
+        self.buckets
+            .get(&self.hash(item))?
+            .iter()
+            .copied()
+            .find(|&index| self.get(index) == item)
+    }
+
+    fn intern(&mut self, item: T) -> u32 {
+        let hash = self.hash(&item);
+        let items = &self.items;
+        let bucket = self.buckets.entry(hash).or_default();
+        if let Some(&index) = bucket.iter().find(|&&index| items[index as usize] == item) {
+            return index;
+        }
+
+        let index = u32::try_from(items.len()).expect("too many items in schema graph");
+        bucket.push(index);
+        self.items.push(item);
+        index
+    }
+
+    /// Like `intern`, but only clones `item` if it isn't interned yet.
+    fn intern_ref(&mut self, item: &T) -> u32
+    where
+        T: Clone,
+    {
+        match self.find(item) {
+            Some(index) => index,
+            None => self.intern(item.clone()),
+        }
+    }
+
+    fn hash(&self, item: &T) -> u64 {
+        self.hasher.hash_one(item)
+    }
+}
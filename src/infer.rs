@@ -0,0 +1,458 @@
+//! Infer a schema from sample JSON values.
+//!
+//! When all you have is example payloads, [`Inferrer`] can reverse-engineer a
+//! schema that describes all of them. Each sample is merged into what has been
+//! observed so far:
+//!
+//! * Integers widen to the narrowest integer type that holds every observed value,
+//!   e.g. `1` and `300` give `u16`, while `-1` and `300` give `i16`. Integers mixed
+//!   with floating-point numbers give `f64`.
+//! * Fields that are missing from some objects, or that are sometimes `null`,
+//!   become `Option`. Fields that are missing from some objects also default to
+//!   `None` and are marked as [`skip_serializing_if`].
+//! * The elements of every array at the same position are merged into a single
+//!   element type.
+//! * Objects with keys that don't look like field names (e.g. `"42"` or
+//!   `"en.title"`), or with more than [`MAX_FIELDS`] distinct keys, are treated as
+//!   maps with string keys, provided that all of their values have the same shape.
+//!
+//! Objects are named after the path that leads to them, e.g. the `address` field
+//! of `User` becomes a struct named `UserAddress`. The resulting schema can be fed
+//! into any of the generators, e.g. [`rust::generate`] to produce Rust types for
+//! the payloads.
+//!
+//! Values of incompatible shapes at the same position, such as a string in one
+//! sample and a number in another, can't be described by a single schema and are
+//! reported as an [`Error`].
+//!
+//! [`Inferrer`]: struct.Inferrer.html
+//! [`MAX_FIELDS`]: constant.MAX_FIELDS.html
+//! [`rust::generate`]: ../rust/fn.generate.html
+//! [`Error`]: enum.Error.html
+//! [`skip_serializing_if`]: ../struct.NamedField.html#structfield.skip_serializing_if
+
+use crate::{
+    json_schema::upper_camel_case, FieldDefault, Map, NamedField, Schema, Sequence, Struct,
+    TypeName,
+};
+use serde_json::Value;
+use std::fmt;
+
+/// The number of distinct keys above which an object is treated as a map.
+pub const MAX_FIELDS: usize = 32;
+
+/// Infers a schema named `name` that describes all of `samples`.
+///
+/// # Examples
+///
+/// ```
+/// use schematic::Schema;
+/// use serde_json::json;
+///
+/// let samples = [
+///     json!({ "id": 1, "name": "Alice" }),
+///     json!({ "id": 1000, "name": null }),
+/// ];
+///
+/// let schema = schematic::infer::infer("User", &samples).unwrap();
+/// let user = schema.as_struct().unwrap();
+/// assert_eq!(Schema::U16, user.fields[0].schema);
+/// assert_eq!(schematic::describe::<Option<String>>(), user.fields[1].schema);
+/// ```
+pub fn infer<'a, I>(name: &str, samples: I) -> Result<Schema, Error>
+where
+    I: IntoIterator<Item = &'a Value>,
+{
+    let mut inferrer = Inferrer::new(name);
+    for sample in samples {
+        inferrer.add(sample)?;
+    }
+
+    Ok(inferrer.schema())
+}
+
+/// Incrementally infers a schema from samples.
+///
+/// Use this instead of [`infer`] when samples arrive one at a time, e.g. while
+/// streaming through a log of payloads.
+///
+/// [`infer`]: fn.infer.html
+#[derive(Debug, Clone)]
+pub struct Inferrer {
+    name: String,
+    samples: usize,
+    shape: Shape,
+}
+
+impl Inferrer {
+    /// Creates an inferrer for a schema named `name`.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            samples: 0,
+            shape: Shape::default(),
+        }
+    }
+
+    /// Merges `sample` into the observed shape.
+    ///
+    /// If the sample conflicts with a previous sample, the error is returned and
+    /// the observed shape is left as it was.
+    pub fn add(&mut self, sample: &Value) -> Result<(), Error> {
+        let mut shape = self.shape.clone();
+        observe(&mut shape, sample, &mut String::new()).map_err(|conflict| Error::Conflict {
+            sample: self.samples,
+            pointer: conflict.pointer,
+            expected: conflict.expected,
+            found: conflict.found,
+        })?;
+
+        self.shape = shape;
+        self.samples += 1;
+        Ok(())
+    }
+
+    /// Returns the schema describing every sample added so far.
+    ///
+    /// Values that were only ever observed as `null`, or elements of arrays that
+    /// were always empty, have no known type and are described as `()`.
+    pub fn schema(&self) -> Schema {
+        to_schema(&self.shape, &self.name)
+    }
+}
+
+/// An error inferring a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A value in a sample has a different shape than values previously observed at
+    /// the same position.
+    Conflict {
+        /// The index of the sample containing the value.
+        sample: usize,
+
+        /// The JSON Pointer to the value within the sample.
+        pointer: String,
+
+        /// The kind of value previously observed, e.g. `"string"`.
+        expected: &'static str,
+
+        /// The kind of value found.
+        found: &'static str,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Conflict {
+                sample,
+                pointer,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected {} but found {} at `{}` in sample {}",
+                expected, found, pointer, sample,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The merged shape of all values observed at one position.
+#[derive(Debug, Clone, Default)]
+struct Shape {
+    /// Whether `null` was observed.
+    nullable: bool,
+    kind: Kind,
+}
+
+#[derive(Debug, Clone, Default)]
+enum Kind {
+    /// No non-null value has been observed.
+    #[default]
+    Unknown,
+    Bool,
+    Integer {
+        min: i128,
+        max: i128,
+    },
+    Float,
+    String,
+    Array(Box<Shape>),
+    Object(Object),
+}
+
+impl Kind {
+    fn describe(&self) -> &'static str {
+        match self {
+            Kind::Unknown => "null",
+            Kind::Bool => "boolean",
+            Kind::Integer { .. } | Kind::Float => "number",
+            Kind::String => "string",
+            Kind::Array(_) => "array",
+            Kind::Object(_) => "object",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Object {
+    /// The number of objects observed.
+    count: usize,
+
+    /// The shape of each key, in the order they were first observed.
+    fields: Vec<Field>,
+}
+
+#[derive(Debug, Clone)]
+struct Field {
+    name: String,
+
+    /// The number of objects that contained the key.
+    count: usize,
+    shape: Shape,
+}
+
+struct Conflict {
+    pointer: String,
+    expected: &'static str,
+    found: &'static str,
+}
+
+/// Merges `value` into `shape`, where `pointer` is the path to `value`.
+fn observe(shape: &mut Shape, value: &Value, pointer: &mut String) -> Result<(), Conflict> {
+    let observed = match value {
+        Value::Null => {
+            shape.nullable = true;
+            return Ok(());
+        }
+
+        Value::Bool(_) => Kind::Bool,
+        Value::String(_) => Kind::String,
+        Value::Array(_) => Kind::Array(Box::default()),
+        Value::Object(_) => Kind::Object(Object::default()),
+        Value::Number(number) => match (number.as_i64(), number.as_u64()) {
+            (Some(value), _) => Kind::Integer {
+                min: value.into(),
+                max: value.into(),
+            },
+            (_, Some(value)) => Kind::Integer {
+                min: value.into(),
+                max: value.into(),
+            },
+            _ => Kind::Float,
+        },
+    };
+
+    shape.kind = match (std::mem::take(&mut shape.kind), observed) {
+        (Kind::Unknown, observed) => observed,
+
+        (Kind::Integer { min, max }, Kind::Integer { min: value, .. }) => Kind::Integer {
+            min: min.min(value),
+            max: max.max(value),
+        },
+        (Kind::Integer { .. }, Kind::Float) | (Kind::Float, Kind::Integer { .. }) => Kind::Float,
+
+        (kind @ Kind::Bool, Kind::Bool)
+        | (kind @ Kind::Float, Kind::Float)
+        | (kind @ Kind::String, Kind::String)
+        | (kind @ Kind::Array(_), Kind::Array(_))
+        | (kind @ Kind::Object(_), Kind::Object(_)) => kind,
+
+        (kind, observed) => {
+            return Err(Conflict {
+                pointer: pointer.clone(),
+                expected: kind.describe(),
+                found: observed.describe(),
+            })
+        }
+    };
+
+    let len = pointer.len();
+    match (&mut shape.kind, value) {
+        (Kind::Array(element), Value::Array(values)) => {
+            for (index, value) in values.iter().enumerate() {
+                pointer.push_str(&format!("/{}", index));
+                observe(element, value, pointer)?;
+                pointer.truncate(len);
+            }
+        }
+
+        (Kind::Object(object), Value::Object(values)) => {
+            object.count += 1;
+            for (key, value) in values {
+                let index = match object.fields.iter().position(|field| field.name == *key) {
+                    Some(index) => index,
+                    None => {
+                        object.fields.push(Field {
+                            name: key.clone(),
+                            count: 0,
+                            shape: Shape::default(),
+                        });
+                        object.fields.len() - 1
+                    }
+                };
+
+                let field = &mut object.fields[index];
+                field.count += 1;
+
+                pointer.push('/');
+                pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                observe(&mut field.shape, value, pointer)?;
+                pointer.truncate(len);
+            }
+        }
+
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Merges two shapes, returning `None` if they conflict.
+fn merge(first: &Shape, second: &Shape) -> Option<Shape> {
+    let kind = match (&first.kind, &second.kind) {
+        (Kind::Unknown, kind) | (kind, Kind::Unknown) => kind.clone(),
+
+        (
+            Kind::Integer { min, max },
+            Kind::Integer {
+                min: other_min,
+                max: other_max,
+            },
+        ) => Kind::Integer {
+            min: *min.min(other_min),
+            max: *max.max(other_max),
+        },
+        (Kind::Integer { .. }, Kind::Float)
+        | (Kind::Float, Kind::Integer { .. })
+        | (Kind::Float, Kind::Float) => Kind::Float,
+
+        (Kind::Bool, Kind::Bool) => Kind::Bool,
+        (Kind::String, Kind::String) => Kind::String,
+
+        (Kind::Array(first), Kind::Array(second)) => Kind::Array(Box::new(merge(first, second)?)),
+
+        (Kind::Object(first), Kind::Object(second)) => {
+            let mut object = first.clone();
+            object.count += second.count;
+            for field in &second.fields {
+                match object
+                    .fields
+                    .iter_mut()
+                    .find(|other| other.name == field.name)
+                {
+                    Some(other) => {
+                        other.count += field.count;
+                        other.shape = merge(&other.shape, &field.shape)?;
+                    }
+                    None => object.fields.push(field.clone()),
+                }
+            }
+
+            Kind::Object(object)
+        }
+
+        _ => return None,
+    };
+
+    Some(Shape {
+        nullable: first.nullable || second.nullable,
+        kind,
+    })
+}
+
+fn to_schema(shape: &Shape, name: &str) -> Schema {
+    let schema = match &shape.kind {
+        Kind::Unknown => Schema::Unit,
+        Kind::Bool => Schema::Bool,
+        Kind::Integer { min, max } => Schema::narrowest_integer(Some(*min), Some(*max)),
+        Kind::Float => Schema::F64,
+        Kind::String => string(),
+
+        Kind::Array(element) => {
+            let element = to_schema(element, name);
+            Schema::Seq(Box::new(Sequence {
+                name: TypeName::generic("Vec", "alloc::vec", vec![element.full_type_name()]),
+                element,
+                len: None,
+            }))
+        }
+
+        Kind::Object(object) => match map_value(object) {
+            Some(value) => {
+                let key = string();
+                let value = to_schema(&value, &format!("{}Value", name));
+                Schema::Map(Box::new(Map {
+                    name: TypeName::generic(
+                        "HashMap",
+                        "std::collections::hash_map",
+                        vec![key.full_type_name(), value.full_type_name()],
+                    ),
+                    key,
+                    value,
+                }))
+            }
+
+            None => Schema::Struct(Struct {
+                name: TypeName::new(name.to_string(), ""),
+                docs: None,
+                attributes: Default::default(),
+                fields: object
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let mut schema = to_schema(
+                            &field.shape,
+                            &format!("{}{}", name, upper_camel_case(&field.name)),
+                        );
+
+                        let missing = field.count < object.count;
+                        if missing && !field.shape.nullable {
+                            schema = Schema::Option(Box::new(schema));
+                        }
+
+                        NamedField {
+                            default: missing.then(|| FieldDefault::Value(Value::Null)),
+                            skip_serializing_if: missing,
+                            ..NamedField::new(field.name.clone(), schema)
+                        }
+                    })
+                    .collect(),
+                layout: None,
+            }),
+        },
+    };
+
+    if shape.nullable {
+        Schema::Option(Box::new(schema))
+    } else {
+        schema
+    }
+}
+
+/// Returns the shape of the values if `object` looks like a map.
+fn map_value(object: &Object) -> Option<Shape> {
+    let is_field_name = |name: &str| {
+        name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    };
+
+    if object.fields.len() <= MAX_FIELDS
+        && object.fields.iter().all(|field| is_field_name(&field.name))
+    {
+        return None;
+    }
+
+    let mut fields = object.fields.iter();
+    let first = fields.next()?.shape.clone();
+    fields.try_fold(first, |value, field| merge(&value, &field.shape))
+}
+
+fn string() -> Schema {
+    Schema::String(TypeName::new("String", "alloc::string"))
+}
@@ -0,0 +1,1385 @@
+//! Import and export JSON Schema documents.
+//!
+//! [`Schema::from_json_schema`] converts a JSON Schema document into a [`Schema`],
+//! so that APIs described with JSON Schema can be inspected and exported with the
+//! same tooling as Rust types. The conversion follows the shape of the document:
+//!
+//! * Objects with `properties` become structs. Properties that aren't `required`
+//!   become `Option` fields defaulting to `None`, unless they have a `default`,
+//!   which is kept as the default of the field instead. Either way, they are
+//!   marked as [`skip_serializing_if`].
+//! * Objects with only `additionalProperties` become maps with string keys.
+//! * `oneOf` and `anyOf` with a discriminator property become enums with one
+//!   variant per alternative. The discriminator is either declared with an OpenAPI
+//!   style `discriminator` keyword, or is a property with a `const` value in each
+//!   alternative.
+//! * `enum` with string values becomes an enum with unit variants.
+//! * Arrays become sequences, fixed-length arrays when `minItems` and `maxItems`
+//!   are equal, and tuples when the element types are listed with `prefixItems`.
+//! * Integers become the narrowest integer type that can hold the range given by
+//!   `minimum` and `maximum`, defaulting to `i64`.
+//! * On properties and definitions, `pattern`, `minLength` and `maxLength` of
+//!   strings, `minimum` and `maximum` of numbers, and integer bounds narrower than
+//!   the chosen type become the [`Constraints`] of the field or newtype.
+//! * Nullable types, either `"type": [..., "null"]` or a `oneOf` with a `null`
+//!   alternative, become `Option`.
+//! * `$ref` to an entry in `$defs` (or `definitions`) becomes a named type, using
+//!   the key as its name. Definitions that aren't structs or enums become newtypes.
+//!
+//! Anonymous objects and enums are named after their `title`, or else after the
+//! path that leads to them, e.g. the `address` property of `User` becomes a struct
+//! named `UserAddress`. Imported types have an empty module.
+//!
+//! Keywords that only annotate a schema, such as `title`, `examples`, or `format`,
+//! are ignored, and `description` becomes the documentation of the type or field.
+//! Any other keyword that has no equivalent in the data model, such as `allOf`, or
+//! `pattern` on the items of an array, is reported as an [`Error`] rather than
+//! silently dropped.
+//!
+//! In the other direction, [`Schema::to_json_schema`] describes the JSON that Serde
+//! produces for a type with its default, externally tagged representation:
+//!
+//! * User-defined types become entries in `$defs`, named after the type, and are
+//!   referenced with `$ref`. A user-defined root type is written inline instead,
+//!   with its name as the `title`.
+//! * Structs become objects, listing the fields that are
+//!   [`is_required`][`NamedField::is_required`] as `required`. Fields with a known
+//!   default value have it as their `default`.
+//! * Integers are bounded by the range of their type, `Option` becomes a `oneOf`
+//!   with `null`, and tuples become arrays with `prefixItems`.
+//! * Enums with only unit variants become string `enum`s. Other enums become a
+//!   `oneOf` of the variant name for unit variants, and of an object with the
+//!   variant name as its only property for variants with data.
+//! * Maps become objects with `additionalProperties`. Keys must be strings or
+//!   integers, which are written as strings.
+//! * The [`Constraints`] of fields and newtypes become `minimum`, `maximum` and
+//!   `pattern`, and `minLength`, `minItems` or `minProperties` and their maximums,
+//!   depending on the type. Bounds only replace the range of an integer type when
+//!   they are narrower.
+//!
+//! Documentation becomes the `description` of the type or field. Type parameters,
+//! and maps with other keys, have no equivalent and are reported as an [`Error`].
+//!
+//! [`Schema::from_json_schema`]: ../enum.Schema.html#method.from_json_schema
+//! [`Schema::to_json_schema`]: ../enum.Schema.html#method.to_json_schema
+//! [`NamedField::is_required`]: ../struct.NamedField.html#method.is_required
+//! [`Schema`]: ../enum.Schema.html
+//! [`Constraints`]: ../struct.Constraints.html
+//! [`Error`]: enum.Error.html
+//! [`skip_serializing_if`]: ../struct.NamedField.html#structfield.skip_serializing_if
+
+use crate::{
+    doc::trim_docs, Array, Constraints, Enum, FieldDefault, Map, NamedField, NewtypeStruct, Number,
+    Schema, Sequence, Struct, TypeName, Variant,
+};
+use serde_json::{json, Map as Object, Value};
+use std::{borrow::Cow, cmp::Ordering, convert::TryFrom, fmt};
+
+/// Keywords that don't affect the shape of the data.
+const ANNOTATIONS: &[&str] = &[
+    "$schema",
+    "$id",
+    "$comment",
+    "$defs",
+    "definitions",
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+    "format",
+];
+
+impl Schema {
+    /// Converts a JSON Schema document into a schema.
+    ///
+    /// The root of the document is named after its `title`, or `Root` if it doesn't
+    /// have one. See the [`json_schema`] module for details of the conversion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use schematic::Schema;
+    /// use serde_json::json;
+    ///
+    /// let document = json!({
+    ///     "title": "Point",
+    ///     "type": "object",
+    ///     "properties": {
+    ///         "x": { "type": "integer", "minimum": 0, "maximum": 255 },
+    ///         "y": { "type": "integer", "minimum": 0, "maximum": 255 },
+    ///     },
+    ///     "required": ["x", "y"],
+    /// });
+    ///
+    /// let schema = Schema::from_json_schema(&document).unwrap();
+    /// let point = schema.as_struct().unwrap();
+    /// assert_eq!("Point", point.name.name);
+    /// assert_eq!(Schema::U8, point.fields[0].schema);
+    /// ```
+    ///
+    /// [`json_schema`]: json_schema/index.html
+    pub fn from_json_schema(document: &Value) -> Result<Schema, Error> {
+        let mut importer = Importer {
+            document,
+            resolving: Vec::new(),
+        };
+
+        importer.import(document, "", "Root")
+    }
+
+    /// Converts the schema into a JSON Schema document, using draft 2020-12.
+    ///
+    /// See the [`json_schema`] module for details of the conversion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    ///
+    /// let schema = schematic::describe::<(bool, Option<String>)>();
+    /// assert_eq!(
+    ///     json!({
+    ///         "$schema": "https://json-schema.org/draft/2020-12/schema",
+    ///         "type": "array",
+    ///         "prefixItems": [
+    ///             { "type": "boolean" },
+    ///             { "oneOf": [{ "type": "string" }, { "type": "null" }] },
+    ///         ],
+    ///         "items": false,
+    ///         "minItems": 2,
+    ///     }),
+    ///     schema.to_json_schema().unwrap(),
+    /// );
+    /// ```
+    ///
+    /// [`json_schema`]: json_schema/index.html
+    pub fn to_json_schema(&self) -> Result<Value, Error> {
+        let mut exporter = Exporter {
+            keys: definition_keys(self),
+            definitions: Object::new(),
+        };
+
+        let root = match self.type_name() {
+            Some(name) => {
+                let mut root = Object::new();
+                root.insert("title".into(), name.to_string().into());
+                root.extend(into_object(exporter.export_definition(self)?));
+                root
+            }
+            None => into_object(exporter.export(self)?),
+        };
+
+        let mut document = Object::new();
+        document.insert("$schema".into(), DRAFT.into());
+        document.extend(root);
+        if !exporter.definitions.is_empty() {
+            document.insert("$defs".into(), Value::Object(exporter.definitions));
+        }
+
+        Ok(Value::Object(document))
+    }
+}
+
+/// The dialect of exported documents.
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// An error importing or exporting a JSON Schema document.
+///
+/// Each import error carries the JSON Pointer to the schema that caused it, e.g.
+/// `/properties/name`, with the root of the document being the empty string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A keyword, or the way it's used, has no equivalent in the data model.
+    Unsupported { pointer: String, keyword: String },
+
+    /// The document isn't a valid JSON Schema.
+    Invalid { pointer: String, message: String },
+
+    /// A `$ref` doesn't point to a definition in the document.
+    UnresolvedRef { pointer: String, reference: String },
+
+    /// A `$ref` refers back to a definition that contains it.
+    ///
+    /// Recursive types can't be represented, since a schema is a tree.
+    RecursiveRef { pointer: String, reference: String },
+
+    /// A type in the schema has no equivalent in JSON Schema, such as a type
+    /// parameter, or the key of a map that isn't a string or an integer.
+    Unrepresentable(Box<Schema>),
+}
+
+impl Error {
+    fn unsupported(pointer: &str, keyword: &str) -> Self {
+        Error::Unsupported {
+            pointer: pointer.into(),
+            keyword: keyword.into(),
+        }
+    }
+
+    fn invalid(pointer: &str, message: impl Into<String>) -> Self {
+        Error::Invalid {
+            pointer: pointer.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Unsupported { pointer, keyword } => {
+                write!(f, "unsupported keyword `{}` at `{}`", keyword, pointer)
+            }
+
+            Error::Invalid { pointer, message } => {
+                write!(f, "invalid schema at `{}`: {}", pointer, message)
+            }
+
+            Error::UnresolvedRef { pointer, reference } => {
+                write!(f, "cannot resolve `{}` at `{}`", reference, pointer)
+            }
+
+            Error::RecursiveRef { pointer, reference } => {
+                write!(f, "recursive reference `{}` at `{}`", reference, pointer)
+            }
+
+            Error::Unrepresentable(schema) => match schema.type_name() {
+                Some(name) => write!(f, "type `{}` has no JSON Schema representation", name.name),
+                None => write!(f, "{:?} has no JSON Schema representation", schema),
+            },
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+struct Importer<'a> {
+    document: &'a Value,
+
+    /// The definitions currently being imported, used to detect recursive references.
+    resolving: Vec<&'a str>,
+}
+
+impl<'a> Importer<'a> {
+    /// Imports `schema`, naming it `name` if it's an anonymous user-defined type.
+    fn import(&mut self, schema: &'a Value, pointer: &str, name: &str) -> Result<Schema, Error> {
+        self.import_constrained(schema, pointer, name, None)
+    }
+
+    /// Imports `schema` like [`import`], filling in `constraints` from the keywords
+    /// that restrict its values.
+    ///
+    /// Without `constraints`, those keywords are unsupported, except for integer
+    /// bounds, which still pick the integer type.
+    ///
+    /// [`import`]: #method.import
+    fn import_constrained(
+        &mut self,
+        schema: &'a Value,
+        pointer: &str,
+        name: &str,
+        constraints: Option<&mut Constraints>,
+    ) -> Result<Schema, Error> {
+        let object = as_object(schema, pointer)?;
+        let name = object.get("title").and_then(Value::as_str).unwrap_or(name);
+
+        if let Some(reference) = object.get("$ref") {
+            check_keywords(object, pointer, &["$ref"])?;
+            let reference = reference
+                .as_str()
+                .ok_or_else(|| Error::invalid(pointer, "`$ref` must be a string"))?;
+            return self.import_ref(reference, pointer);
+        }
+
+        for &keyword in &["oneOf", "anyOf"] {
+            if let Some(alternatives) = object.get(keyword) {
+                return self.import_union(object, keyword, alternatives, pointer, name);
+            }
+        }
+
+        if let Some(values) = object.get("enum") {
+            check_keywords(object, pointer, &["type", "enum"])?;
+            return import_string_enum(object, values, pointer, name);
+        }
+
+        match object.get("type") {
+            Some(Value::String(ty)) => self.import_type(object, ty, pointer, name, constraints),
+
+            Some(Value::Array(types)) => {
+                let mut types = types.iter().collect::<Vec<_>>();
+                let len = types.len();
+                types.retain(|ty| ty.as_str() != Some("null"));
+
+                match (types.as_slice(), len) {
+                    ([Value::String(ty)], 2) => Ok(Schema::Option(Box::new(self.import_type(
+                        object,
+                        ty,
+                        pointer,
+                        name,
+                        constraints,
+                    )?))),
+                    ([Value::String(ty)], 1) => {
+                        self.import_type(object, ty, pointer, name, constraints)
+                    }
+                    _ => Err(Error::unsupported(pointer, "type")),
+                }
+            }
+
+            Some(_) => Err(Error::invalid(pointer, "`type` must be a string or array")),
+
+            // The type can be left out when it's implied by the other keywords.
+            None if object.contains_key("properties") => {
+                self.import_type(object, "object", pointer, name, None)
+            }
+            None if object.contains_key("items") || object.contains_key("prefixItems") => {
+                self.import_type(object, "array", pointer, name, None)
+            }
+            None => {
+                check_keywords(object, pointer, &[])?;
+                Err(Error::invalid(pointer, "missing `type`"))
+            }
+        }
+    }
+
+    fn import_type(
+        &mut self,
+        object: &'a Object<String, Value>,
+        ty: &str,
+        pointer: &str,
+        name: &str,
+        constraints: Option<&mut Constraints>,
+    ) -> Result<Schema, Error> {
+        match ty {
+            "null" => {
+                check_keywords(object, pointer, &["type"])?;
+                Ok(Schema::Unit)
+            }
+
+            "boolean" => {
+                check_keywords(object, pointer, &["type"])?;
+                Ok(Schema::Bool)
+            }
+
+            "number" => match constraints {
+                Some(constraints) => {
+                    check_keywords(object, pointer, &["type", "minimum", "maximum"])?;
+                    constraints.min = number(object, "minimum", pointer)?;
+                    constraints.max = number(object, "maximum", pointer)?;
+                    Ok(Schema::F64)
+                }
+                None => {
+                    check_keywords(object, pointer, &["type"])?;
+                    Ok(Schema::F64)
+                }
+            },
+
+            "string" => {
+                match constraints {
+                    Some(constraints) => {
+                        check_keywords(
+                            object,
+                            pointer,
+                            &["type", "pattern", "minLength", "maxLength"],
+                        )?;
+                        constraints.pattern = match object.get("pattern") {
+                            None => None,
+                            Some(Value::String(pattern)) => Some(pattern.clone().into()),
+                            Some(_) => {
+                                return Err(Error::invalid(pointer, "`pattern` must be a string"))
+                            }
+                        };
+                        constraints.min_len = length(object, "minLength", pointer)?;
+                        constraints.max_len = length(object, "maxLength", pointer)?;
+                    }
+                    None => check_keywords(object, pointer, &["type"])?,
+                }
+                Ok(Schema::String(TypeName::new("String", "alloc::string")))
+            }
+
+            "integer" => {
+                check_keywords(
+                    object,
+                    pointer,
+                    &[
+                        "type",
+                        "minimum",
+                        "maximum",
+                        "exclusiveMinimum",
+                        "exclusiveMaximum",
+                    ],
+                )?;
+                import_integer(object, pointer, constraints)
+            }
+
+            "array" => self.import_array(object, pointer, name),
+
+            "object" => self.import_object(object, pointer, name),
+
+            _ => Err(Error::invalid(pointer, format!("unknown type `{}`", ty))),
+        }
+    }
+
+    fn import_ref(&mut self, reference: &str, pointer: &str) -> Result<Schema, Error> {
+        let (name, definition, definition_pointer) = self.resolve(reference, pointer)?;
+
+        if self.resolving.contains(&name) {
+            return Err(Error::RecursiveRef {
+                pointer: pointer.into(),
+                reference: reference.into(),
+            });
+        }
+
+        self.resolving.push(name);
+        let mut constraints = Constraints::default();
+        let schema = self.import_constrained(
+            definition,
+            &definition_pointer,
+            name,
+            Some(&mut constraints),
+        );
+        self.resolving.pop();
+        let schema = schema?;
+
+        // Definitions are always named types, so that references to them are too.
+        // Structs and enums don't have constraints, so there are none to lose.
+        if schema.type_name().is_some() {
+            Ok(schema)
+        } else {
+            Ok(Schema::NewtypeStruct(Box::new(NewtypeStruct {
+                name: TypeName::new(name.to_string(), ""),
+                docs: description(as_object(definition, &definition_pointer)?),
+                inner: schema,
+                constraints,
+            })))
+        }
+    }
+
+    /// Finds the definition that `reference` points to.
+    ///
+    /// Returns the name of the definition, its schema, and its JSON Pointer.
+    fn resolve(
+        &self,
+        reference: &str,
+        pointer: &str,
+    ) -> Result<(&'a str, &'a Value, String), Error> {
+        let unresolved = || Error::UnresolvedRef {
+            pointer: pointer.into(),
+            reference: reference.into(),
+        };
+
+        for &section in &["$defs", "definitions"] {
+            let prefix = format!("#/{}/", section);
+            if let Some(key) = reference.strip_prefix(prefix.as_str()) {
+                let key = key.replace("~1", "/").replace("~0", "~");
+                let definitions = self
+                    .document
+                    .get(section)
+                    .and_then(Value::as_object)
+                    .ok_or_else(unresolved)?;
+                let (name, definition) = definitions.get_key_value(&key).ok_or_else(unresolved)?;
+
+                let definition_pointer = format!("/{}/{}", section, escape_pointer(name));
+                return Ok((name, definition, definition_pointer));
+            }
+        }
+
+        Err(unresolved())
+    }
+
+    fn import_object(
+        &mut self,
+        object: &'a Object<String, Value>,
+        pointer: &str,
+        name: &str,
+    ) -> Result<Schema, Error> {
+        check_keywords(
+            object,
+            pointer,
+            &["type", "properties", "required", "additionalProperties"],
+        )?;
+
+        match object.get("additionalProperties") {
+            None | Some(Value::Bool(false)) => {}
+
+            // An object without any fixed properties is a map.
+            Some(value @ Value::Object(_)) if !object.contains_key("properties") => {
+                let key = Schema::String(TypeName::new("String", "alloc::string"));
+                let value = self.import(
+                    value,
+                    &format!("{}/additionalProperties", pointer),
+                    &format!("{}Value", name),
+                )?;
+
+                return Ok(Schema::Map(Box::new(Map {
+                    name: TypeName::generic(
+                        "HashMap",
+                        "std::collections::hash_map",
+                        vec![key.full_type_name(), value.full_type_name()],
+                    ),
+                    key,
+                    value,
+                })));
+            }
+
+            Some(_) => return Err(Error::unsupported(pointer, "additionalProperties")),
+        }
+
+        Ok(Schema::Struct(Struct {
+            name: TypeName::new(name.to_string(), ""),
+            docs: description(object),
+            attributes: Default::default(),
+            fields: self.import_fields(object, pointer, name, None)?,
+            layout: None,
+        }))
+    }
+
+    /// Imports the `properties` of an object as fields, skipping the property `skip`.
+    ///
+    /// Anonymous types are named after the property, prefixed with `name`.
+    fn import_fields(
+        &mut self,
+        object: &'a Object<String, Value>,
+        pointer: &str,
+        name: &str,
+        skip: Option<&str>,
+    ) -> Result<Vec<NamedField>, Error> {
+        let required = match object.get("required") {
+            Some(Value::Array(required)) => required
+                .iter()
+                .map(|name| {
+                    name.as_str().ok_or_else(|| {
+                        Error::invalid(pointer, "`required` must be an array of strings")
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Some(_) => {
+                return Err(Error::invalid(
+                    pointer,
+                    "`required` must be an array of strings",
+                ))
+            }
+            None => Vec::new(),
+        };
+
+        let properties = match object.get("properties") {
+            Some(Value::Object(properties)) => properties,
+            Some(_) => return Err(Error::invalid(pointer, "`properties` must be an object")),
+            None => return Ok(Vec::new()),
+        };
+
+        let mut fields = Vec::new();
+        for (key, property) in properties {
+            if Some(key.as_str()) == skip {
+                continue;
+            }
+
+            let property_pointer = format!("{}/properties/{}", pointer, escape_pointer(key));
+            let mut constraints = Constraints::default();
+            let mut schema = self.import_constrained(
+                property,
+                &property_pointer,
+                &format!("{}{}", name, upper_camel_case(key)),
+                Some(&mut constraints),
+            )?;
+
+            // Properties that aren't required may be missing in either direction, and
+            // without a default they are `None` when missing.
+            let property_object = as_object(property, &property_pointer)?;
+            let optional = !required.contains(&key.as_str());
+            let mut default = None;
+            if optional {
+                if let Some(value) = property_object.get("default") {
+                    default = Some(FieldDefault::Value(value.clone()));
+                } else {
+                    if !matches!(schema, Schema::Option(_)) {
+                        schema = Schema::Option(Box::new(schema));
+                    }
+                    default = Some(FieldDefault::Value(Value::Null));
+                }
+            }
+
+            let mut field = NamedField {
+                name: key.clone().into(),
+                docs: description(property_object),
+                attributes: Default::default(),
+                schema,
+                constraints,
+                default,
+                skip_serializing_if: false,
+            };
+            field.skip_serializing_if = !field.is_required();
+            fields.push(field);
+        }
+
+        Ok(fields)
+    }
+
+    fn import_array(
+        &mut self,
+        object: &'a Object<String, Value>,
+        pointer: &str,
+        name: &str,
+    ) -> Result<Schema, Error> {
+        check_keywords(
+            object,
+            pointer,
+            &[
+                "type",
+                "items",
+                "prefixItems",
+                "additionalItems",
+                "minItems",
+                "maxItems",
+            ],
+        )?;
+
+        let min_items = object.get("minItems").and_then(Value::as_u64);
+        let max_items = object.get("maxItems").and_then(Value::as_u64);
+
+        // Tuples list their elements in `prefixItems`, or in `items` before draft 2020-12.
+        let tuple = match (object.get("prefixItems"), object.get("items")) {
+            (Some(Value::Array(elements)), rest) => Some((
+                elements,
+                "prefixItems",
+                rest.or_else(|| object.get("additionalItems")),
+            )),
+            (None, Some(Value::Array(elements))) => {
+                Some((elements, "items", object.get("additionalItems")))
+            }
+            _ => None,
+        };
+
+        if let Some((elements, keyword, rest)) = tuple {
+            let len = elements.len() as u64;
+            let closed = rest == Some(&Value::Bool(false)) || max_items == Some(len);
+            if !closed {
+                return Err(Error::unsupported(pointer, keyword));
+            }
+            if min_items.is_some_and(|min| min != len) {
+                return Err(Error::unsupported(pointer, "minItems"));
+            }
+
+            let elements = elements
+                .iter()
+                .enumerate()
+                .map(|(index, element)| {
+                    self.import(
+                        element,
+                        &format!("{}/{}/{}", pointer, keyword, index),
+                        &format!("{}{}", name, index),
+                    )
+                })
+                .collect::<Result<_, _>>()?;
+
+            return Ok(Schema::Tuple(elements));
+        }
+
+        let items = object
+            .get("items")
+            .ok_or_else(|| Error::invalid(pointer, "missing `items`"))?;
+        let element = self.import(
+            items,
+            &format!("{}/items", pointer),
+            &format!("{}Item", name),
+        )?;
+
+        match (min_items, max_items) {
+            (Some(min), Some(max)) if min == max => Ok(Schema::Array(Box::new(Array {
+                element,
+                len: min as usize,
+            }))),
+
+            (None, None) => Ok(Schema::Seq(Box::new(Sequence {
+                name: TypeName::generic("Vec", "alloc::vec", vec![element.full_type_name()]),
+                element,
+                len: None,
+            }))),
+
+            (Some(_), _) => Err(Error::unsupported(pointer, "minItems")),
+            (None, Some(_)) => Err(Error::unsupported(pointer, "maxItems")),
+        }
+    }
+
+    fn import_union(
+        &mut self,
+        object: &'a Object<String, Value>,
+        keyword: &str,
+        alternatives: &'a Value,
+        pointer: &str,
+        name: &str,
+    ) -> Result<Schema, Error> {
+        if object.get("type").is_some_and(|ty| ty != "object") {
+            return Err(Error::unsupported(pointer, "type"));
+        }
+        check_keywords(object, pointer, &["type", keyword, "discriminator"])?;
+
+        let alternatives = alternatives
+            .as_array()
+            .ok_or_else(|| Error::invalid(pointer, format!("`{}` must be an array", keyword)))?;
+
+        // A union with `null` is an optional value.
+        let is_null = |schema: &Value| schema.get("type").and_then(Value::as_str) == Some("null");
+        if let [first, second] = alternatives.as_slice() {
+            let (index, inner) = match (is_null(first), is_null(second)) {
+                (true, false) => (1, second),
+                (false, true) => (0, first),
+                _ => return self.import_enum(object, keyword, alternatives, pointer, name),
+            };
+
+            let inner_pointer = format!("{}/{}/{}", pointer, keyword, index);
+            return Ok(Schema::Option(Box::new(self.import(
+                inner,
+                &inner_pointer,
+                name,
+            )?)));
+        }
+
+        self.import_enum(object, keyword, alternatives, pointer, name)
+    }
+
+    /// Imports a union of objects that are distinguished by a discriminator property.
+    fn import_enum(
+        &mut self,
+        object: &'a Object<String, Value>,
+        keyword: &str,
+        alternatives: &'a [Value],
+        pointer: &str,
+        name: &str,
+    ) -> Result<Schema, Error> {
+        let discriminator = object.get("discriminator");
+        let mut property = discriminator
+            .and_then(|discriminator| discriminator.get("propertyName"))
+            .and_then(Value::as_str);
+        let mapping = discriminator
+            .and_then(|discriminator| discriminator.get("mapping"))
+            .and_then(Value::as_object);
+
+        let mut variants = Vec::new();
+        for (index, alternative) in alternatives.iter().enumerate() {
+            let mut alternative_pointer = format!("{}/{}/{}", pointer, keyword, index);
+
+            // Alternatives are usually references to definitions, in which case the
+            // fields are those of the definition.
+            let mut definition = None;
+            let mut alternative = as_object(alternative, &alternative_pointer)?;
+            if let Some(reference) = alternative.get("$ref").and_then(Value::as_str) {
+                check_keywords(alternative, &alternative_pointer, &["$ref"])?;
+
+                let (definition_name, value, value_pointer) =
+                    self.resolve(reference, &alternative_pointer)?;
+                definition = Some((definition_name, reference));
+                alternative = as_object(value, &value_pointer)?;
+                alternative_pointer = value_pointer;
+            }
+
+            if alternative.get("type").is_some_and(|ty| ty != "object")
+                || alternative
+                    .get("additionalProperties")
+                    .is_some_and(|value| value != false)
+            {
+                return Err(Error::unsupported(pointer, keyword));
+            }
+            check_keywords(
+                alternative,
+                &alternative_pointer,
+                &["type", "properties", "required", "additionalProperties"],
+            )?;
+
+            // Without an explicit discriminator, look for a property with a fixed value.
+            let properties = alternative.get("properties").and_then(Value::as_object);
+            if property.is_none() {
+                property = properties.and_then(|properties| {
+                    properties
+                        .iter()
+                        .find(|(_, schema)| schema.get("const").is_some())
+                        .map(|(key, _)| key.as_str())
+                });
+            }
+            let property = property.ok_or_else(|| Error::unsupported(pointer, keyword))?;
+
+            let tag = properties
+                .and_then(|properties| properties.get(property))
+                .and_then(|schema| {
+                    schema.get("const").or_else(|| {
+                        match schema.get("enum")?.as_array()?.as_slice() {
+                            [value] => Some(value),
+                            _ => None,
+                        }
+                    })
+                })
+                .and_then(Value::as_str)
+                .or_else(|| {
+                    let (_, reference) = definition?;
+                    mapping?
+                        .iter()
+                        .find(|(_, target)| target.as_str() == Some(reference))
+                        .map(|(tag, _)| tag.as_str())
+                })
+                .or_else(|| definition.map(|(definition_name, _)| definition_name))
+                .ok_or_else(|| {
+                    Error::invalid(&alternative_pointer, "cannot determine variant name")
+                })?;
+
+            if let Some((definition_name, reference)) = definition {
+                if self.resolving.contains(&definition_name) {
+                    return Err(Error::RecursiveRef {
+                        pointer: alternative_pointer,
+                        reference: reference.into(),
+                    });
+                }
+                self.resolving.push(definition_name);
+            }
+
+            let fields = self.import_fields(
+                alternative,
+                &alternative_pointer,
+                &format!("{}{}", name, upper_camel_case(tag)),
+                Some(property),
+            );
+
+            if definition.is_some() {
+                self.resolving.pop();
+            }
+
+            let fields = fields?;
+            let docs = description(alternative);
+            variants.push(if fields.is_empty() {
+                Variant::Unit {
+                    name: tag.to_string().into(),
+                    docs,
+                    attributes: Default::default(),
+                    discriminant: None,
+                }
+            } else {
+                Variant::Struct {
+                    name: tag.to_string().into(),
+                    docs,
+                    attributes: Default::default(),
+                    fields,
+                }
+            });
+        }
+
+        Ok(Schema::Enum(Enum {
+            name: TypeName::new(name.to_string(), ""),
+            docs: description(object),
+            repr: None,
+            variants,
+            layout: None,
+        }))
+    }
+}
+
+struct Exporter<'a> {
+    /// The key in `$defs` of each user-defined type.
+    keys: Vec<(&'a TypeName, String)>,
+
+    /// The definitions exported so far, in dependency order.
+    definitions: Object<String, Value>,
+}
+
+impl<'a> Exporter<'a> {
+    /// Exports the schema of a value of `schema`, referring to user-defined types
+    /// with `$ref`.
+    fn export(&mut self, schema: &'a Schema) -> Result<Value, Error> {
+        if let Some(name) = schema.type_name() {
+            let key = self.key(name);
+            if !self.definitions.contains_key(&key) {
+                let definition = self.export_definition(schema)?;
+                self.definitions.insert(key.clone(), definition);
+            }
+
+            return Ok(json!({ "$ref": format!("#/$defs/{}", escape_pointer(&key)) }));
+        }
+
+        Ok(match schema {
+            Schema::Unit => json!({ "type": "null" }),
+            Schema::Bool => json!({ "type": "boolean" }),
+            Schema::Char => json!({ "type": "string", "minLength": 1, "maxLength": 1 }),
+
+            Schema::I8 => integer(i8::MIN, i8::MAX),
+            Schema::I16 => integer(i16::MIN, i16::MAX),
+            Schema::I32 => integer(i32::MIN, i32::MAX),
+            Schema::I64 | Schema::ISize => integer(i64::MIN, i64::MAX),
+            Schema::U8 => integer(u8::MIN, u8::MAX),
+            Schema::U16 => integer(u16::MIN, u16::MAX),
+            Schema::U32 => integer(u32::MIN, u32::MAX),
+            Schema::U64 | Schema::USize => integer(u64::MIN, u64::MAX),
+
+            // JSON numbers can't hold the bounds of 128-bit integers.
+            Schema::I128 => json!({ "type": "integer" }),
+            Schema::U128 => json!({ "type": "integer", "minimum": 0 }),
+
+            Schema::F32 | Schema::F64 => json!({ "type": "number" }),
+            Schema::Str | Schema::String(_) => json!({ "type": "string" }),
+
+            Schema::Option(inner) => json!({ "oneOf": [self.export(inner)?, { "type": "null" }] }),
+            Schema::Tuple(elements) => self.export_tuple(elements)?,
+            Schema::Array(array) => json!({
+                "type": "array",
+                "items": self.export(&array.element)?,
+                "minItems": array.len,
+                "maxItems": array.len,
+            }),
+            Schema::Slice(element) => json!({ "type": "array", "items": self.export(element)? }),
+            Schema::Seq(seq) => json!({ "type": "array", "items": self.export(&seq.element)? }),
+
+            Schema::Map(map) => {
+                let mut object = Object::new();
+                object.insert("type".into(), "object".into());
+                if let Some(pattern) = key_pattern(&map.key)? {
+                    object.insert("propertyNames".into(), json!({ "pattern": pattern }));
+                }
+                object.insert("additionalProperties".into(), self.export(&map.value)?);
+                Value::Object(object)
+            }
+
+            _ => return Err(Error::Unrepresentable(Box::new(schema.clone()))),
+        })
+    }
+
+    /// Exports the definition of the user-defined type `schema`.
+    fn export_definition(&mut self, schema: &'a Schema) -> Result<Value, Error> {
+        let mut definition = match schema {
+            Schema::Struct(schema) => self.export_fields(&schema.fields)?,
+            Schema::UnitStruct(_) => json!({ "type": "null" }),
+            Schema::TupleStruct(schema) => self.export_tuple(&schema.elements)?,
+            Schema::NewtypeStruct(schema) => {
+                let mut inner = into_object(self.export(&schema.inner)?);
+                constrain(&mut inner, &schema.inner, &schema.constraints);
+                Value::Object(inner)
+            }
+            Schema::Enum(schema) => self.export_enum(schema)?,
+            _ => unreachable!("only user-defined types have definitions"),
+        };
+
+        if let (Value::Object(object), Some(docs)) = (&mut definition, schema.docs()) {
+            object.insert("description".into(), trim_docs(docs).into());
+        }
+
+        Ok(definition)
+    }
+
+    fn export_fields(&mut self, fields: &'a [NamedField]) -> Result<Value, Error> {
+        let mut properties = Object::new();
+        for field in fields {
+            let mut property = into_object(self.export(&field.schema)?);
+            constrain(&mut property, &field.schema, &field.constraints);
+            if let Some(default) = field.default.as_ref().and_then(FieldDefault::value) {
+                property.insert("default".into(), default.clone());
+            }
+            if let Some(docs) = &field.docs {
+                property.insert("description".into(), trim_docs(docs).into());
+            }
+            properties.insert(field.name.to_string(), Value::Object(property));
+        }
+
+        let required = fields
+            .iter()
+            .filter(|field| field.is_required())
+            .map(|field| Value::from(field.name.as_ref()))
+            .collect::<Vec<_>>();
+
+        let mut object = Object::new();
+        object.insert("type".into(), "object".into());
+        object.insert("properties".into(), Value::Object(properties));
+        if !required.is_empty() {
+            object.insert("required".into(), Value::Array(required));
+        }
+
+        Ok(Value::Object(object))
+    }
+
+    fn export_tuple(&mut self, elements: &'a [Schema]) -> Result<Value, Error> {
+        let elements = elements
+            .iter()
+            .map(|element| self.export(element))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(json!({
+            "type": "array",
+            "prefixItems": elements,
+            "items": false,
+            "minItems": elements.len(),
+        }))
+    }
+
+    fn export_enum(&mut self, schema: &'a Enum) -> Result<Value, Error> {
+        // An enum without variants has no values, so nothing matches it.
+        if schema.variants.is_empty() {
+            return Ok(json!({ "not": {} }));
+        }
+
+        if !schema.has_data() {
+            let names = schema
+                .variants
+                .iter()
+                .map(Variant::name)
+                .collect::<Vec<_>>();
+            return Ok(json!({ "type": "string", "enum": names }));
+        }
+
+        let mut alternatives = Vec::new();
+        for variant in &schema.variants {
+            let mut alternative = match variant {
+                Variant::Unit { name, .. } => json!({ "const": name }),
+
+                Variant::Tuple { name, elements, .. } => {
+                    let data = match elements.as_slice() {
+                        [inner] => self.export(inner)?,
+                        elements => self.export_tuple(elements)?,
+                    };
+                    tagged(name, data)
+                }
+
+                Variant::Struct { name, fields, .. } => {
+                    let data = self.export_fields(fields)?;
+                    tagged(name, data)
+                }
+            };
+
+            if let (Value::Object(object), Some(docs)) = (&mut alternative, variant.docs()) {
+                object.insert("description".into(), trim_docs(docs).into());
+            }
+            alternatives.push(alternative);
+        }
+
+        Ok(json!({ "oneOf": alternatives }))
+    }
+
+    /// Returns the key in `$defs` of the type `name`.
+    fn key(&self, name: &TypeName) -> String {
+        self.keys
+            .iter()
+            .find(|(key_name, _)| *key_name == name)
+            .map(|(_, key)| key.clone())
+            .expect("every user-defined type has a key")
+    }
+}
+
+/// Picks the key in `$defs` of each user-defined type in `schema`.
+///
+/// Types are keyed by their short name, e.g. `Page<User>`, unless another type has
+/// the same short name, in which case both are keyed by their qualified name.
+fn definition_keys(schema: &Schema) -> Vec<(&TypeName, String)> {
+    let names = schema
+        .named_types()
+        .into_iter()
+        .filter_map(Schema::type_name)
+        .collect::<Vec<_>>();
+
+    names
+        .iter()
+        .map(|&name| {
+            let short = name.to_string();
+            let ambiguous = names
+                .iter()
+                .any(|&other| other != name && other.to_string() == short);
+            (name, if ambiguous { name.qualified() } else { short })
+        })
+        .collect()
+}
+
+/// Adds the keywords for `constraints` on values of `schema` to `object`.
+///
+/// Constraints on an `Option` apply to the inner value. Since JSON Schema keywords
+/// only apply to values of the matching type, they can be added next to the `oneOf`
+/// with `null`.
+fn constrain(object: &mut Object<String, Value>, schema: &Schema, constraints: &Constraints) {
+    if let Some(min) = constraints.min {
+        insert_bound(object, "minimum", min, Ordering::Greater);
+    }
+    if let Some(max) = constraints.max {
+        insert_bound(object, "maximum", max, Ordering::Less);
+    }
+
+    let kind = length_kind(schema);
+    if let (Some(LengthKind::String), Some(pattern)) = (&kind, &constraints.pattern) {
+        object.insert("pattern".into(), pattern.to_string().into());
+    }
+
+    let (min_len, max_len) = match kind {
+        Some(LengthKind::String) => ("minLength", "maxLength"),
+        Some(LengthKind::Array) => ("minItems", "maxItems"),
+        Some(LengthKind::Object) => ("minProperties", "maxProperties"),
+        None => return,
+    };
+    if let Some(len) = constraints.min_len {
+        object.insert(min_len.into(), len.into());
+    }
+    if let Some(len) = constraints.max_len {
+        object.insert(max_len.into(), len.into());
+    }
+}
+
+/// The kinds of JSON values that lengths can be given for.
+enum LengthKind {
+    String,
+    Array,
+    Object,
+}
+
+fn length_kind(schema: &Schema) -> Option<LengthKind> {
+    match schema {
+        Schema::Str | Schema::String(_) | Schema::Char => Some(LengthKind::String),
+        Schema::Array(_) | Schema::Slice(_) | Schema::Seq(_) => Some(LengthKind::Array),
+        Schema::Map(_) => Some(LengthKind::Object),
+        Schema::Option(inner) => length_kind(inner),
+        Schema::NewtypeStruct(newtype) => length_kind(&newtype.inner),
+        _ => None,
+    }
+}
+
+/// Sets `keyword` to `bound`, unless `object` already has an integer bound that is
+/// `tighter`, such as the range of a narrower integer type.
+fn insert_bound(
+    object: &mut Object<String, Value>,
+    keyword: &str,
+    bound: Number,
+    tighter: Ordering,
+) {
+    let existing = object.get(keyword).and_then(|value| {
+        (value.as_i64().map(i128::from)).or_else(|| value.as_u64().map(i128::from))
+    });
+    if let (Some(existing), Number::Int(bound)) = (existing, bound) {
+        if existing.cmp(&bound) == tighter {
+            return;
+        }
+    }
+
+    let value = match bound {
+        Number::Int(bound) => i64::try_from(bound)
+            .map(Value::from)
+            .or_else(|_| u64::try_from(bound).map(Value::from))
+            .unwrap_or_else(|_| Value::from(bound as f64)),
+        Number::Float(bound) => Value::from(bound),
+    };
+    object.insert(keyword.into(), value);
+}
+
+/// Returns the schema of an integer with the bounds of its type.
+fn integer<T: Into<Value>>(min: T, max: T) -> Value {
+    json!({ "type": "integer", "minimum": min.into(), "maximum": max.into() })
+}
+
+/// Returns the schema of the data of a variant in the externally tagged
+/// representation, i.e. an object with the name of the variant as its only key.
+fn tagged(name: &str, data: Value) -> Value {
+    json!({
+        "type": "object",
+        "properties": { name: data },
+        "required": [name],
+        "additionalProperties": false,
+    })
+}
+
+/// Returns the pattern that the keys of a map with keys of type `key` match when
+/// serialized as JSON object keys, or `None` if they are arbitrary strings.
+fn key_pattern(key: &Schema) -> Result<Option<&'static str>, Error> {
+    match key {
+        Schema::Str | Schema::String(_) | Schema::Char => Ok(None),
+
+        Schema::U8 | Schema::U16 | Schema::U32 | Schema::U64 | Schema::U128 | Schema::USize => {
+            Ok(Some("^[0-9]+$"))
+        }
+        Schema::I8 | Schema::I16 | Schema::I32 | Schema::I64 | Schema::I128 | Schema::ISize => {
+            Ok(Some("^-?[0-9]+$"))
+        }
+
+        // Newtypes are serialized as their inner value.
+        Schema::NewtypeStruct(newtype) => key_pattern(&newtype.inner),
+
+        _ => Err(Error::Unrepresentable(Box::new(key.clone()))),
+    }
+}
+
+fn into_object(value: Value) -> Object<String, Value> {
+    match value {
+        Value::Object(object) => object,
+        _ => unreachable!("exported schemas are objects"),
+    }
+}
+
+fn import_string_enum(
+    object: &Object<String, Value>,
+    values: &Value,
+    pointer: &str,
+    name: &str,
+) -> Result<Schema, Error> {
+    let values = values
+        .as_array()
+        .ok_or_else(|| Error::invalid(pointer, "`enum` must be an array"))?;
+
+    let variants = values
+        .iter()
+        .map(|value| match value {
+            Value::String(value) => Ok(Variant::Unit {
+                name: value.clone().into(),
+                docs: None,
+                attributes: Default::default(),
+                discriminant: None,
+            }),
+            _ => Err(Error::unsupported(pointer, "enum")),
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Schema::Enum(Enum {
+        name: TypeName::new(name.to_string(), ""),
+        docs: description(object),
+        repr: None,
+        variants,
+        layout: None,
+    }))
+}
+
+/// Picks the narrowest integer type for the bounds of an integer schema.
+///
+/// Bounds that are narrower than the range of that type are kept as `constraints`.
+fn import_integer(
+    object: &Object<String, Value>,
+    pointer: &str,
+    constraints: Option<&mut Constraints>,
+) -> Result<Schema, Error> {
+    let bound = |keyword: &str, round: fn(f64) -> f64| -> Result<Option<i128>, Error> {
+        match object.get(keyword) {
+            None | Some(Value::Bool(_)) => Ok(None),
+            Some(Value::Number(number)) => Ok(Some(match (number.as_i64(), number.as_u64()) {
+                (Some(value), _) => value.into(),
+                (_, Some(value)) => value.into(),
+                _ => round(number.as_f64().unwrap()) as i128,
+            })),
+            Some(_) => Err(Error::invalid(
+                pointer,
+                format!("`{}` must be a number", keyword),
+            )),
+        }
+    };
+
+    // Before draft 6, the exclusive bounds were flags that modified `minimum` and `maximum`.
+    let exclusive = |keyword| object.get(keyword) == Some(&Value::Bool(true));
+
+    let mut min = bound("minimum", f64::ceil)?;
+    if exclusive("exclusiveMinimum") {
+        min = min.map(|min| min + 1);
+    }
+    if let Some(exclusive_min) = bound("exclusiveMinimum", f64::floor)? {
+        min = Some(min.map_or(exclusive_min + 1, |min| min.max(exclusive_min + 1)));
+    }
+
+    let mut max = bound("maximum", f64::floor)?;
+    if exclusive("exclusiveMaximum") {
+        max = max.map(|max| max - 1);
+    }
+    if let Some(exclusive_max) = bound("exclusiveMaximum", f64::ceil)? {
+        max = Some(max.map_or(exclusive_max - 1, |max| max.min(exclusive_max - 1)));
+    }
+
+    let schema = Schema::narrowest_integer(min, max);
+    if let Some(constraints) = constraints {
+        let (type_min, type_max) = integer_range(&schema);
+        constraints.min = min.filter(|&min| min != type_min).map(Number::Int);
+        constraints.max = max.filter(|&max| max != type_max).map(Number::Int);
+    }
+
+    Ok(schema)
+}
+
+/// Returns the smallest and largest values of an integer type.
+fn integer_range(schema: &Schema) -> (i128, i128) {
+    match schema {
+        Schema::I8 => (i8::MIN.into(), i8::MAX.into()),
+        Schema::I16 => (i16::MIN.into(), i16::MAX.into()),
+        Schema::I32 => (i32::MIN.into(), i32::MAX.into()),
+        Schema::I64 => (i64::MIN.into(), i64::MAX.into()),
+        Schema::U8 => (0, u8::MAX.into()),
+        Schema::U16 => (0, u16::MAX.into()),
+        Schema::U32 => (0, u32::MAX.into()),
+        Schema::U64 => (0, u64::MAX.into()),
+        Schema::U128 => (0, i128::MAX),
+        _ => (i128::MIN, i128::MAX),
+    }
+}
+
+/// Reads the number `keyword`, keeping integers exact.
+fn number(
+    object: &Object<String, Value>,
+    keyword: &str,
+    pointer: &str,
+) -> Result<Option<Number>, Error> {
+    match object.get(keyword) {
+        None => Ok(None),
+        Some(Value::Number(number)) => Ok(Some(match (number.as_i64(), number.as_u64()) {
+            (Some(value), _) => Number::Int(value.into()),
+            (_, Some(value)) => Number::Int(value.into()),
+            _ => Number::Float(number.as_f64().unwrap()),
+        })),
+        Some(_) => Err(Error::invalid(
+            pointer,
+            format!("`{}` must be a number", keyword),
+        )),
+    }
+}
+
+/// Reads the length `keyword`.
+fn length(
+    object: &Object<String, Value>,
+    keyword: &str,
+    pointer: &str,
+) -> Result<Option<usize>, Error> {
+    match object.get(keyword) {
+        None => Ok(None),
+        Some(value) => value.as_u64().map(|len| Some(len as usize)).ok_or_else(|| {
+            Error::invalid(
+                pointer,
+                format!("`{}` must be a non-negative integer", keyword),
+            )
+        }),
+    }
+}
+
+/// Checks that `object` only uses annotations and the keywords in `supported`.
+fn check_keywords(
+    object: &Object<String, Value>,
+    pointer: &str,
+    supported: &[&str],
+) -> Result<(), Error> {
+    match object
+        .keys()
+        .find(|key| !ANNOTATIONS.contains(&key.as_str()) && !supported.contains(&key.as_str()))
+    {
+        Some(keyword) => Err(Error::unsupported(pointer, keyword)),
+        None => Ok(()),
+    }
+}
+
+fn as_object<'a>(schema: &'a Value, pointer: &str) -> Result<&'a Object<String, Value>, Error> {
+    schema
+        .as_object()
+        .ok_or_else(|| Error::invalid(pointer, "expected a schema object"))
+}
+
+fn description(object: &Object<String, Value>) -> Option<Cow<'static, str>> {
+    object
+        .get("description")
+        .and_then(Value::as_str)
+        .map(|description| description.to_string().into())
+}
+
+/// Escapes a key for use as a JSON Pointer reference token.
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+pub(crate) fn upper_camel_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            let mut chars = segment.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            Some(first).into_iter().chain(chars).collect::<String>()
+        })
+        .collect()
+}
@@ -45,25 +45,132 @@
 //!   * `struct_variant` for variants with named fields.
 //!   * `tuple_variant` for variants with unnamed fields.
 //!
+//! # `no_std` Support
+//!
+//! The data model, i.e. the [`Describe`] and [`Describer`] traits, [`Schema`],
+//! [`SchemaDescriber`] and the `Describe` impls for primitives and `alloc` types,
+//! only needs `alloc`. Everything else, including the exporters, needs the `std`
+//! feature, which is enabled by default. To use Schematic in a `#![no_std]` crate,
+//! turn off default features:
+//!
+//! ```toml
+//! [dependencies]
+//! schematic = { version = "0.1", default-features = false }
+//! ```
+//!
 //! [Serde]: https://crates.io/crates/serde
+//! [`Describe`]: trait.Describe.html
+//! [`Describer`]: trait.Describer.html
+//! [`Schema`]: enum.Schema.html
+//! [`SchemaDescriber`]: struct.SchemaDescriber.html
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::{borrow::Cow, format, string::String, vec::Vec};
 use serde::{Deserialize, Serialize};
-use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+#[cfg(feature = "std")]
+pub mod c_header;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod doc;
+#[cfg(feature = "std")]
+pub mod dot;
+#[cfg(feature = "std")]
+pub mod graph;
+#[cfg(feature = "std")]
+pub mod infer;
+#[cfg(feature = "std")]
+pub mod json_schema;
+#[cfg(feature = "std")]
+pub mod parse;
+#[cfg(feature = "std")]
+pub mod record;
+#[cfg(feature = "registry")]
+pub mod registry;
+#[cfg(feature = "std")]
+pub mod rust;
+#[cfg(feature = "std")]
+pub mod skeleton;
+#[cfg(feature = "std")]
+pub mod snapshot;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "std")]
+pub mod validate;
 
 mod describe;
+mod display;
 mod primitive;
 mod schema;
 mod schema_describer;
+#[cfg(feature = "std")]
+mod value;
 
 pub use crate::{describe::*, schema::*, schema_describer::*};
+#[cfg(feature = "std")]
+pub use crate::{skeleton::skeleton, value::*};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec;
+    pub use serde_json;
+}
 
 /// Describes `T` into an in-memory representation of the type tree.
+///
+/// # Panics
+///
+/// Panics if the `Describe` impl of `T`, or of a type within it, reports an error.
+/// Use [`try_describe`] to handle the error instead.
+///
+/// [`try_describe`]: fn.try_describe.html
 pub fn describe<T: Describe>() -> Schema {
-    let mut describe = SchemaDescriber;
+    match try_describe::<T>() {
+        Ok(schema) => schema,
+        Err(error) => panic!("failed to describe `{}`: {}", T::type_name(), error),
+    }
+}
 
-    // NOTE: The unwrap here will never panic because the error type of
-    // `SchemaDescriber` is `!`.
-    T::describe(&mut describe).unwrap()
+/// Describes `T` into an in-memory representation of the type tree, or returns the
+/// error reported by the `Describe` impl of `T` or of a type within it.
+///
+/// The error carries the path to the type that couldn't be described, see
+/// [`SchemaError`] for more. Use [`DescribeError::custom`] to report an error from a
+/// `Describe` impl.
+///
+/// [`SchemaError`]: struct.SchemaError.html
+/// [`DescribeError::custom`]: trait.DescribeError.html#tymethod.custom
+pub fn try_describe<T: Describe>() -> Result<Schema, SchemaError> {
+    T::describe(&mut SchemaDescriber::new())
+}
+
+/// Describes `T` once and returns the same shared schema on every later call.
+///
+/// Schemas are kept in a cache shared by the whole program, keyed by the
+/// `TypeId` of `T`. Use [`cache::SchemaCache`] to control how long schemas are
+/// kept around.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// let schema = schematic::describe_cached::<Option<String>>();
+/// assert_eq!(schematic::describe::<Option<String>>(), *schema);
+/// assert!(Arc::ptr_eq(&schema, &schematic::describe_cached::<Option<String>>()));
+/// ```
+///
+/// This function is only available with the `std` feature.
+///
+/// [`cache::SchemaCache`]: cache/struct.SchemaCache.html
+#[cfg(feature = "std")]
+pub fn describe_cached<T: Describe + 'static>() -> Arc<Schema> {
+    static CACHE: cache::SchemaCache = cache::SchemaCache::new();
+    CACHE.get::<T>()
 }
 
 /// Unique name for a type.
@@ -116,4 +223,30 @@ impl TypeName {
             type_params: type_params.into(),
         }
     }
+
+    /// Returns the fully-qualified name of the type, with the module of the type
+    /// and of each of its type parameters, e.g. `alloc::vec::Vec<u8>`.
+    ///
+    /// This is the same as formatting the name with `{:#}`, and can be parsed back
+    /// with [`parse::parse_type_name`] or `str::parse`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use schematic::{Describe, TypeName};
+    /// use std::collections::BTreeMap;
+    ///
+    /// let name = BTreeMap::<String, [u8; 4]>::type_name();
+    /// assert_eq!(
+    ///     "alloc::collections::btree_map::BTreeMap<alloc::string::String, [u8; 4]>",
+    ///     name.qualified(),
+    /// );
+    /// # #[cfg(feature = "std")]
+    /// assert_eq!(Ok(name.clone()), name.qualified().parse());
+    /// ```
+    ///
+    /// [`parse::parse_type_name`]: parse/fn.parse_type_name.html
+    pub fn qualified(&self) -> String {
+        format!("{:#}", self)
+    }
 }
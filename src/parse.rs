@@ -0,0 +1,1196 @@
+//! Parse schemas from the Rust-like syntax used by `Display`.
+//!
+//! This makes the output of `Display` usable as a lightweight schema IDL, e.g. to
+//! write schemas by hand for tests or to describe types from another language:
+//!
+//! ```
+//! use schematic::Schema;
+//!
+//! let schema = schematic::parse::parse(
+//!     "
+//!     /// Top-level configuration.
+//!     struct Config { servers: Vec<Server>, mode: Mode }
+//!
+//!     struct Server { address: ([u8; 4], u16), name: Option<String> }
+//!
+//!     #[repr(u8)]
+//!     enum Mode { Fast = 1, Slow = 2 }
+//!     ",
+//! )?;
+//!
+//! assert_eq!(Some(" Top-level configuration."), schema.docs());
+//! assert_eq!(
+//!     "struct Config { servers: Vec<Server>, mode: Mode }",
+//!     schema.to_string(),
+//! );
+//! # Ok::<(), schematic::parse::Error>(())
+//! ```
+//!
+//! A document is a list of type definitions, optionally preceded by a type that
+//! isn't a definition, e.g. `Vec<Event>`. The first item is the schema that is
+//! returned, and the other definitions are only used when they're referred to by
+//! name. Definitions can also be written inline wherever a type is expected, which
+//! is how the alternate form of `Display` (`{:#}`) renders nested types. Doc
+//! comments (`///`) are kept as the documentation of definitions, fields and
+//! variants, and other comments are ignored.
+//!
+//! The built-in types are the primitives, `&str`, `String`, `Option`, arrays,
+//! slices, tuples, and the collections from the standard library, such as `Vec` and
+//! `HashMap`. Since the syntax doesn't include module paths, parsed definitions
+//! belong to the module `""`. Parameters of a definition that don't name a type,
+//! like the `T` in `struct Page<T> { items: Vec<T> }`, are type parameters, and
+//! are parsed as [`Schema::TypeParam`] within that definition.
+//!
+//! [`Schema::TypeParam`]: ../enum.Schema.html#variant.TypeParam
+
+use crate::{
+    describe::Describe, NamedField, Primitive, PrimitiveValue, Schema, Sequence, TypeName, Variant,
+};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque},
+    convert::TryFrom,
+    fmt,
+    ops::Range,
+    str::FromStr,
+};
+
+/// Parses a schema from `source`.
+///
+/// # Errors
+///
+/// Returns an error if `source` isn't valid syntax, refers to types that aren't
+/// defined, or defines types that contain themselves.
+pub fn parse(source: &str) -> Result<Schema, Error> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        source,
+        tokens,
+        position: 0,
+    };
+    let document = parser.document()?;
+
+    let mut definitions = HashMap::new();
+    for definition in &document.definitions {
+        let key = definition.key();
+        if definitions.insert(key.clone(), definition).is_some() {
+            return Err(Error::DuplicateType {
+                span: Span::new(source, definition.name_span.clone()),
+                name: key,
+            });
+        }
+    }
+
+    let mut resolver = Resolver {
+        source,
+        definitions,
+        resolving: Vec::new(),
+        type_params: Vec::new(),
+    };
+    match &document.root {
+        Some(root) => resolver.resolve(root),
+        None => resolver.definition(&document.definitions[0]),
+    }
+}
+
+impl FromStr for Schema {
+    type Err = Error;
+
+    /// Parses a schema, see [`parse`] for the syntax.
+    ///
+    /// [`parse`]: parse/fn.parse.html
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        parse(source)
+    }
+}
+
+/// Parses a [`TypeName`] from its qualified form, e.g. `alloc::vec::Vec<u8>`.
+///
+/// This is the inverse of [`TypeName::qualified`]. Tuples, arrays and slices are
+/// written with Rust syntax, e.g. `(u8, [bool; 2])`, and the last segment of a path
+/// is the name of the type, with the rest being its module.
+///
+/// # Examples
+///
+/// ```
+/// use schematic::Describe;
+///
+/// let name = schematic::parse::parse_type_name("alloc::vec::Vec<(u8, [bool; 2])>")?;
+/// assert_eq!(Vec::<(u8, [bool; 2])>::type_name(), name);
+/// assert_eq!("alloc::vec::Vec<(u8, [bool; 2])>", name.qualified());
+/// # Ok::<(), schematic::parse::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if `source` isn't a valid type name.
+///
+/// [`TypeName`]: ../struct.TypeName.html
+/// [`TypeName::qualified`]: ../struct.TypeName.html#method.qualified
+pub fn parse_type_name(source: &str) -> Result<TypeName, Error> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        source,
+        tokens,
+        position: 0,
+    };
+
+    let name = parser.type_name()?;
+    if parser.peek() != Token::Eof {
+        return Err(parser.unexpected("end of input"));
+    }
+
+    Ok(name)
+}
+
+impl FromStr for TypeName {
+    type Err = Error;
+
+    /// Parses a qualified type name, see [`parse_type_name`] for the syntax.
+    ///
+    /// [`parse_type_name`]: parse/fn.parse_type_name.html
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        parse_type_name(source)
+    }
+}
+
+/// A location in the parsed source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    /// The byte offset of the start of the span.
+    pub start: usize,
+
+    /// The byte offset of the end of the span, exclusive.
+    pub end: usize,
+
+    /// The line of the start of the span, starting at 1.
+    pub line: usize,
+
+    /// The column of the start of the span in characters, starting at 1.
+    pub column: usize,
+}
+
+impl Span {
+    fn new(source: &str, range: Range<usize>) -> Self {
+        let before = &source[..range.start];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+        Span {
+            start: range.start,
+            end: range.end,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// An error parsing a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A character that isn't part of the syntax.
+    UnexpectedCharacter { span: Span, character: char },
+
+    /// A token other than the ones that are valid at that position.
+    UnexpectedToken {
+        span: Span,
+        expected: &'static str,
+        found: String,
+    },
+
+    /// A number that is out of range, such as a discriminant that doesn't fit the
+    /// `repr` of its enum.
+    InvalidNumber { span: Span, literal: String },
+
+    /// A reference to a type that isn't built in or defined in the document.
+    UnknownType { span: Span, name: String },
+
+    /// A built-in generic type with the wrong number of type arguments.
+    WrongArguments {
+        span: Span,
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+
+    /// A type that is defined more than once.
+    DuplicateType { span: Span, name: String },
+
+    /// A type that contains itself, which can't be represented by `Schema`.
+    RecursiveType { span: Span, name: String },
+}
+
+impl Error {
+    /// Returns the location of the error in the source.
+    pub fn span(&self) -> &Span {
+        match self {
+            Error::UnexpectedCharacter { span, .. }
+            | Error::UnexpectedToken { span, .. }
+            | Error::InvalidNumber { span, .. }
+            | Error::UnknownType { span, .. }
+            | Error::WrongArguments { span, .. }
+            | Error::DuplicateType { span, .. }
+            | Error::RecursiveType { span, .. } => span,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnexpectedCharacter { character, .. } => {
+                write!(f, "unexpected character `{}`", character)?
+            }
+
+            Error::UnexpectedToken {
+                expected, found, ..
+            } => write!(f, "expected {}, found {}", expected, found)?,
+
+            Error::InvalidNumber { literal, .. } => {
+                write!(f, "number `{}` is out of range", literal)?
+            }
+
+            Error::UnknownType { name, .. } => write!(f, "unknown type `{}`", name)?,
+
+            Error::WrongArguments {
+                name,
+                expected,
+                found,
+                ..
+            } => write!(
+                f,
+                "`{}` expects {} type argument{}, found {}",
+                name,
+                expected,
+                if *expected == 1 { "" } else { "s" },
+                found,
+            )?,
+
+            Error::DuplicateType { name, .. } => {
+                write!(f, "type `{}` is defined more than once", name)?
+            }
+
+            Error::RecursiveType { name, .. } => write!(f, "type `{}` contains itself", name)?,
+        }
+
+        write!(f, " at {}", self.span())
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Ident(&'a str),
+    Number(&'a str),
+
+    /// The text of a doc comment, after the `///`.
+    Doc(&'a str),
+    Punct(char),
+    Eof,
+}
+
+impl fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(ident) => write!(f, "`{}`", ident),
+            Token::Number(number) => write!(f, "`{}`", number),
+            Token::Doc(_) => f.write_str("doc comment"),
+            Token::Punct(punct) => write!(f, "`{}`", punct),
+            Token::Eof => f.write_str("end of input"),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token<'_>, Range<usize>)>, Error> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+
+            '/' if source[start..].starts_with("//") => {
+                let end = source[start..]
+                    .find('\n')
+                    .map_or(source.len(), |index| start + index);
+                while chars.peek().is_some_and(|&(index, _)| index < end) {
+                    chars.next();
+                }
+
+                // `////` and longer are regular comments, the same as in Rust.
+                let comment = &source[start..end];
+                if comment.starts_with("///") && !comment.starts_with("////") {
+                    tokens.push((Token::Doc(&comment[3..]), start..end));
+                }
+                continue;
+            }
+
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut end = start + 1;
+                while let Some(&(index, c)) = chars.peek() {
+                    if !c.is_ascii_alphanumeric() && c != '_' {
+                        break;
+                    }
+                    end = index + 1;
+                    chars.next();
+                }
+                (Token::Ident(&source[start..end]), start..end)
+            }
+
+            c if c.is_ascii_digit() => {
+                let mut end = start + 1;
+                while let Some(&(index, c)) = chars.peek() {
+                    if !c.is_ascii_digit() && c != '_' {
+                        break;
+                    }
+                    end = index + 1;
+                    chars.next();
+                }
+                (Token::Number(&source[start..end]), start..end)
+            }
+
+            '{' | '}' | '(' | ')' | '[' | ']' | '<' | '>' | ',' | ':' | ';' | '=' | '&' | '#'
+            | '-' => (Token::Punct(c), start..start + 1),
+
+            c => {
+                return Err(Error::UnexpectedCharacter {
+                    span: Span::new(source, start..start + c.len_utf8()),
+                    character: c,
+                })
+            }
+        };
+
+        tokens.push(token);
+    }
+
+    tokens.push((Token::Eof, source.len()..source.len()));
+    Ok(tokens)
+}
+
+struct Document {
+    /// The first item, if it isn't a definition.
+    root: Option<Type>,
+    definitions: Vec<Definition>,
+}
+
+struct Definition {
+    docs: Option<String>,
+    repr: Option<Primitive>,
+    name: String,
+    name_span: Range<usize>,
+    params: Vec<Type>,
+    body: Body,
+}
+
+impl Definition {
+    /// Returns the name used to refer to the definition, e.g. `Page<UserId>`.
+    fn key(&self) -> String {
+        path_key(&self.name, &self.params)
+    }
+}
+
+enum Body {
+    UnitStruct,
+    Struct(Vec<Field>),
+    TupleStruct {
+        elements: Vec<Type>,
+        trailing_comma: bool,
+    },
+    Enum(Vec<VariantDefinition>),
+}
+
+struct Field {
+    docs: Option<String>,
+    name: String,
+    ty: Type,
+}
+
+struct VariantDefinition {
+    docs: Option<String>,
+    name: String,
+    kind: VariantKind,
+}
+
+enum VariantKind {
+    Unit(Option<(i128, Range<usize>)>),
+    Tuple(Vec<Type>),
+    Struct(Vec<Field>),
+}
+
+enum Type {
+    Path {
+        name: String,
+        args: Vec<Type>,
+        span: Range<usize>,
+    },
+    Tuple(Vec<Type>),
+    Array(Box<Type>, usize),
+    Slice(Box<Type>),
+    Str,
+    Definition(Box<Definition>),
+}
+
+fn path_key(name: &str, args: &[Type]) -> String {
+    if args.is_empty() {
+        return name.into();
+    }
+
+    let args = args.iter().map(type_key).collect::<Vec<_>>();
+    format!("{}<{}>", name, args.join(", "))
+}
+
+fn type_key(ty: &Type) -> String {
+    match ty {
+        Type::Path { name, args, .. } => path_key(name, args),
+        Type::Tuple(elements) if elements.len() == 1 => format!("({},)", type_key(&elements[0])),
+        Type::Tuple(elements) => {
+            let elements = elements.iter().map(type_key).collect::<Vec<_>>();
+            format!("({})", elements.join(", "))
+        }
+        Type::Array(element, len) => format!("[{}; {}]", type_key(element), len),
+        Type::Slice(element) => format!("&[{}]", type_key(element)),
+        Type::Str => "&str".into(),
+        Type::Definition(definition) => definition.key(),
+    }
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<(Token<'a>, Range<usize>)>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Token<'a> {
+        self.tokens[self.position].0
+    }
+
+    fn next(&mut self) -> (Token<'a>, Range<usize>) {
+        let token = self.tokens[self.position].clone();
+        if token.0 != Token::Eof {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn unexpected(&self, expected: &'static str) -> Error {
+        let (token, range) = &self.tokens[self.position];
+        Error::UnexpectedToken {
+            span: Span::new(self.source, range.clone()),
+            expected,
+            found: token.to_string(),
+        }
+    }
+
+    /// Consumes the punctuation `punct` if it's the next token.
+    fn eat(&mut self, punct: char) -> bool {
+        if self.peek() == Token::Punct(punct) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: char, expected: &'static str) -> Result<(), Error> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn ident(&mut self, expected: &'static str) -> Result<(&'a str, Range<usize>), Error> {
+        match self.peek() {
+            Token::Ident(ident) => {
+                let (_, range) = self.next();
+                Ok((ident, range))
+            }
+            _ => Err(self.unexpected(expected)),
+        }
+    }
+
+    /// Consumes any doc comments, returning their combined text.
+    fn docs(&mut self) -> Option<String> {
+        let mut lines = Vec::new();
+        while let Token::Doc(line) = self.peek() {
+            lines.push(line);
+            self.next();
+        }
+
+        if lines.is_empty() {
+            None
+        } else {
+            Some(lines.join("\n"))
+        }
+    }
+
+    fn is_definition(&self) -> bool {
+        matches!(
+            self.peek(),
+            Token::Punct('#') | Token::Ident("struct") | Token::Ident("enum")
+        )
+    }
+
+    fn document(&mut self) -> Result<Document, Error> {
+        let mut document = Document {
+            root: None,
+            definitions: Vec::new(),
+        };
+
+        if !self.is_definition() && !matches!(self.peek(), Token::Doc(_)) {
+            document.root = Some(self.ty()?);
+            self.eat(';');
+        }
+
+        loop {
+            let docs = self.docs();
+            if self.peek() == Token::Eof && docs.is_none() {
+                break;
+            }
+
+            if !self.is_definition() {
+                return Err(self.unexpected("`struct` or `enum`"));
+            }
+            document.definitions.push(self.definition(docs)?);
+            self.eat(';');
+        }
+
+        if document.root.is_none() && document.definitions.is_empty() {
+            return Err(self.unexpected("a type"));
+        }
+
+        Ok(document)
+    }
+
+    fn definition(&mut self, docs: Option<String>) -> Result<Definition, Error> {
+        let repr = if self.eat('#') {
+            self.expect('[', "`[`")?;
+            match self.ident("`repr`")? {
+                ("repr", _) => {}
+                _ => {
+                    self.position -= 1;
+                    return Err(self.unexpected("`repr`"));
+                }
+            }
+            self.expect('(', "`(`")?;
+            let repr = self.primitive()?;
+            self.expect(')', "`)`")?;
+            self.expect(']', "`]`")?;
+            Some(repr)
+        } else {
+            None
+        };
+
+        let keyword = match self.peek() {
+            Token::Ident("enum") => "enum",
+            Token::Ident("struct") if repr.is_none() => "struct",
+            _ if repr.is_some() => return Err(self.unexpected("`enum`")),
+            _ => return Err(self.unexpected("`struct` or `enum`")),
+        };
+        self.next();
+
+        let (name, name_span) = self.ident("a type name")?;
+        let params = if self.eat('<') {
+            self.list('>', "a type", Self::ty)?.0
+        } else {
+            Vec::new()
+        };
+
+        let body = if keyword == "enum" {
+            self.expect('{', "`{`")?;
+            Body::Enum(self.list('}', "a variant", Self::variant)?.0)
+        } else if self.eat('{') {
+            Body::Struct(self.list('}', "a field", Self::field)?.0)
+        } else if self.eat('(') {
+            let (elements, trailing_comma) = self.list(')', "a type", Self::ty)?;
+            Body::TupleStruct {
+                elements,
+                trailing_comma,
+            }
+        } else {
+            Body::UnitStruct
+        };
+
+        Ok(Definition {
+            docs,
+            repr,
+            name: name.into(),
+            name_span,
+            params,
+            body,
+        })
+    }
+
+    fn primitive(&mut self) -> Result<Primitive, Error> {
+        let primitive = match self.peek() {
+            Token::Ident("u8") => Primitive::U8,
+            Token::Ident("u16") => Primitive::U16,
+            Token::Ident("u32") => Primitive::U32,
+            Token::Ident("u64") => Primitive::U64,
+            Token::Ident("u128") => Primitive::U128,
+            Token::Ident("usize") => Primitive::Usize,
+            Token::Ident("i8") => Primitive::I8,
+            Token::Ident("i16") => Primitive::I16,
+            Token::Ident("i32") => Primitive::I32,
+            Token::Ident("i64") => Primitive::I64,
+            Token::Ident("i128") => Primitive::I128,
+            Token::Ident("isize") => Primitive::Isize,
+            _ => return Err(self.unexpected("an integer type")),
+        };
+        self.next();
+
+        Ok(primitive)
+    }
+
+    /// Parses a comma-separated list of items up to the closing punctuation `end`,
+    /// and returns whether the last item was followed by a comma.
+    fn list<T>(
+        &mut self,
+        end: char,
+        expected: &'static str,
+        mut item: impl FnMut(&mut Self) -> Result<T, Error>,
+    ) -> Result<(Vec<T>, bool), Error> {
+        let mut items = Vec::new();
+        let mut trailing_comma = false;
+
+        while !self.eat(end) {
+            if !items.is_empty() && !trailing_comma {
+                return Err(self.unexpected(match end {
+                    '>' => "`,` or `>`",
+                    ')' => "`,` or `)`",
+                    _ => "`,` or `}`",
+                }));
+            }
+
+            if self.peek() == Token::Eof {
+                return Err(self.unexpected(expected));
+            }
+
+            items.push(item(self)?);
+            trailing_comma = self.eat(',');
+        }
+
+        Ok((items, trailing_comma))
+    }
+
+    fn field(&mut self) -> Result<Field, Error> {
+        let docs = self.docs();
+        let (name, _) = self.ident("a field name")?;
+        self.expect(':', "`:`")?;
+
+        Ok(Field {
+            docs,
+            name: name.into(),
+            ty: self.ty()?,
+        })
+    }
+
+    fn variant(&mut self) -> Result<VariantDefinition, Error> {
+        let docs = self.docs();
+        let (name, _) = self.ident("a variant name")?;
+
+        let kind = if self.eat('(') {
+            VariantKind::Tuple(self.list(')', "a type", Self::ty)?.0)
+        } else if self.eat('{') {
+            VariantKind::Struct(self.list('}', "a field", Self::field)?.0)
+        } else if self.eat('=') {
+            let negative = self.eat('-');
+            let (literal, range) = match self.next() {
+                (Token::Number(literal), range) => (literal, range),
+                _ => {
+                    self.position -= 1;
+                    return Err(self.unexpected("a discriminant"));
+                }
+            };
+
+            let value = self.number::<i128>(literal, range.clone())?;
+            VariantKind::Unit(Some((if negative { -value } else { value }, range)))
+        } else {
+            VariantKind::Unit(None)
+        };
+
+        Ok(VariantDefinition {
+            docs,
+            name: name.into(),
+            kind,
+        })
+    }
+
+    fn number<T: FromStr>(&self, literal: &str, range: Range<usize>) -> Result<T, Error> {
+        literal
+            .replace('_', "")
+            .parse()
+            .map_err(|_| Error::InvalidNumber {
+                span: Span::new(self.source, range),
+                literal: literal.into(),
+            })
+    }
+
+    fn type_name(&mut self) -> Result<TypeName, Error> {
+        match self.next() {
+            (Token::Punct('('), _) => {
+                let (mut params, trailing_comma) = self.list(')', "a type", Self::type_name)?;
+                if params.len() == 1 && !trailing_comma {
+                    Ok(params.remove(0))
+                } else {
+                    Ok(TypeName::generic("()", "", params))
+                }
+            }
+
+            (Token::Punct('['), _) => {
+                let element = self.type_name()?;
+                let name = if self.eat(';') {
+                    let len: usize = match self.next() {
+                        (Token::Number(literal), range) => self.number(literal, range)?,
+                        _ => {
+                            self.position -= 1;
+                            return Err(self.unexpected("an array length"));
+                        }
+                    };
+                    format!("[; {}]", len)
+                } else {
+                    "[]".into()
+                };
+                self.expect(']', "`;` or `]`")?;
+
+                Ok(TypeName::generic(name, "", vec![element]))
+            }
+
+            (Token::Ident(first), _) => {
+                let mut segments = vec![first];
+                while self.eat(':') {
+                    self.expect(':', "`::`")?;
+                    segments.push(self.ident("an identifier")?.0);
+                }
+                let name = segments.pop().unwrap();
+
+                let params = if self.eat('<') {
+                    self.list('>', "a type", Self::type_name)?.0
+                } else {
+                    Vec::new()
+                };
+
+                Ok(TypeName::generic(
+                    name.to_owned(),
+                    segments.join("::"),
+                    params,
+                ))
+            }
+
+            _ => {
+                self.position -= 1;
+                Err(self.unexpected("a type"))
+            }
+        }
+    }
+
+    fn ty(&mut self) -> Result<Type, Error> {
+        if self.is_definition() {
+            return Ok(Type::Definition(Box::new(self.definition(None)?)));
+        }
+
+        match self.next() {
+            (Token::Punct('('), _) => {
+                let (mut elements, trailing_comma) = self.list(')', "a type", Self::ty)?;
+
+                // A single type in parentheses is just that type, the same as in Rust.
+                if elements.len() == 1 && !trailing_comma {
+                    Ok(elements.remove(0))
+                } else {
+                    Ok(Type::Tuple(elements))
+                }
+            }
+
+            (Token::Punct('['), _) => {
+                let element = self.ty()?;
+                self.expect(';', "`;`")?;
+                let len = match self.next() {
+                    (Token::Number(literal), range) => self.number(literal, range)?,
+                    _ => {
+                        self.position -= 1;
+                        return Err(self.unexpected("an array length"));
+                    }
+                };
+                self.expect(']', "`]`")?;
+
+                Ok(Type::Array(Box::new(element), len))
+            }
+
+            (Token::Punct('&'), _) => {
+                if self.eat('[') {
+                    let element = self.ty()?;
+                    self.expect(']', "`]`")?;
+                    Ok(Type::Slice(Box::new(element)))
+                } else {
+                    match self.ident("`str` or `[`")? {
+                        ("str", _) => Ok(Type::Str),
+                        _ => {
+                            self.position -= 1;
+                            Err(self.unexpected("`str` or `[`"))
+                        }
+                    }
+                }
+            }
+
+            (Token::Ident(name), span) => {
+                let args = if self.eat('<') {
+                    self.list('>', "a type", Self::ty)?.0
+                } else {
+                    Vec::new()
+                };
+
+                Ok(Type::Path {
+                    name: name.into(),
+                    args,
+                    span,
+                })
+            }
+
+            _ => {
+                self.position -= 1;
+                Err(self.unexpected("a type"))
+            }
+        }
+    }
+}
+
+struct Resolver<'a> {
+    source: &'a str,
+    definitions: HashMap<String, &'a Definition>,
+
+    /// The keys of the definitions currently being resolved, used to detect types
+    /// that contain themselves.
+    resolving: Vec<String>,
+
+    /// The type parameters of each definition currently being resolved.
+    type_params: Vec<Vec<&'a str>>,
+}
+
+impl<'a> Resolver<'a> {
+    fn resolve(&mut self, ty: &'a Type) -> Result<Schema, Error> {
+        let schema = match ty {
+            Type::Str => Schema::Str,
+            Type::Tuple(elements) if elements.is_empty() => Schema::Unit,
+            Type::Tuple(elements) => Schema::Tuple(self.resolve_all(elements)?),
+            Type::Array(element, len) => Schema::Array(Box::new(crate::Array {
+                element: self.resolve(element)?,
+                len: *len,
+            })),
+            Type::Slice(element) => Schema::Slice(Box::new(self.resolve(element)?)),
+            Type::Definition(definition) => self.definition(definition)?,
+            Type::Path { name, args, span } => self.path(name, args, span)?,
+        };
+
+        Ok(schema)
+    }
+
+    fn resolve_all(&mut self, types: &'a [Type]) -> Result<Vec<Schema>, Error> {
+        types.iter().map(|ty| self.resolve(ty)).collect()
+    }
+
+    fn path(&mut self, name: &str, args: &'a [Type], span: &Range<usize>) -> Result<Schema, Error> {
+        let key = path_key(name, args);
+        if let Some(definition) = self.definitions.get(&key).copied() {
+            if self.resolving.contains(&key) {
+                return Err(Error::RecursiveType {
+                    span: Span::new(self.source, span.clone()),
+                    name: key,
+                });
+            }
+
+            return self.definition(definition);
+        }
+
+        if args.is_empty()
+            && self
+                .type_params
+                .last()
+                .is_some_and(|params| params.contains(&name))
+        {
+            return Ok(Schema::TypeParam(name.to_owned().into()));
+        }
+
+        let expected = match name {
+            "Option" | "Vec" | "VecDeque" | "HashSet" | "BTreeSet" | "BinaryHeap"
+            | "LinkedList" => 1,
+            "HashMap" | "BTreeMap" => 2,
+            _ => 0,
+        };
+
+        if args.len() != expected {
+            if expected == 0 {
+                return Err(Error::UnknownType {
+                    span: Span::new(self.source, span.clone()),
+                    name: key,
+                });
+            }
+
+            return Err(Error::WrongArguments {
+                span: Span::new(self.source, span.clone()),
+                name: name.into(),
+                expected,
+                found: args.len(),
+            });
+        }
+
+        let mut args = self.resolve_all(args)?.into_iter();
+        let mut arg = || args.next().unwrap();
+
+        let schema = match name {
+            "bool" => Schema::Bool,
+            "char" => Schema::Char,
+
+            "i8" => Schema::I8,
+            "i16" => Schema::I16,
+            "i32" => Schema::I32,
+            "i64" => Schema::I64,
+            "i128" => Schema::I128,
+            "isize" => Schema::ISize,
+
+            "u8" => Schema::U8,
+            "u16" => Schema::U16,
+            "u32" => Schema::U32,
+            "u64" => Schema::U64,
+            "u128" => Schema::U128,
+            "usize" => Schema::USize,
+
+            "f32" => Schema::F32,
+            "f64" => Schema::F64,
+
+            "String" => Schema::String(String::type_name()),
+            "Option" => Schema::Option(Box::new(arg())),
+
+            "Vec" => seq(Vec::<()>::type_name(), arg()),
+            "VecDeque" => seq(VecDeque::<()>::type_name(), arg()),
+            "HashSet" => seq(HashSet::<()>::type_name(), arg()),
+            "BTreeSet" => seq(BTreeSet::<()>::type_name(), arg()),
+            "BinaryHeap" => seq(BinaryHeap::<()>::type_name(), arg()),
+            "LinkedList" => seq(LinkedList::<()>::type_name(), arg()),
+
+            "HashMap" => map(HashMap::<(), ()>::type_name(), arg(), arg()),
+            "BTreeMap" => map(BTreeMap::<(), ()>::type_name(), arg(), arg()),
+
+            _ => {
+                return Err(Error::UnknownType {
+                    span: Span::new(self.source, span.clone()),
+                    name: key,
+                })
+            }
+        };
+
+        Ok(schema)
+    }
+
+    fn definition(&mut self, definition: &'a Definition) -> Result<Schema, Error> {
+        let key = definition.key();
+        if self.resolving.contains(&key) {
+            return Err(Error::RecursiveType {
+                span: Span::new(self.source, definition.name_span.clone()),
+                name: key,
+            });
+        }
+
+        self.resolving.push(key);
+        let schema = self
+            .definition_params(definition)
+            .and_then(|(params, type_params)| {
+                self.type_params.push(type_params);
+                let schema = self.definition_body(definition, params);
+                self.type_params.pop();
+                schema
+            });
+        self.resolving.pop();
+
+        schema
+    }
+
+    /// Resolves the parameters of a definition, treating parameters that don't name
+    /// a type as type parameters, as in `struct Page<T>`.
+    fn definition_params(
+        &mut self,
+        definition: &'a Definition,
+    ) -> Result<(Vec<TypeName>, Vec<&'a str>), Error> {
+        let mut params = Vec::new();
+        let mut type_params = Vec::new();
+        for param in &definition.params {
+            match (self.resolve(param), param) {
+                (Ok(schema), _) => params.push(schema.full_type_name()),
+                (Err(Error::UnknownType { .. }), Type::Path { name, args, .. })
+                    if args.is_empty() =>
+                {
+                    params.push(TypeName::new(name.clone(), ""));
+                    type_params.push(name.as_str());
+                }
+                (Err(error), _) => return Err(error),
+            }
+        }
+
+        Ok((params, type_params))
+    }
+
+    fn definition_body(
+        &mut self,
+        definition: &'a Definition,
+        params: Vec<TypeName>,
+    ) -> Result<Schema, Error> {
+        let name = TypeName::generic(definition.name.clone(), "", params);
+        let docs = definition.docs.clone().map(Cow::Owned);
+
+        let schema = match &definition.body {
+            Body::UnitStruct => Schema::UnitStruct(crate::UnitStruct { name, docs }),
+
+            Body::Struct(fields) => Schema::Struct(crate::Struct {
+                name,
+                docs,
+                attributes: Default::default(),
+                fields: self.fields(fields)?,
+                layout: None,
+            }),
+
+            Body::TupleStruct {
+                elements,
+                trailing_comma,
+            } if elements.len() == 1 && !trailing_comma => {
+                Schema::NewtypeStruct(Box::new(crate::NewtypeStruct {
+                    name,
+                    docs,
+                    inner: self.resolve(&elements[0])?,
+                    constraints: Default::default(),
+                }))
+            }
+
+            Body::TupleStruct { elements, .. } => Schema::TupleStruct(crate::TupleStruct {
+                name,
+                docs,
+                elements: self.resolve_all(elements)?,
+                layout: None,
+            }),
+
+            Body::Enum(variants) => {
+                let variants = variants
+                    .iter()
+                    .map(|variant| self.variant(variant, definition.repr))
+                    .collect::<Result<_, _>>()?;
+
+                Schema::Enum(crate::Enum {
+                    name,
+                    docs,
+                    repr: definition.repr,
+                    variants,
+                    layout: None,
+                })
+            }
+        };
+
+        Ok(schema)
+    }
+
+    fn fields(&mut self, fields: &'a [Field]) -> Result<Vec<NamedField>, Error> {
+        fields
+            .iter()
+            .map(|field| {
+                Ok(NamedField {
+                    name: field.name.clone().into(),
+                    docs: field.docs.clone().map(Cow::Owned),
+                    attributes: Default::default(),
+                    schema: self.resolve(&field.ty)?,
+                    constraints: Default::default(),
+                    default: None,
+                    skip_serializing_if: false,
+                })
+            })
+            .collect()
+    }
+
+    fn variant(
+        &mut self,
+        variant: &'a VariantDefinition,
+        repr: Option<Primitive>,
+    ) -> Result<Variant, Error> {
+        let name = Cow::Owned(variant.name.clone());
+        let docs = variant.docs.clone().map(Cow::Owned);
+
+        let variant = match &variant.kind {
+            VariantKind::Unit(discriminant) => Variant::Unit {
+                name,
+                docs,
+                attributes: Default::default(),
+                discriminant: match discriminant {
+                    Some((value, range)) => {
+                        Some(discriminant_value(*value, repr).ok_or_else(|| {
+                            Error::InvalidNumber {
+                                span: Span::new(self.source, range.clone()),
+                                literal: value.to_string(),
+                            }
+                        })?)
+                    }
+                    None => None,
+                },
+            },
+
+            VariantKind::Tuple(elements) => Variant::Tuple {
+                name,
+                docs,
+                attributes: Default::default(),
+                elements: self.resolve_all(elements)?,
+            },
+
+            VariantKind::Struct(fields) => Variant::Struct {
+                name,
+                docs,
+                attributes: Default::default(),
+                fields: self.fields(fields)?,
+            },
+        };
+
+        Ok(variant)
+    }
+}
+
+fn seq(name: TypeName, element: Schema) -> Schema {
+    Schema::Seq(Box::new(Sequence {
+        name: TypeName::generic(name.name, name.module, vec![element.full_type_name()]),
+        element,
+        len: None,
+    }))
+}
+
+fn map(name: TypeName, key: Schema, value: Schema) -> Schema {
+    Schema::Map(Box::new(crate::Map {
+        name: TypeName::generic(
+            name.name,
+            name.module,
+            vec![key.full_type_name(), value.full_type_name()],
+        ),
+        key,
+        value,
+    }))
+}
+
+/// Converts a discriminant to the `repr` of its enum, or `isize` if the enum has no
+/// explicit `repr`.
+fn discriminant_value(value: i128, repr: Option<Primitive>) -> Option<PrimitiveValue> {
+    let value = match repr.unwrap_or(Primitive::Isize) {
+        Primitive::U8 => PrimitiveValue::U8(u8::try_from(value).ok()?),
+        Primitive::U16 => PrimitiveValue::U16(u16::try_from(value).ok()?),
+        Primitive::U32 => PrimitiveValue::U32(u32::try_from(value).ok()?),
+        Primitive::U64 => PrimitiveValue::U64(u64::try_from(value).ok()?),
+        Primitive::U128 => PrimitiveValue::U128(u128::try_from(value).ok()?),
+        Primitive::Usize => PrimitiveValue::Usize(usize::try_from(value).ok()?),
+        Primitive::I8 => PrimitiveValue::I8(i8::try_from(value).ok()?),
+        Primitive::I16 => PrimitiveValue::I16(i16::try_from(value).ok()?),
+        Primitive::I32 => PrimitiveValue::I32(i32::try_from(value).ok()?),
+        Primitive::I64 => PrimitiveValue::I64(i64::try_from(value).ok()?),
+        Primitive::I128 => PrimitiveValue::I128(value),
+        Primitive::Isize => PrimitiveValue::Isize(isize::try_from(value).ok()?),
+    };
+
+    Some(value)
+}
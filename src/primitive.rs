@@ -1,8 +1,27 @@
 //! Implementations of `Describe` for primitives and types provided by the standard
 //! library.
+//!
+//! Type names follow a single scheme, so that they can be written and parsed in
+//! their qualified form (see `TypeName::qualified`):
+//!
+//! * Library types are named after the module that declares them, rather than the
+//!   one that re-exports them, e.g. `alloc::vec::Vec` and
+//!   `std::collections::hash_map::HashMap`.
+//! * Primitives have an empty module, e.g. `u8` and `str`.
+//! * Structural types have an empty module and a name that stands for their
+//!   syntax, with the element types as type parameters: `()` for tuples, `[]` for
+//!   slices and `[; N]` for arrays of length `N`.
 
 use crate::{describe::*, TypeName};
-use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use alloc::{
+    collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    format,
+    string::String,
+    vec,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
 
 /// Generates the `Describe` impl for primitives and collection types.
 ///
@@ -10,7 +29,7 @@ use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedL
 /// function and automatically adds the `Describe` bound for generic parameters. For
 /// example, invoking with `Collection<A, B> => describe_map` will expand to:
 ///
-/// ```
+/// ```ignore
 /// impl<A, B> Describe for Collection<A, B> where A: Describe, B: Describe {
 ///     fn describe<E: Describer>(describer: D) -> Result<> {
 ///         describer.describe_map::<A, B>()
@@ -91,12 +110,17 @@ macro_rules! describe_seq {
 describe_seq! {
     Vec => "alloc::vec",
     VecDeque => "alloc::collections::vec_deque",
-    HashSet => "std::collections::hash_set::HashSet",
     BTreeSet => "alloc::collections::btree_set",
     BinaryHeap => "alloc::collections::binary_heap",
-    LinkedList => "alloc::collections::LinkedList",
+    LinkedList => "alloc::collections::linked_list",
+}
+
+#[cfg(feature = "std")]
+describe_seq! {
+    HashSet => "std::collections::hash_set",
 }
 
+#[cfg(feature = "std")]
 impl<K: Describe, V: Describe> Describe for HashMap<K, V> {
     fn type_name() -> TypeName {
         TypeName::generic(
@@ -131,7 +155,7 @@ impl<K: Describe, V: Describe> Describe for BTreeMap<K, V> {
     }
 }
 
-impl<'a> Describe for &'a str {
+impl Describe for &str {
     fn type_name() -> TypeName {
         TypeName::new("str", "")
     }
@@ -210,7 +234,7 @@ describe_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
 describe_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
 describe_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
 
-impl<'a, T> Describe for &'a [T]
+impl<T> Describe for &[T]
 where
     T: Describe,
 {
@@ -237,7 +261,7 @@ macro_rules! describe_array {
         $(
             impl<T> Describe for [T; $len] where T: Describe {
                 fn type_name() -> TypeName {
-                    TypeName::generic(stringify!([;$len]), "", vec![T::type_name()])
+                    TypeName::generic(format!("[; {}]", $len), "", vec![T::type_name()])
                 }
 
                 fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
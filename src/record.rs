@@ -0,0 +1,949 @@
+//! Derive a schema by serializing values.
+//!
+//! For types that don't implement [`Describe`], such as types from other crates,
+//! a schema can still be recovered from their `Serialize` implementation. The
+//! [`Recorder`] serializes values with a serializer that records the calls made
+//! by the implementation, e.g. `serialize_struct("Foo", 2)` followed by two calls
+//! to `serialize_field`, and builds a schema from them.
+//!
+//! A single value only shows part of its type: an `Option` that is `None` doesn't
+//! reveal the inner type, and an enum value only reveals one variant. Recording
+//! several values merges what each of them shows, so the schema becomes more
+//! complete as more values are recorded:
+//!
+//! * All variants seen across values are included in the enum, in declaration
+//!   order.
+//! * `None` and `Some` merge into an `Option` of the inner type.
+//! * Elements of sequences and entries of maps are merged into a single element,
+//!   key, and value type.
+//! * Struct fields that are skipped in some values, e.g. with serde's
+//!   `skip_serializing_if`, become `Option` fields that default to `None`.
+//!
+//! Types that were never observed, like the inner type of an `Option` that was
+//! always `None`, are described as `()`. Serde only provides the name of a type,
+//! not its module, so named types in the schema have an empty module. Arrays and
+//! tuples are indistinguishable to a serializer, so arrays are described as tuples.
+//! All sequences are described as `Vec` and all maps as `HashMap`.
+//!
+//! Since the result depends on which values were recorded, prefer implementing
+//! `Describe` where possible and use recording as a best-effort fallback.
+//!
+//! [`Describe`]: ../trait.Describe.html
+//! [`Recorder`]: struct.Recorder.html
+
+use crate::{
+    Enum, FieldDefault, Map, NamedField, NewtypeStruct, Schema, Sequence, Struct, TupleStruct,
+    TypeName, UnitStruct, Variant,
+};
+use serde::ser::{self, Serialize};
+use std::fmt;
+
+/// Records a single value and returns the schema observed from it.
+///
+/// # Examples
+///
+/// ```
+/// use schematic::Schema;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Point {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// let schema = schematic::record::schema_of(&Point { x: 1.0, y: 2.0 }).unwrap();
+/// let point = schema.as_struct().unwrap();
+/// assert_eq!("Point", point.name.name);
+/// assert_eq!(Schema::F32, point.fields[1].schema);
+/// ```
+pub fn schema_of<T>(value: &T) -> Result<Schema, Error>
+where
+    T: Serialize + ?Sized,
+{
+    let mut recorder = Recorder::new();
+    recorder.record(value)?;
+    Ok(recorder.schema())
+}
+
+/// Builds a schema from the values of a type.
+///
+/// # Examples
+///
+/// ```
+/// use schematic::record::Recorder;
+///
+/// let mut recorder = Recorder::new();
+/// recorder.record(&None::<u32>).unwrap();
+/// assert_eq!(schematic::describe::<Option<()>>(), recorder.schema());
+///
+/// recorder.record(&Some(5_u32)).unwrap();
+/// assert_eq!(schematic::describe::<Option<u32>>(), recorder.schema());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Recorder {
+    shape: Shape,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Serializes `value` and merges what it shows into the recorded schema.
+    ///
+    /// If the value doesn't match the previously recorded values, the error is
+    /// returned and the recorded schema is left as it was.
+    pub fn record<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let shape = value.serialize(ShapeSerializer)?;
+        self.shape = merge(self.shape.clone(), shape)?;
+        Ok(())
+    }
+
+    /// Returns the schema built from all of the values recorded so far.
+    pub fn schema(&self) -> Schema {
+        self.shape.to_schema()
+    }
+}
+
+/// An error recording a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A value doesn't have the same shape as values previously recorded at the same
+    /// position, e.g. because a type serializes itself differently depending on its
+    /// value.
+    Conflict {
+        /// The path to the value, e.g. `/servers/*/port`. Fields and variants are
+        /// identified by name, tuple elements by index, the elements of sequences by
+        /// `*`, and the keys and values of maps by `<key>` and `<value>`.
+        path: String,
+
+        /// A description of the previously recorded shape.
+        expected: String,
+
+        /// A description of the shape of the value.
+        found: String,
+    },
+
+    /// The `Serialize` implementation of a value returned an error.
+    Custom(String),
+}
+
+impl Error {
+    /// Prefixes the path of a conflict with `segment`.
+    fn within(self, segment: &str) -> Self {
+        match self {
+            Error::Conflict {
+                path,
+                expected,
+                found,
+            } => Error::Conflict {
+                path: format!("/{}{}", segment, path),
+                expected,
+                found,
+            },
+
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Conflict {
+                path,
+                expected,
+                found,
+            } => write!(f, "expected {} but found {} at `{}`", expected, found, path),
+
+            Error::Custom(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Error::Custom(message.to_string())
+    }
+}
+
+/// What has been observed of a type.
+#[derive(Debug, Clone, Default)]
+enum Shape {
+    /// No value of the type has been observed.
+    #[default]
+    Unknown,
+
+    /// Any type without nested types, i.e. primitives, strings, and `()`.
+    Primitive(Schema),
+
+    Option(Box<Shape>),
+    Tuple(Vec<Shape>),
+    Seq(Box<Shape>),
+    Map(Box<Shape>, Box<Shape>),
+
+    UnitStruct(&'static str),
+    NewtypeStruct(&'static str, Box<Shape>),
+    TupleStruct(&'static str, Vec<Shape>),
+    Struct(&'static str, Fields),
+    Enum(&'static str, Vec<VariantShape>),
+}
+
+/// The fields of a struct or struct variant.
+#[derive(Debug, Clone)]
+struct Fields {
+    /// The number of values observed.
+    count: usize,
+    fields: Vec<FieldShape>,
+}
+
+#[derive(Debug, Clone)]
+struct FieldShape {
+    name: &'static str,
+
+    /// The number of values in which the field wasn't skipped.
+    count: usize,
+    shape: Shape,
+}
+
+#[derive(Debug, Clone)]
+struct VariantShape {
+    index: u32,
+    name: &'static str,
+    data: VariantData,
+}
+
+#[derive(Debug, Clone)]
+enum VariantData {
+    Unit,
+    Tuple(Vec<Shape>),
+    Struct(Fields),
+}
+
+impl Shape {
+    fn describe(&self) -> String {
+        match self {
+            Shape::Unknown => "nothing".into(),
+            Shape::Primitive(schema) => format!("`{}`", schema.full_type_name().name),
+            Shape::Option(_) => "an option".into(),
+            Shape::Tuple(elements) => format!("a tuple of {} elements", elements.len()),
+            Shape::Seq(_) => "a sequence".into(),
+            Shape::Map(..) => "a map".into(),
+            Shape::UnitStruct(name)
+            | Shape::NewtypeStruct(name, _)
+            | Shape::TupleStruct(name, _)
+            | Shape::Struct(name, _) => format!("struct `{}`", name),
+            Shape::Enum(name, _) => format!("enum `{}`", name),
+        }
+    }
+
+    fn to_schema(&self) -> Schema {
+        match self {
+            Shape::Unknown => Schema::Unit,
+            Shape::Primitive(schema) => schema.clone(),
+            Shape::Option(inner) => Schema::Option(Box::new(inner.to_schema())),
+            Shape::Tuple(elements) => {
+                Schema::Tuple(elements.iter().map(Shape::to_schema).collect())
+            }
+
+            Shape::Seq(element) => {
+                let element = element.to_schema();
+                Schema::Seq(Box::new(Sequence {
+                    name: TypeName::generic("Vec", "alloc::vec", vec![element.full_type_name()]),
+                    element,
+                    len: None,
+                }))
+            }
+
+            Shape::Map(key, value) => {
+                let key = key.to_schema();
+                let value = value.to_schema();
+                Schema::Map(Box::new(Map {
+                    name: TypeName::generic(
+                        "HashMap",
+                        "std::collections::hash_map",
+                        vec![key.full_type_name(), value.full_type_name()],
+                    ),
+                    key,
+                    value,
+                }))
+            }
+
+            Shape::UnitStruct(name) => Schema::UnitStruct(UnitStruct {
+                name: TypeName::new(*name, ""),
+                docs: None,
+            }),
+
+            Shape::NewtypeStruct(name, inner) => Schema::NewtypeStruct(Box::new(NewtypeStruct {
+                name: TypeName::new(*name, ""),
+                docs: None,
+                inner: inner.to_schema(),
+                constraints: Default::default(),
+            })),
+
+            Shape::TupleStruct(name, elements) => Schema::TupleStruct(TupleStruct {
+                name: TypeName::new(*name, ""),
+                docs: None,
+                elements: elements.iter().map(Shape::to_schema).collect(),
+                layout: None,
+            }),
+
+            Shape::Struct(name, fields) => Schema::Struct(Struct {
+                name: TypeName::new(*name, ""),
+                docs: None,
+                attributes: Default::default(),
+                fields: fields.to_schema(),
+                layout: None,
+            }),
+
+            Shape::Enum(name, variants) => Schema::Enum(Enum {
+                name: TypeName::new(*name, ""),
+                docs: None,
+                repr: None,
+                variants: variants
+                    .iter()
+                    .map(|variant| match &variant.data {
+                        VariantData::Unit => Variant::Unit {
+                            name: variant.name.into(),
+                            docs: None,
+                            attributes: Default::default(),
+                            discriminant: None,
+                        },
+                        VariantData::Tuple(elements) => Variant::Tuple {
+                            name: variant.name.into(),
+                            docs: None,
+                            attributes: Default::default(),
+                            elements: elements.iter().map(Shape::to_schema).collect(),
+                        },
+                        VariantData::Struct(fields) => Variant::Struct {
+                            name: variant.name.into(),
+                            docs: None,
+                            attributes: Default::default(),
+                            fields: fields.to_schema(),
+                        },
+                    })
+                    .collect(),
+                layout: None,
+            }),
+        }
+    }
+}
+
+impl VariantData {
+    fn describe(&self, name: &str) -> String {
+        match self {
+            VariantData::Unit => format!("unit variant `{}`", name),
+            VariantData::Tuple(elements) => {
+                format!("tuple variant `{}` of {} elements", name, elements.len())
+            }
+            VariantData::Struct(_) => format!("struct variant `{}`", name),
+        }
+    }
+}
+
+impl Fields {
+    fn new() -> Self {
+        Fields {
+            count: 1,
+            fields: Vec::new(),
+        }
+    }
+
+    fn to_schema(&self) -> Vec<NamedField> {
+        self.fields
+            .iter()
+            .map(|field| {
+                let skipped = field.count < self.count;
+                let schema = match &field.shape {
+                    Shape::Option(_) => field.shape.to_schema(),
+
+                    // Fields that are skipped in some values are presumably optional.
+                    shape if skipped => Schema::Option(Box::new(shape.to_schema())),
+
+                    shape => shape.to_schema(),
+                };
+
+                NamedField {
+                    default: skipped.then(|| FieldDefault::Value(serde_json::Value::Null)),
+                    skip_serializing_if: skipped,
+                    ..NamedField::new(field.name, schema)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Merges two observations of the same type.
+fn merge(first: Shape, second: Shape) -> Result<Shape, Error> {
+    let conflict = |first: &Shape, second: &Shape| Error::Conflict {
+        path: String::new(),
+        expected: first.describe(),
+        found: second.describe(),
+    };
+
+    Ok(match (first, second) {
+        (Shape::Unknown, shape) | (shape, Shape::Unknown) => shape,
+
+        (Shape::Primitive(first), Shape::Primitive(second)) if first == second => {
+            Shape::Primitive(first)
+        }
+
+        (Shape::Option(first), Shape::Option(second)) => {
+            Shape::Option(Box::new(merge(*first, *second)?))
+        }
+
+        (Shape::Tuple(first), Shape::Tuple(second)) if first.len() == second.len() => {
+            Shape::Tuple(merge_elements(first, second)?)
+        }
+
+        (Shape::Seq(first), Shape::Seq(second)) => Shape::Seq(Box::new(
+            merge(*first, *second).map_err(|error| error.within("*"))?,
+        )),
+
+        (Shape::Map(first_key, first_value), Shape::Map(second_key, second_value)) => Shape::Map(
+            Box::new(merge(*first_key, *second_key).map_err(|error| error.within("<key>"))?),
+            Box::new(merge(*first_value, *second_value).map_err(|error| error.within("<value>"))?),
+        ),
+
+        (Shape::UnitStruct(first), Shape::UnitStruct(second)) if first == second => {
+            Shape::UnitStruct(first)
+        }
+
+        (Shape::NewtypeStruct(first, first_inner), Shape::NewtypeStruct(second, second_inner))
+            if first == second =>
+        {
+            let inner = merge(*first_inner, *second_inner).map_err(|error| error.within("0"))?;
+            Shape::NewtypeStruct(first, Box::new(inner))
+        }
+
+        (
+            Shape::TupleStruct(first, first_elements),
+            Shape::TupleStruct(second, second_elements),
+        ) if first == second && first_elements.len() == second_elements.len() => {
+            Shape::TupleStruct(first, merge_elements(first_elements, second_elements)?)
+        }
+
+        (Shape::Struct(first, first_fields), Shape::Struct(second, second_fields))
+            if first == second =>
+        {
+            Shape::Struct(first, merge_fields(first_fields, second_fields)?)
+        }
+
+        (Shape::Enum(first, mut variants), Shape::Enum(second, second_variants))
+            if first == second =>
+        {
+            for variant in second_variants {
+                let existing = match variants
+                    .iter_mut()
+                    .find(|other| other.index == variant.index)
+                {
+                    Some(existing) => existing,
+                    None => {
+                        variants.push(variant);
+                        continue;
+                    }
+                };
+
+                let name = variant.name;
+                let data = std::mem::replace(&mut existing.data, VariantData::Unit);
+                existing.data = match (data, variant.data) {
+                    (VariantData::Unit, VariantData::Unit) => VariantData::Unit,
+                    (VariantData::Tuple(first), VariantData::Tuple(second))
+                        if first.len() == second.len() =>
+                    {
+                        VariantData::Tuple(
+                            merge_elements(first, second).map_err(|error| error.within(name))?,
+                        )
+                    }
+                    (VariantData::Struct(first), VariantData::Struct(second)) => {
+                        VariantData::Struct(
+                            merge_fields(first, second).map_err(|error| error.within(name))?,
+                        )
+                    }
+                    (first, second) => {
+                        return Err(Error::Conflict {
+                            path: format!("/{}", name),
+                            expected: first.describe(name),
+                            found: second.describe(name),
+                        })
+                    }
+                };
+            }
+
+            variants.sort_by_key(|variant| variant.index);
+            Shape::Enum(first, variants)
+        }
+
+        (first, second) => return Err(conflict(&first, &second)),
+    })
+}
+
+fn merge_elements(first: Vec<Shape>, second: Vec<Shape>) -> Result<Vec<Shape>, Error> {
+    first
+        .into_iter()
+        .zip(second)
+        .enumerate()
+        .map(|(index, (first, second))| {
+            merge(first, second).map_err(|error| error.within(&index.to_string()))
+        })
+        .collect()
+}
+
+fn merge_fields(mut first: Fields, second: Fields) -> Result<Fields, Error> {
+    first.count += second.count;
+    for field in second.fields {
+        match first
+            .fields
+            .iter_mut()
+            .find(|other| other.name == field.name)
+        {
+            Some(existing) => {
+                existing.count += field.count;
+                let shape = std::mem::take(&mut existing.shape);
+                let name = field.name;
+                existing.shape = merge(shape, field.shape).map_err(|error| error.within(name))?;
+            }
+            None => first.fields.push(field),
+        }
+    }
+
+    Ok(first)
+}
+
+/// A serializer that returns the shape of the serialized value.
+struct ShapeSerializer;
+
+impl ShapeSerializer {
+    fn primitive(schema: Schema) -> Result<Shape, Error> {
+        Ok(Shape::Primitive(schema))
+    }
+}
+
+impl ser::Serializer for ShapeSerializer {
+    type Ok = Shape;
+    type Error = Error;
+
+    type SerializeSeq = SerializeSeq;
+    type SerializeTuple = SerializeTuple;
+    type SerializeTupleStruct = SerializeTuple;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeStruct;
+    type SerializeStructVariant = SerializeStructVariant;
+
+    fn serialize_bool(self, _: bool) -> Result<Shape, Error> {
+        Self::primitive(Schema::Bool)
+    }
+
+    fn serialize_i8(self, _: i8) -> Result<Shape, Error> {
+        Self::primitive(Schema::I8)
+    }
+
+    fn serialize_i16(self, _: i16) -> Result<Shape, Error> {
+        Self::primitive(Schema::I16)
+    }
+
+    fn serialize_i32(self, _: i32) -> Result<Shape, Error> {
+        Self::primitive(Schema::I32)
+    }
+
+    fn serialize_i64(self, _: i64) -> Result<Shape, Error> {
+        Self::primitive(Schema::I64)
+    }
+
+    fn serialize_i128(self, _: i128) -> Result<Shape, Error> {
+        Self::primitive(Schema::I128)
+    }
+
+    fn serialize_u8(self, _: u8) -> Result<Shape, Error> {
+        Self::primitive(Schema::U8)
+    }
+
+    fn serialize_u16(self, _: u16) -> Result<Shape, Error> {
+        Self::primitive(Schema::U16)
+    }
+
+    fn serialize_u32(self, _: u32) -> Result<Shape, Error> {
+        Self::primitive(Schema::U32)
+    }
+
+    fn serialize_u64(self, _: u64) -> Result<Shape, Error> {
+        Self::primitive(Schema::U64)
+    }
+
+    fn serialize_u128(self, _: u128) -> Result<Shape, Error> {
+        Self::primitive(Schema::U128)
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<Shape, Error> {
+        Self::primitive(Schema::F32)
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<Shape, Error> {
+        Self::primitive(Schema::F64)
+    }
+
+    fn serialize_char(self, _: char) -> Result<Shape, Error> {
+        Self::primitive(Schema::Char)
+    }
+
+    // Serde doesn't distinguish between borrowed and owned strings, so assume the
+    // owned type since that's what can be deserialized.
+    fn serialize_str(self, _: &str) -> Result<Shape, Error> {
+        Self::primitive(Schema::String(TypeName::new("String", "alloc::string")))
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<Shape, Error> {
+        Ok(Shape::Seq(Box::new(Shape::Primitive(Schema::U8))))
+    }
+
+    fn serialize_none(self) -> Result<Shape, Error> {
+        Ok(Shape::Option(Box::new(Shape::Unknown)))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Shape, Error> {
+        Ok(Shape::Option(Box::new(value.serialize(self)?)))
+    }
+
+    fn serialize_unit(self) -> Result<Shape, Error> {
+        Self::primitive(Schema::Unit)
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Shape, Error> {
+        Ok(Shape::UnitStruct(name))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+    ) -> Result<Shape, Error> {
+        Ok(Shape::Enum(
+            name,
+            vec![VariantShape {
+                index,
+                name: variant,
+                data: VariantData::Unit,
+            }],
+        ))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Shape, Error> {
+        let inner = value.serialize(self).map_err(|error| error.within("0"))?;
+        Ok(Shape::NewtypeStruct(name, Box::new(inner)))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Shape, Error> {
+        let inner = value
+            .serialize(self)
+            .map_err(|error| error.within("0").within(variant))?;
+
+        Ok(Shape::Enum(
+            name,
+            vec![VariantShape {
+                index,
+                name: variant,
+                data: VariantData::Tuple(vec![inner]),
+            }],
+        ))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<SerializeSeq, Error> {
+        Ok(SerializeSeq {
+            element: Shape::Unknown,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeTuple, Error> {
+        Ok(SerializeTuple {
+            name: None,
+            elements: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<SerializeTuple, Error> {
+        Ok(SerializeTuple {
+            name: Some(name),
+            elements: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeTupleVariant, Error> {
+        Ok(SerializeTupleVariant {
+            name,
+            index,
+            variant,
+            elements: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap {
+            key: Shape::Unknown,
+            value: Shape::Unknown,
+        })
+    }
+
+    fn serialize_struct(self, name: &'static str, _: usize) -> Result<SerializeStruct, Error> {
+        Ok(SerializeStruct {
+            name,
+            fields: Fields::new(),
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<SerializeStructVariant, Error> {
+        Ok(SerializeStructVariant {
+            name,
+            index,
+            variant,
+            fields: Fields::new(),
+        })
+    }
+}
+
+struct SerializeSeq {
+    element: Shape,
+}
+
+impl ser::SerializeSeq for SerializeSeq {
+    type Ok = Shape;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let shape = value.serialize(ShapeSerializer);
+        let element = std::mem::take(&mut self.element);
+        self.element = shape
+            .and_then(|shape| merge(element, shape))
+            .map_err(|error| error.within("*"))?;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Shape, Error> {
+        Ok(Shape::Seq(Box::new(self.element)))
+    }
+}
+
+struct SerializeTuple {
+    /// The name of the tuple struct, or `None` for tuples.
+    name: Option<&'static str>,
+    elements: Vec<Shape>,
+}
+
+impl ser::SerializeTuple for SerializeTuple {
+    type Ok = Shape;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let index = self.elements.len().to_string();
+        let shape = value
+            .serialize(ShapeSerializer)
+            .map_err(|error| error.within(&index))?;
+        self.elements.push(shape);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Shape, Error> {
+        Ok(match self.name {
+            Some(name) => Shape::TupleStruct(name, self.elements),
+            None => Shape::Tuple(self.elements),
+        })
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeTuple {
+    type Ok = Shape;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeTuple::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Shape, Error> {
+        ser::SerializeTuple::end(self)
+    }
+}
+
+struct SerializeTupleVariant {
+    name: &'static str,
+    index: u32,
+    variant: &'static str,
+    elements: Vec<Shape>,
+}
+
+impl ser::SerializeTupleVariant for SerializeTupleVariant {
+    type Ok = Shape;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let index = self.elements.len().to_string();
+        let shape = value
+            .serialize(ShapeSerializer)
+            .map_err(|error| error.within(&index).within(self.variant))?;
+        self.elements.push(shape);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Shape, Error> {
+        Ok(Shape::Enum(
+            self.name,
+            vec![VariantShape {
+                index: self.index,
+                name: self.variant,
+                data: VariantData::Tuple(self.elements),
+            }],
+        ))
+    }
+}
+
+struct SerializeMap {
+    key: Shape,
+    value: Shape,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Shape;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        let shape = key.serialize(ShapeSerializer);
+        let existing = std::mem::take(&mut self.key);
+        self.key = shape
+            .and_then(|shape| merge(existing, shape))
+            .map_err(|error| error.within("<key>"))?;
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let shape = value.serialize(ShapeSerializer);
+        let existing = std::mem::take(&mut self.value);
+        self.value = shape
+            .and_then(|shape| merge(existing, shape))
+            .map_err(|error| error.within("<value>"))?;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Shape, Error> {
+        Ok(Shape::Map(Box::new(self.key), Box::new(self.value)))
+    }
+}
+
+/// Records the fields of a struct or struct variant.
+fn record_field<T: Serialize + ?Sized>(
+    fields: &mut Fields,
+    name: &'static str,
+    value: &T,
+) -> Result<(), Error> {
+    let shape = value
+        .serialize(ShapeSerializer)
+        .map_err(|error| error.within(name))?;
+    fields.fields.push(FieldShape {
+        name,
+        count: 1,
+        shape,
+    });
+    Ok(())
+}
+
+fn skip_field(fields: &mut Fields, name: &'static str) {
+    fields.fields.push(FieldShape {
+        name,
+        count: 0,
+        shape: Shape::Unknown,
+    });
+}
+
+struct SerializeStruct {
+    name: &'static str,
+    fields: Fields,
+}
+
+impl ser::SerializeStruct for SerializeStruct {
+    type Ok = Shape;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        record_field(&mut self.fields, name, value)
+    }
+
+    fn skip_field(&mut self, name: &'static str) -> Result<(), Error> {
+        skip_field(&mut self.fields, name);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Shape, Error> {
+        Ok(Shape::Struct(self.name, self.fields))
+    }
+}
+
+struct SerializeStructVariant {
+    name: &'static str,
+    index: u32,
+    variant: &'static str,
+    fields: Fields,
+}
+
+impl ser::SerializeStructVariant for SerializeStructVariant {
+    type Ok = Shape;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        record_field(&mut self.fields, name, value).map_err(|error| error.within(self.variant))
+    }
+
+    fn skip_field(&mut self, name: &'static str) -> Result<(), Error> {
+        skip_field(&mut self.fields, name);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Shape, Error> {
+        Ok(Shape::Enum(
+            self.name,
+            vec![VariantShape {
+                index: self.index,
+                name: self.variant,
+                data: VariantData::Struct(self.fields),
+            }],
+        ))
+    }
+}
@@ -0,0 +1,116 @@
+//! A link-time registry of describable types.
+//!
+//! Types are added to the registry with [`export!`], anywhere in the crate graph,
+//! and [`all`] lists every registered type. This makes it possible to dump the
+//! schema of every public type from a single test or binary without maintaining a
+//! list of types by hand:
+//!
+//! ```
+//! # use schematic::{Describe, Describer, TypeName};
+//! pub struct Config;
+//! # impl Describe for Config {
+//! #     fn type_name() -> TypeName {
+//! #         schematic::type_name!(Config)
+//! #     }
+//! #
+//! #     fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+//! #         describer.describe_unit_struct(Self::type_name())
+//! #     }
+//! # }
+//!
+//! schematic::export!(Config);
+//!
+//! for (name, describe) in schematic::registry::all() {
+//!     let json = serde_json::to_string(&describe()).unwrap();
+//!     println!("{}: {}", name.name, json);
+//! }
+//! ```
+//!
+//! Registration happens when the program starts, using [inventory], so it only
+//! works on platforms supported by inventory. This module is only available with
+//! the `registry` feature.
+//!
+//! [`export!`]: ../macro.export.html
+//! [`all`]: fn.all.html
+//! [inventory]: https://crates.io/crates/inventory
+
+use self::__private::Registration;
+use crate::{Schema, TypeName};
+
+/// Returns every type registered with [`export!`], along with a function that
+/// describes it.
+///
+/// Types are ordered by module, then by name, and then by their fully-qualified
+/// name, which tells instantiations of a generic type apart. Types that were
+/// registered more than once are only listed once.
+///
+/// [`export!`]: ../macro.export.html
+pub fn all() -> impl Iterator<Item = (TypeName, fn() -> Schema)> {
+    let mut types = inventory::iter::<Registration>
+        .into_iter()
+        .map(|registration| ((registration.type_name)(), registration.describe))
+        .collect::<Vec<_>>();
+
+    types
+        .sort_by_cached_key(|(name, _)| (name.module.clone(), name.name.clone(), name.qualified()));
+    types.dedup_by(|(left, _), (right, _)| left == right);
+
+    types.into_iter()
+}
+
+/// Adds one or more types to the registry listed by [`registry::all`].
+///
+/// Each type must implement [`Describe`]. The macro can be invoked anywhere an
+/// item is allowed, usually right after the type's `Describe` impl:
+///
+/// ```
+/// # use schematic::{Describe, Describer, TypeName};
+/// pub struct UserId(u64);
+///
+/// impl Describe for UserId {
+///     fn type_name() -> TypeName {
+///         schematic::type_name!(UserId)
+///     }
+///
+///     fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+///         describer.describe_newtype_struct::<u64>(Self::type_name())
+///     }
+/// }
+///
+/// schematic::export!(UserId, Vec<UserId>);
+///
+/// assert!(schematic::registry::all().any(|(name, _)| name.name == "UserId"));
+/// ```
+///
+/// This macro is only available with the `registry` feature.
+///
+/// [`registry::all`]: registry/fn.all.html
+/// [`Describe`]: trait.Describe.html
+#[macro_export]
+macro_rules! export {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            $crate::registry::__private::inventory::submit! {
+                $crate::registry::__private::Registration {
+                    type_name: <$ty as $crate::Describe>::type_name,
+                    describe: $crate::describe::<$ty>,
+                }
+            }
+        )+
+    };
+}
+
+inventory::collect!(Registration);
+
+#[doc(hidden)]
+pub mod __private {
+    pub use inventory;
+
+    use crate::{Schema, TypeName};
+
+    /// An entry in the registry, created by `export!`.
+    pub struct Registration {
+        pub type_name: fn() -> TypeName,
+        pub describe: fn() -> Schema,
+    }
+}
@@ -0,0 +1,936 @@
+//! Generate Rust type definitions from a schema.
+//!
+//! This is useful when schemas come from elsewhere, e.g. a serialized [`Schema`]
+//! received from another service, and you want Rust types that serialize to the
+//! same format. Each user-defined type reachable from the root schema becomes a
+//! `struct` or `enum` deriving `Serialize` and `Deserialize`, along with an
+//! implementation of [`Describe`] that reproduces the original schema:
+//!
+//! ```ignore
+//! let schema: Schema = serde_json::from_str(&input)?;
+//! let source = schematic::rust::generate(&schema)?;
+//! std::fs::write(out_dir.join("types.rs"), source)?;
+//! ```
+//!
+//! Names that aren't valid Rust identifiers are converted to ones that are, with a
+//! `#[serde(rename = "...")]` attribute preserving the original name. Sequences,
+//! maps, and strings are generated as the corresponding standard library type, so
+//! custom collection types in the input schema will round-trip as their `std`
+//! equivalents. Borrowed types (`&str` and `&[T]`) cannot be generated.
+//!
+//! Instantiations of generic types are generated as distinct types, e.g. `Page<User>`
+//! becomes `PageUser`. Generic definitions, described with [`type_param!`]
+//! placeholders, are generated as generic types instead.
+//!
+//! [`Schema`]: ../enum.Schema.html
+//! [`Describe`]: ../trait.Describe.html
+//! [`type_param!`]: ../macro.type_param.html
+
+use crate::{
+    Attribute, Attributes, Constraints, FieldDefault, NamedField, Number, Schema, TypeName, Variant,
+};
+use std::fmt::{self, Write};
+
+/// Generates Rust definitions for `schema` and every type it depends on.
+///
+/// # Examples
+///
+/// ```
+/// use schematic::{NamedField, Schema, Struct, TypeName};
+///
+/// let schema = Schema::Struct(Struct {
+///     name: TypeName::new("Point", "my_crate"),
+///     docs: None,
+///     attributes: Default::default(),
+///     fields: vec![NamedField::new("x", Schema::F32)],
+///     layout: None,
+/// });
+///
+/// let source = schematic::rust::generate(&schema).unwrap();
+/// assert!(source.contains("pub struct Point {\n    pub x: f32,\n}"));
+/// ```
+pub fn generate(schema: &Schema) -> Result<String, Error> {
+    let mut output = String::new();
+
+    for ty in schema.named_types() {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+
+        write_definition(&mut output, ty)?;
+        output.push('\n');
+        write_describe_impl(&mut output, ty)?;
+    }
+
+    Ok(output)
+}
+
+/// An error generating Rust code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The schema contains a type that cannot be generated, such as a borrowed type.
+    Unsupported(Box<Schema>),
+}
+
+impl Error {
+    fn unsupported(schema: &Schema) -> Self {
+        Error::Unsupported(Box::new(schema.clone()))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Unsupported(schema) => write!(f, "cannot generate Rust type for {:?}", schema),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+fn write_definition(output: &mut String, schema: &Schema) -> Result<(), Error> {
+    let name = schema.type_name().unwrap();
+    let (ident, params) = definition_ident(schema);
+
+    write_docs(output, "", schema.docs());
+    match schema {
+        Schema::Struct(schema) if schema.layout.is_some() => {
+            writeln!(output, "#[repr(C)]").unwrap()
+        }
+        Schema::TupleStruct(schema) if schema.layout.is_some() => {
+            writeln!(output, "#[repr(C)]").unwrap()
+        }
+        Schema::Enum(enum_) => match enum_.repr {
+            Some(repr) => writeln!(output, "#[repr({})]", repr).unwrap(),
+
+            // Rust only allows explicit discriminants on enums with data if the enum
+            // has a primitive representation.
+            None if enum_.has_data()
+                && enum_.variants.iter().any(|variant| {
+                    matches!(
+                        variant,
+                        Variant::Unit {
+                            discriminant: Some(_),
+                            ..
+                        }
+                    )
+                }) =>
+            {
+                return Err(Error::unsupported(schema));
+            }
+
+            None => {}
+        },
+        _ => {}
+    }
+    writeln!(
+        output,
+        "#[derive(::serde::Serialize, ::serde::Deserialize)]"
+    )
+    .unwrap();
+    if ident != name.name {
+        writeln!(output, "#[serde(rename = {:?})]", name.name).unwrap();
+    }
+
+    let ident = format!("{}{}", ident, generics(&params, ""));
+    match schema {
+        Schema::UnitStruct(_) => writeln!(output, "pub struct {};", ident).unwrap(),
+
+        Schema::NewtypeStruct(schema) => writeln!(
+            output,
+            "pub struct {}(pub {});",
+            ident,
+            rust_type(&schema.inner)?
+        )
+        .unwrap(),
+
+        Schema::TupleStruct(schema) => {
+            let elements = schema
+                .elements
+                .iter()
+                .map(|element| Ok(format!("pub {}", rust_type(element)?)))
+                .collect::<Result<Vec<_>, _>>()?;
+            writeln!(output, "pub struct {}({});", ident, elements.join(", ")).unwrap();
+        }
+
+        Schema::Struct(schema) => {
+            writeln!(output, "pub struct {} {{", ident).unwrap();
+            for field in &schema.fields {
+                write_field(output, "    ", "pub ", field)?;
+            }
+            writeln!(output, "}}").unwrap();
+        }
+
+        Schema::Enum(schema) => {
+            writeln!(output, "pub enum {} {{", ident).unwrap();
+            for variant in &schema.variants {
+                write_variant(output, variant)?;
+            }
+            writeln!(output, "}}").unwrap();
+        }
+
+        _ => unreachable!("only named types have definitions"),
+    }
+
+    Ok(())
+}
+
+fn write_variant(output: &mut String, variant: &Variant) -> Result<(), Error> {
+    let ident = variant_ident(variant.name());
+
+    write_docs(output, "    ", variant.docs());
+    if ident != variant.name() {
+        writeln!(output, "    #[serde(rename = {:?})]", variant.name()).unwrap();
+    }
+
+    match variant {
+        Variant::Unit {
+            discriminant: Some(discriminant),
+            ..
+        } => writeln!(output, "    {} = {},", ident, discriminant).unwrap(),
+
+        Variant::Unit { .. } => writeln!(output, "    {},", ident).unwrap(),
+
+        Variant::Tuple { elements, .. } => {
+            let elements = elements
+                .iter()
+                .map(rust_type)
+                .collect::<Result<Vec<_>, _>>()?;
+            writeln!(output, "    {}({}),", ident, elements.join(", ")).unwrap();
+        }
+
+        Variant::Struct { fields, .. } => {
+            writeln!(output, "    {} {{", ident).unwrap();
+            for field in fields {
+                write_field(output, "        ", "", field)?;
+            }
+            writeln!(output, "    }},").unwrap();
+        }
+    }
+
+    Ok(())
+}
+
+fn write_field(
+    output: &mut String,
+    indent: &str,
+    visibility: &str,
+    field: &NamedField,
+) -> Result<(), Error> {
+    let ident = field_ident(&field.name);
+
+    write_docs(output, indent, field.docs.as_deref());
+    if ident.trim_start_matches("r#") != field.name {
+        writeln!(output, "{}#[serde(rename = {:?})]", indent, field.name).unwrap();
+    }
+
+    // Only `Option` fields have a default and a skip predicate that can be
+    // reproduced without the original code.
+    if let Schema::Option(_) = field.schema {
+        let mut args = Vec::new();
+        if field.default == Some(FieldDefault::Value(serde_json::Value::Null)) {
+            args.push("default");
+        }
+        if field.skip_serializing_if {
+            args.push("skip_serializing_if = \"::std::option::Option::is_none\"");
+        }
+        if !args.is_empty() {
+            writeln!(output, "{}#[serde({})]", indent, args.join(", ")).unwrap();
+        }
+    }
+
+    writeln!(
+        output,
+        "{}{}{}: {},",
+        indent,
+        visibility,
+        ident,
+        rust_type(&field.schema)?,
+    )
+    .unwrap();
+
+    Ok(())
+}
+
+fn write_docs(output: &mut String, indent: &str, docs: Option<&str>) {
+    if let Some(docs) = docs {
+        for line in docs.split('\n') {
+            writeln!(output, "{}///{}", indent, line).unwrap();
+        }
+    }
+}
+
+fn write_describe_impl(output: &mut String, schema: &Schema) -> Result<(), Error> {
+    let name = schema.type_name().unwrap();
+    let (ident, params) = definition_ident(schema);
+
+    writeln!(
+        output,
+        "impl{} ::schematic::Describe for {}{} {{",
+        generics(&params, ": ::schematic::Describe"),
+        ident,
+        generics(&params, ""),
+    )
+    .unwrap();
+    writeln!(output, "    fn type_name() -> ::schematic::TypeName {{").unwrap();
+    writeln!(output, "        {}", type_name_expr(name, &params)).unwrap();
+    writeln!(output, "    }}").unwrap();
+    writeln!(output).unwrap();
+    writeln!(
+        output,
+        "    fn describe<D: ::schematic::Describer>(describer: D) -> ::std::result::Result<D::Ok, D::Error> {{",
+    )
+    .unwrap();
+
+    let (attributes, constraints) = match schema {
+        Schema::Struct(schema) => (Some(&schema.attributes), None),
+        Schema::NewtypeStruct(schema) => (None, Some(&schema.constraints)),
+        _ => (None, None),
+    };
+    let info = match info_expr(
+        "TypeInfo",
+        3,
+        schema.docs(),
+        attributes,
+        constraints,
+        None,
+        false,
+    ) {
+        Some(info) => format!(", {}", info),
+        None => String::new(),
+    };
+    let with = if info.is_empty() { "" } else { "_with" };
+
+    match schema {
+        Schema::UnitStruct(_) => {
+            writeln!(
+                output,
+                "        describer.describe_unit_struct{}(Self::type_name(){})",
+                with, info,
+            )
+            .unwrap();
+        }
+
+        Schema::NewtypeStruct(schema) => {
+            writeln!(
+                output,
+                "        describer.describe_newtype_struct{}::<{}>(Self::type_name(){})",
+                with,
+                rust_type(&schema.inner)?,
+                info,
+            )
+            .unwrap();
+        }
+
+        Schema::TupleStruct(schema) => {
+            writeln!(output, "        use ::schematic::DescribeTupleStruct as _;").unwrap();
+            writeln!(
+                output,
+                "        let mut describer = describer.describe_tuple_struct{}(Self::type_name(){})?;",
+                with, info,
+            )
+            .unwrap();
+            for element in &schema.elements {
+                writeln!(
+                    output,
+                    "        describer.describe_element::<{}>()?;",
+                    rust_type(element)?,
+                )
+                .unwrap();
+            }
+            if schema.layout.is_some() {
+                let fields = (0..schema.elements.len()).map(|index| index.to_string());
+                write_layout(output, &ident, fields);
+            }
+            writeln!(output, "        describer.end()").unwrap();
+        }
+
+        Schema::Struct(schema) => {
+            writeln!(output, "        use ::schematic::DescribeStruct as _;").unwrap();
+            writeln!(
+                output,
+                "        let mut describer = describer.describe_struct{}(Self::type_name(){})?;",
+                with, info,
+            )
+            .unwrap();
+            for field in &schema.fields {
+                write_describe_field(output, "", field)?;
+            }
+            if schema.layout.is_some() {
+                let fields = schema.fields.iter().map(|field| field_ident(&field.name));
+                write_layout(output, &ident, fields);
+            }
+            writeln!(output, "        describer.end()").unwrap();
+        }
+
+        Schema::Enum(schema) => {
+            writeln!(output, "        use ::schematic::DescribeEnum as _;").unwrap();
+            let variants = &schema.variants;
+            if variants
+                .iter()
+                .any(|variant| matches!(variant, Variant::Struct { .. }))
+            {
+                writeln!(
+                    output,
+                    "        use ::schematic::DescribeStructVariant as _;"
+                )
+                .unwrap();
+            }
+            if variants
+                .iter()
+                .any(|variant| matches!(variant, Variant::Tuple { .. }))
+            {
+                writeln!(
+                    output,
+                    "        use ::schematic::DescribeTupleVariant as _;"
+                )
+                .unwrap();
+            }
+            writeln!(
+                output,
+                "        let mut describer = describer.describe_enum{}(Self::type_name(){})?;",
+                with, info,
+            )
+            .unwrap();
+            if let Some(repr) = schema.repr {
+                writeln!(
+                    output,
+                    "        describer.describe_repr(::schematic::Primitive::{:?})?;",
+                    repr,
+                )
+                .unwrap();
+            }
+            for variant in &schema.variants {
+                write_describe_variant(output, variant)?;
+            }
+            writeln!(output, "        describer.end()").unwrap();
+        }
+
+        _ => unreachable!("only named types have definitions"),
+    }
+
+    writeln!(output, "    }}").unwrap();
+    writeln!(output, "}}").unwrap();
+
+    Ok(())
+}
+
+fn write_describe_variant(output: &mut String, variant: &Variant) -> Result<(), Error> {
+    let name = variant.name();
+    let info = match info_expr(
+        "VariantInfo",
+        2,
+        variant.docs(),
+        Some(variant.attributes()),
+        None,
+        None,
+        false,
+    ) {
+        Some(info) => format!(", {}", info),
+        None => String::new(),
+    };
+    let with = if info.is_empty() { "" } else { "_with" };
+
+    match variant {
+        Variant::Unit { discriminant, .. } => {
+            let discriminant = match discriminant {
+                Some(discriminant) => {
+                    format!("Some(::schematic::PrimitiveValue::{:?})", discriminant,)
+                }
+                None => "None".into(),
+            };
+            writeln!(
+                output,
+                "        describer.describe_unit_variant{}({:?}, {}{})?;",
+                with, name, discriminant, info,
+            )
+            .unwrap();
+        }
+
+        Variant::Tuple { elements, .. } => {
+            writeln!(output, "        {{").unwrap();
+            writeln!(
+                output,
+                "            let mut variant = describer.start_tuple_variant{}({:?}{})?;",
+                with, name, info,
+            )
+            .unwrap();
+            for element in elements {
+                writeln!(
+                    output,
+                    "            variant.describe_element::<{}>()?;",
+                    rust_type(element)?,
+                )
+                .unwrap();
+            }
+            writeln!(output, "            describer.end_tuple_variant(variant)?;").unwrap();
+            writeln!(output, "        }}").unwrap();
+        }
+
+        Variant::Struct { fields, .. } => {
+            writeln!(output, "        {{").unwrap();
+            writeln!(
+                output,
+                "            let mut variant = describer.start_struct_variant{}({:?}{})?;",
+                with, name, info,
+            )
+            .unwrap();
+            for field in fields {
+                write_describe_field(output, "    ", field)?;
+            }
+            writeln!(
+                output,
+                "            describer.end_struct_variant(variant)?;"
+            )
+            .unwrap();
+            writeln!(output, "        }}").unwrap();
+        }
+    }
+
+    Ok(())
+}
+
+fn write_describe_field(
+    output: &mut String,
+    indent: &str,
+    field: &NamedField,
+) -> Result<(), Error> {
+    let receiver = if indent.is_empty() {
+        "describer"
+    } else {
+        "variant"
+    };
+    let info = info_expr(
+        "FieldInfo",
+        5,
+        field.docs.as_deref(),
+        Some(&field.attributes),
+        Some(&field.constraints),
+        field.default.as_ref(),
+        field.skip_serializing_if,
+    );
+    match info {
+        Some(info) => writeln!(
+            output,
+            "        {}{}.describe_field_with::<{}>({:?}, {})?;",
+            indent,
+            receiver,
+            rust_type(&field.schema)?,
+            field.name,
+            info,
+        ),
+        None => writeln!(
+            output,
+            "        {}{}.describe_field::<{}>({:?})?;",
+            indent,
+            receiver,
+            rust_type(&field.schema)?,
+            field.name,
+        ),
+    }
+    .unwrap();
+
+    Ok(())
+}
+
+fn write_layout(output: &mut String, ident: &str, fields: impl Iterator<Item = String>) {
+    let mut args = ident.to_string();
+    for field in fields {
+        args.push_str(", ");
+        args.push_str(&field);
+    }
+
+    writeln!(
+        output,
+        "        describer.describe_layout(::schematic::layout!({}))?;",
+        args,
+    )
+    .unwrap();
+}
+
+/// Returns the Rust expression that constructs the info struct `ty`, or `None` if
+/// there's no information to pass.
+///
+/// `len` is the number of fields in `ty`.
+fn info_expr(
+    ty: &str,
+    len: usize,
+    docs: Option<&str>,
+    attributes: Option<&Attributes>,
+    constraints: Option<&Constraints>,
+    default: Option<&FieldDefault>,
+    skip_serializing_if: bool,
+) -> Option<String> {
+    let mut fields = Vec::new();
+    if let Some(docs) = docs {
+        fields.push(format!("docs: Some({:?})", docs));
+    }
+    if let Some(attributes) = attributes.filter(|attributes| !attributes.is_empty()) {
+        fields.push(format!("attributes: {}", attributes_expr(attributes)));
+    }
+    if let Some(constraints) = constraints.filter(|constraints| !constraints.is_empty()) {
+        fields.push(format!("constraints: {}", constraints_expr(constraints)));
+    }
+    if let Some(default) = default {
+        fields.push(format!("default: Some({})", default_expr(default)));
+    }
+    if skip_serializing_if {
+        fields.push("skip_serializing_if: true".into());
+    }
+
+    if fields.is_empty() {
+        None
+    } else {
+        Some(struct_expr(ty, len, fields))
+    }
+}
+
+/// Returns the Rust expression that constructs `attributes`.
+fn attributes_expr(attributes: &Attributes) -> String {
+    let entries = attributes
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Attribute::Bool(value) => value.to_string(),
+                Attribute::Number(value) => number_expr(*value),
+                Attribute::String(value) => format!("{:?}", value),
+            };
+            format!("{:?} => {}", key, value)
+        })
+        .collect::<Vec<_>>();
+
+    format!("::schematic::attributes! {{ {} }}", entries.join(", "))
+}
+
+/// Returns the Rust expression that constructs `constraints`.
+fn constraints_expr(constraints: &Constraints) -> String {
+    let number = |number| format!("Some({})", number_expr(number));
+
+    let mut fields = Vec::new();
+    if let Some(min) = constraints.min {
+        fields.push(format!("min: {}", number(min)));
+    }
+    if let Some(max) = constraints.max {
+        fields.push(format!("max: {}", number(max)));
+    }
+    if let Some(min_len) = constraints.min_len {
+        fields.push(format!("min_len: Some({})", min_len));
+    }
+    if let Some(max_len) = constraints.max_len {
+        fields.push(format!("max_len: Some({})", max_len));
+    }
+    if let Some(pattern) = &constraints.pattern {
+        fields.push(format!(
+            "pattern: Some(::std::borrow::Cow::Borrowed({:?}))",
+            pattern,
+        ));
+    }
+
+    struct_expr("Constraints", 5, fields)
+}
+
+/// Returns the Rust expression that constructs `default`.
+fn default_expr(default: &FieldDefault) -> String {
+    match default {
+        FieldDefault::Unknown => "::schematic::FieldDefault::Unknown".into(),
+        FieldDefault::Value(value) => format!(
+            "::schematic::FieldDefault::Value(::schematic::__private::serde_json::json!({}))",
+            json_expr(value),
+        ),
+    }
+}
+
+/// Returns the `json!` input that constructs `value`.
+///
+/// Strings use Rust escapes rather than JSON ones, and numbers are suffixed so
+/// that large integers aren't inferred as `i32`.
+fn json_expr(value: &serde_json::Value) -> String {
+    use serde_json::Value;
+
+    match value {
+        Value::Null => "null".into(),
+        Value::Bool(value) => value.to_string(),
+        Value::Number(number) => match (number.as_i64(), number.as_u64(), number.as_f64()) {
+            (Some(value), _, _) => format!("{}i64", value),
+            (None, Some(value), _) => format!("{}u64", value),
+            (None, None, Some(value)) => format!("{:?}f64", value),
+            (None, None, None) => unreachable!("JSON numbers are integers or floats"),
+        },
+        Value::String(value) => format!("{:?}", value),
+        Value::Array(elements) => {
+            let elements = elements.iter().map(json_expr).collect::<Vec<_>>();
+            format!("[{}]", elements.join(", "))
+        }
+        Value::Object(entries) => {
+            let entries = entries
+                .iter()
+                .map(|(key, value)| format!("{:?}: {}", key, json_expr(value)))
+                .collect::<Vec<_>>();
+            format!("{{{}}}", entries.join(", "))
+        }
+    }
+}
+
+/// Returns the Rust expression that constructs `number`.
+fn number_expr(number: Number) -> String {
+    match number {
+        Number::Int(value) => format!("::schematic::Number::Int({})", value),
+        Number::Float(value) if value.is_nan() => "::schematic::Number::Float(f64::NAN)".into(),
+        Number::Float(value) if value.is_infinite() => format!(
+            "::schematic::Number::Float({}f64::INFINITY)",
+            if value < 0.0 { "-" } else { "" },
+        ),
+        Number::Float(value) => format!("::schematic::Number::Float({:?})", value),
+    }
+}
+
+/// Returns a struct expression for the Schematic type `ty`, filling in any of its
+/// `len` fields that aren't in `fields` with their defaults.
+fn struct_expr(ty: &str, len: usize, mut fields: Vec<String>) -> String {
+    if fields.len() < len {
+        fields.push("..::std::default::Default::default()".into());
+    }
+
+    format!("::schematic::{} {{ {} }}", ty, fields.join(", "))
+}
+
+/// Returns the Rust expression that constructs `name`, in a definition with the
+/// type parameters `params`.
+fn type_name_expr(name: &TypeName, params: &[&str]) -> String {
+    if is_type_param(name, params) {
+        format!("{}::type_name()", name.name)
+    } else if name.type_params.is_empty() {
+        format!(
+            "::schematic::TypeName::new({:?}, {:?})",
+            name.name, name.module
+        )
+    } else {
+        let params = name
+            .type_params
+            .iter()
+            .map(|param| type_name_expr(param, params))
+            .collect::<Vec<_>>();
+        format!(
+            "::schematic::TypeName::generic({:?}, {:?}, vec![{}])",
+            name.name,
+            name.module,
+            params.join(", "),
+        )
+    }
+}
+
+/// Returns the Rust type used for a field of type `schema`.
+fn rust_type(schema: &Schema) -> Result<String, Error> {
+    Ok(match schema {
+        Schema::Unit => "()".into(),
+        Schema::Bool => "bool".into(),
+        Schema::Char => "char".into(),
+
+        Schema::I8 => "i8".into(),
+        Schema::I16 => "i16".into(),
+        Schema::I32 => "i32".into(),
+        Schema::I64 => "i64".into(),
+        Schema::I128 => "i128".into(),
+        Schema::ISize => "isize".into(),
+
+        Schema::U8 => "u8".into(),
+        Schema::U16 => "u16".into(),
+        Schema::U32 => "u32".into(),
+        Schema::U64 => "u64".into(),
+        Schema::U128 => "u128".into(),
+        Schema::USize => "usize".into(),
+
+        Schema::F32 => "f32".into(),
+        Schema::F64 => "f64".into(),
+
+        Schema::String(_) => "::std::string::String".into(),
+
+        Schema::Option(inner) => format!("::std::option::Option<{}>", rust_type(inner)?),
+
+        Schema::Tuple(elements) => {
+            let elements = elements
+                .iter()
+                .map(rust_type)
+                .collect::<Result<Vec<_>, _>>()?;
+            if elements.len() == 1 {
+                format!("({},)", elements[0])
+            } else {
+                format!("({})", elements.join(", "))
+            }
+        }
+
+        Schema::Array(array) => format!("[{}; {}]", rust_type(&array.element)?, array.len),
+
+        Schema::Seq(seq) => {
+            let path = match &*seq.name.name {
+                "VecDeque" => "::std::collections::VecDeque",
+                "HashSet" => "::std::collections::HashSet",
+                "BTreeSet" => "::std::collections::BTreeSet",
+                "BinaryHeap" => "::std::collections::BinaryHeap",
+                "LinkedList" => "::std::collections::LinkedList",
+                _ => "::std::vec::Vec",
+            };
+            format!("{}<{}>", path, rust_type(&seq.element)?)
+        }
+
+        Schema::Map(map) => {
+            let path = match &*map.name.name {
+                "BTreeMap" => "::std::collections::BTreeMap",
+                _ => "::std::collections::HashMap",
+            };
+            format!(
+                "{}<{}, {}>",
+                path,
+                rust_type(&map.key)?,
+                rust_type(&map.value)?,
+            )
+        }
+
+        Schema::UnitStruct(_)
+        | Schema::Struct(_)
+        | Schema::TupleStruct(_)
+        | Schema::NewtypeStruct(_)
+        | Schema::Enum(_) => {
+            let (ident, params) = definition_ident(schema);
+            format!("{}{}", ident, generics(&params, ""))
+        }
+
+        Schema::TypeParam(name) => name.to_string(),
+
+        Schema::Str | Schema::Slice(_) => return Err(Error::unsupported(schema)),
+    })
+}
+
+/// Returns the identifier for the named type `schema`, along with its type
+/// parameters if it's a generic definition.
+///
+/// Only the arguments that aren't type parameters are appended to the identifier,
+/// so the definition of `Page<T>` is generated as `Page<T>` rather than `PageT`.
+fn definition_ident(schema: &Schema) -> (String, Vec<&str>) {
+    fn visit<'a>(schema: &'a Schema, used: &mut Vec<&'a str>) {
+        match schema {
+            Schema::TypeParam(name) => used.push(name),
+            schema => {
+                for child in schema.children() {
+                    visit(child, used);
+                }
+            }
+        }
+    }
+
+    let name = schema.type_name().unwrap();
+    let mut used = Vec::new();
+    visit(schema, &mut used);
+
+    let params = name
+        .type_params
+        .iter()
+        .filter(|param| is_type_param(param, &used))
+        .map(|param| &*param.name)
+        .collect::<Vec<_>>();
+
+    let mut ident = upper_camel_case(&name.name);
+    for param in &name.type_params {
+        if !is_type_param(param, &params) {
+            ident.push_str(&type_ident(param));
+        }
+    }
+
+    (ident, params)
+}
+
+/// Returns `true` if `name` is the placeholder for one of `params`.
+fn is_type_param(name: &TypeName, params: &[&str]) -> bool {
+    name.module.is_empty() && name.type_params.is_empty() && params.contains(&&*name.name)
+}
+
+/// Returns the generic parameter list for `params`, with each followed by `bound`.
+fn generics(params: &[&str], bound: &str) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+
+    let params = params
+        .iter()
+        .map(|param| format!("{}{}", param, bound))
+        .collect::<Vec<_>>();
+    format!("<{}>", params.join(", "))
+}
+
+/// Returns the identifier for a generated type.
+///
+/// Generic instantiations are generated as distinct types, so the type parameters
+/// are appended to the name, e.g. `Page<User>` becomes `PageUser`.
+fn type_ident(name: &TypeName) -> String {
+    let mut ident = upper_camel_case(&name.name);
+    for param in &name.type_params {
+        ident.push_str(&type_ident(param));
+    }
+
+    ident
+}
+
+fn variant_ident(name: &str) -> String {
+    upper_camel_case(name)
+}
+
+fn field_ident(name: &str) -> String {
+    let mut ident = String::new();
+    for (index, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if index > 0 && !ident.ends_with('_') {
+                ident.push('_');
+            }
+            ident.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() || c == '_' {
+            ident.push(c);
+        } else if !ident.ends_with('_') {
+            ident.push('_');
+        }
+    }
+
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+
+    if is_keyword(&ident) {
+        ident.insert_str(0, "r#");
+    }
+
+    ident
+}
+
+fn upper_camel_case(name: &str) -> String {
+    let mut ident = String::new();
+    let mut capitalize = true;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if capitalize {
+                ident.push(c.to_ascii_uppercase());
+            } else {
+                ident.push(c);
+            }
+            capitalize = false;
+        } else {
+            capitalize = true;
+        }
+    }
+
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+
+    ident
+}
+
+fn is_keyword(ident: &str) -> bool {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let",
+        "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+        "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+        "virtual", "where", "while", "yield",
+    ];
+
+    KEYWORDS.contains(&ident)
+}
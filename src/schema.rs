@@ -1,7 +1,12 @@
 use crate::TypeName;
+use alloc::{borrow::Cow, boxed::Box, collections::BTreeMap, string::String, vec, vec::Vec};
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    iter,
+};
 use derive_more::From;
 use serde::{Deserialize, Serialize};
-use std::{borrow::Cow, fmt, iter};
 
 /// In-memory representation of a type tree.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -46,6 +51,17 @@ pub enum Schema {
     NewtypeStruct(Box<NewtypeStruct>),
 
     Enum(Enum),
+
+    /// A placeholder for a type parameter in the definition of a generic type,
+    /// such as the `T` in `struct Page<T>`.
+    ///
+    /// Describing a generic type with placeholders declared by [`type_param!`] in
+    /// place of its arguments gives its definition rather than one of its
+    /// instantiations. See [`Schema::is_generic_definition`] for more.
+    ///
+    /// [`type_param!`]: macro.type_param.html
+    /// [`Schema::is_generic_definition`]: #method.is_generic_definition
+    TypeParam(Cow<'static, str>),
 }
 
 impl Schema {
@@ -61,7 +77,10 @@ impl Schema {
     ///
     /// let schema = Schema::Struct(Struct {
     ///     name: TypeName::new("MyStruct", "my_crate::my_module"),
+    ///     docs: None,
+    ///     attributes: Default::default(),
     ///     fields: vec![],
+    ///     layout: None,
     /// });
     ///
     /// let type_name = schema.type_name().unwrap();
@@ -80,6 +99,121 @@ impl Schema {
         })
     }
 
+    /// Returns the name that `Describe::type_name` reports for the described type.
+    ///
+    /// Unlike `type_name`, this also covers primitives and other built-in types,
+    /// which is needed when building the name of a collection from its element type.
+    #[cfg(feature = "std")]
+    pub(crate) fn full_type_name(&self) -> TypeName {
+        let primitive = |name: &'static str| TypeName::new(name, "");
+
+        match self {
+            Schema::Unit => primitive("()"),
+            Schema::Bool => primitive("bool"),
+            Schema::Char => primitive("char"),
+
+            Schema::I8 => primitive("i8"),
+            Schema::I16 => primitive("i16"),
+            Schema::I32 => primitive("i32"),
+            Schema::I64 => primitive("i64"),
+            Schema::I128 => primitive("i128"),
+            Schema::ISize => primitive("isize"),
+
+            Schema::U8 => primitive("u8"),
+            Schema::U16 => primitive("u16"),
+            Schema::U32 => primitive("u32"),
+            Schema::U64 => primitive("u64"),
+            Schema::U128 => primitive("u128"),
+            Schema::USize => primitive("usize"),
+
+            Schema::F32 => primitive("f32"),
+            Schema::F64 => primitive("f64"),
+
+            Schema::Str => primitive("str"),
+            Schema::String(name) => name.clone(),
+
+            Schema::Option(inner) => {
+                TypeName::generic("Option", "core::option", vec![inner.full_type_name()])
+            }
+            Schema::Tuple(elements) => TypeName::generic(
+                "()",
+                "",
+                elements
+                    .iter()
+                    .map(Schema::full_type_name)
+                    .collect::<Vec<_>>(),
+            ),
+            Schema::Array(array) => TypeName::generic(
+                alloc::format!("[; {}]", array.len),
+                "",
+                vec![array.element.full_type_name()],
+            ),
+            Schema::Slice(element) => TypeName::generic("[]", "", vec![element.full_type_name()]),
+            Schema::Seq(seq) => seq.name.clone(),
+            Schema::Map(map) => map.name.clone(),
+            Schema::TypeParam(name) => TypeName::new(name.clone(), ""),
+
+            Schema::UnitStruct(_)
+            | Schema::Struct(_)
+            | Schema::TupleStruct(_)
+            | Schema::NewtypeStruct(_)
+            | Schema::Enum(_) => self.type_name().unwrap().clone(),
+        }
+    }
+
+    /// Returns the narrowest integer type that can hold every value from `min` to `max`.
+    ///
+    /// Non-negative ranges use unsigned types. Unbounded ranges default to `i64`, or
+    /// `u64` if the range is only bounded below by a non-negative number, since
+    /// those are the widest types that JSON parsers typically support.
+    #[cfg(feature = "std")]
+    pub(crate) fn narrowest_integer(min: Option<i128>, max: Option<i128>) -> Schema {
+        let unsigned = [
+            (Schema::U8, u8::MAX as i128),
+            (Schema::U16, u16::MAX as i128),
+            (Schema::U32, u32::MAX as i128),
+            (Schema::U64, u64::MAX as i128),
+        ];
+        let signed = [
+            (Schema::I8, i8::MIN as i128, i8::MAX as i128),
+            (Schema::I16, i16::MIN as i128, i16::MAX as i128),
+            (Schema::I32, i32::MIN as i128, i32::MAX as i128),
+            (Schema::I64, i64::MIN as i128, i64::MAX as i128),
+        ];
+
+        match (min, max) {
+            (Some(min), Some(max)) if min >= 0 => unsigned
+                .iter()
+                .find(|(_, type_max)| max <= *type_max)
+                .map(|(schema, _)| schema.clone())
+                .unwrap_or(Schema::U128),
+            (Some(min), None) if min >= 0 => Schema::U64,
+
+            (Some(min), Some(max)) => signed
+                .iter()
+                .find(|(_, type_min, type_max)| min >= *type_min && max <= *type_max)
+                .map(|(schema, _, _)| schema.clone())
+                .unwrap_or(Schema::I128),
+            _ => Schema::I64,
+        }
+    }
+
+    /// Returns the documentation for user-defined types.
+    ///
+    /// Returns `None` for types that aren't user-defined, or for user-defined types
+    /// that don't have any documentation.
+    pub fn docs(&self) -> Option<&str> {
+        match self {
+            Schema::Struct(schema) => schema.docs.as_deref(),
+            Schema::UnitStruct(schema) => schema.docs.as_deref(),
+            Schema::NewtypeStruct(schema) => schema.docs.as_deref(),
+            Schema::TupleStruct(schema) => schema.docs.as_deref(),
+            Schema::Enum(schema) => schema.docs.as_deref(),
+
+            _ => None,
+        }
+    }
+
     pub fn as_struct(&self) -> Option<&Struct> {
         match self {
             Schema::Struct(schema) => Some(schema),
@@ -125,6 +259,104 @@ impl Schema {
             _ => None,
         }
     }
+
+    /// Returns the schemas directly nested within this schema.
+    ///
+    /// For compound types this is the schema of each element, field, key, or value,
+    /// in declaration order. Primitives and other leaf types have no children.
+    pub fn children(&self) -> Vec<&Schema> {
+        match self {
+            Schema::Option(inner) | Schema::Slice(inner) => vec![&**inner],
+            Schema::Tuple(elements) => elements.iter().collect(),
+            Schema::Array(array) => vec![&array.element],
+            Schema::Seq(seq) => vec![&seq.element],
+            Schema::Map(map) => vec![&map.key, &map.value],
+            Schema::Struct(schema) => schema.fields().map(|field| field.schema).collect(),
+            Schema::TupleStruct(schema) => schema.elements.iter().collect(),
+            Schema::NewtypeStruct(schema) => vec![&schema.inner],
+            Schema::Enum(schema) => schema
+                .variants
+                .iter()
+                .flat_map(Variant::fields)
+                .map(|field| field.schema)
+                .collect(),
+
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns `true` if this is the definition of a generic type, i.e. a
+    /// user-defined type that refers to [`Schema::TypeParam`] placeholders.
+    ///
+    /// The parameters of the definition are the `type_params` of its [`TypeName`],
+    /// each named after its placeholder, so generators can emit the type once as a
+    /// generic definition like `Page<T>` rather than once per instantiation.
+    ///
+    /// [`Schema::TypeParam`]: #variant.TypeParam
+    /// [`TypeName`]: struct.TypeName.html
+    pub fn is_generic_definition(&self) -> bool {
+        fn has_type_param(schema: &Schema) -> bool {
+            match schema {
+                Schema::TypeParam(_) => true,
+                schema => schema.children().into_iter().any(has_type_param),
+            }
+        }
+
+        self.type_name().is_some() && has_type_param(self)
+    }
+
+    /// Returns every user-defined type reachable from this schema.
+    ///
+    /// Each type is yielded once, identified by its [`TypeName`], and always after
+    /// any types it depends on, which makes the result suitable for emitting
+    /// declarations in languages that require types to be declared before use. If
+    /// this schema is itself a user-defined type it is the last element.
+    ///
+    /// [`TypeName`]: struct.TypeName.html
+    pub fn named_types(&self) -> Vec<&Schema> {
+        fn visit<'a>(schema: &'a Schema, types: &mut Vec<&'a Schema>) {
+            if let Some(name) = schema.type_name() {
+                if types.iter().any(|ty| ty.type_name() == Some(name)) {
+                    return;
+                }
+            }
+
+            for child in schema.children() {
+                visit(child, types);
+            }
+
+            if schema.type_name().is_some() {
+                types.push(schema);
+            }
+        }
+
+        let mut types = Vec::new();
+        visit(self, &mut types);
+        types
+    }
+}
+
+/// In-memory layout of a type with a stable representation.
+///
+/// Layout information is only meaningful for types with a defined representation,
+/// i.e. `#[repr(C)]` structs and enums with a `#[repr(C)]` or primitive
+/// representation. For all other types the compiler is free to reorder and pad
+/// fields, so the layout of one build says nothing about the next. Use the
+/// [`layout!`] macro to capture the layout of a type.
+///
+/// [`layout!`]: macro.layout.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Layout {
+    /// The size of the type in bytes, as reported by `core::mem::size_of`.
+    pub size: usize,
+
+    /// The alignment of the type in bytes, as reported by `core::mem::align_of`.
+    pub align: usize,
+
+    /// The byte offset of each field from the start of the type, in declaration order.
+    ///
+    /// Empty for enums, since the offsets of variant fields cannot be queried.
+    pub field_offsets: Vec<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -150,12 +382,18 @@ pub struct Map {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct UnitStruct {
     pub name: TypeName,
+    pub docs: Option<Cow<'static, str>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct NewtypeStruct {
     pub name: TypeName,
+    pub docs: Option<Cow<'static, str>>,
     pub inner: Schema,
+
+    /// Restrictions on the values of the inner type.
+    #[serde(default, skip_serializing_if = "Constraints::is_empty")]
+    pub constraints: Constraints,
 }
 
 impl NewtypeStruct {
@@ -167,21 +405,34 @@ impl NewtypeStruct {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Struct {
     pub name: TypeName,
-    pub fields: Vec<(Cow<'static, str>, Schema)>,
+    pub docs: Option<Cow<'static, str>>,
+
+    /// Free-form metadata, such as hints for editors.
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    pub attributes: Attributes,
+
+    pub fields: Vec<NamedField>,
+
+    /// The in-memory layout of the struct, if it has a stable representation.
+    pub layout: Option<Layout>,
 }
 
 impl Struct {
     pub fn fields(&self) -> impl Iterator<Item = Field<'_>> {
         self.fields
             .iter()
-            .map(|(name, schema)| Field::named(name, schema))
+            .map(|field| Field::named(&field.name, &field.schema))
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TupleStruct {
     pub name: TypeName,
+    pub docs: Option<Cow<'static, str>>,
     pub elements: Vec<Schema>,
+
+    /// The in-memory layout of the struct, if it has a stable representation.
+    pub layout: Option<Layout>,
 }
 
 impl TupleStruct {
@@ -251,6 +502,7 @@ impl<'a> From<&'a UnitStruct> for StructLike<'a> {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Enum {
     pub name: TypeName,
+    pub docs: Option<Cow<'static, str>>,
 
     /// The explicit representation of the enum, as specified by the `#[repr(...)]`
     /// attribute.
@@ -258,6 +510,9 @@ pub struct Enum {
     /// `None` if the
     pub repr: Option<Primitive>,
     pub variants: Vec<Variant>,
+
+    /// The in-memory layout of the enum, if it has a stable representation.
+    pub layout: Option<Layout>,
 }
 
 impl Enum {
@@ -292,16 +547,31 @@ impl Enum {
 pub enum Variant {
     Unit {
         name: Cow<'static, str>,
+        docs: Option<Cow<'static, str>>,
+
+        #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+        attributes: Attributes,
+
         discriminant: Option<PrimitiveValue>,
     },
 
     Struct {
         name: Cow<'static, str>,
-        fields: Vec<(Cow<'static, str>, Schema)>,
+        docs: Option<Cow<'static, str>>,
+
+        #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+        attributes: Attributes,
+
+        fields: Vec<NamedField>,
     },
 
     Tuple {
         name: Cow<'static, str>,
+        docs: Option<Cow<'static, str>>,
+
+        #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+        attributes: Attributes,
+
         elements: Vec<Schema>,
     },
 }
@@ -316,6 +586,8 @@ impl Variant {
     ///
     /// let variant = Variant::Unit {
     ///     name: "Foo".into(),
+    ///     docs: None,
+    ///     attributes: Default::default(),
     ///     discriminant: None,
     /// };
     ///
@@ -329,6 +601,24 @@ impl Variant {
         }
     }
 
+    /// Returns the documentation for the variant, if any.
+    pub fn docs(&self) -> Option<&str> {
+        match self {
+            Variant::Unit { docs, .. } => docs.as_deref(),
+            Variant::Struct { docs, .. } => docs.as_deref(),
+            Variant::Tuple { docs, .. } => docs.as_deref(),
+        }
+    }
+
+    /// Returns the attributes of the variant.
+    pub fn attributes(&self) -> &Attributes {
+        match self {
+            Variant::Unit { attributes, .. } => attributes,
+            Variant::Struct { attributes, .. } => attributes,
+            Variant::Tuple { attributes, .. } => attributes,
+        }
+    }
+
     /// Returns `true` for unit-like variants and struct/tuple-like variants with no fields.
     ///
     /// # Examples
@@ -371,10 +661,11 @@ impl Variant {
         match self {
             Variant::Unit { .. } => Box::new(iter::empty()),
 
-            Variant::Struct { fields, .. } => Box::new(fields.iter().map(|(name, schema)| Field {
-                name: Some(name),
-                schema,
-            })),
+            Variant::Struct { fields, .. } => Box::new(
+                fields
+                    .iter()
+                    .map(|field| Field::named(&field.name, &field.schema)),
+            ),
 
             Variant::Tuple { elements, .. } => {
                 Box::new(elements.iter().map(|schema| Field { name: None, schema }))
@@ -383,6 +674,316 @@ impl Variant {
     }
 }
 
+/// A named field in a struct or struct-like enum variant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NamedField {
+    pub name: Cow<'static, str>,
+    pub docs: Option<Cow<'static, str>>,
+
+    /// Free-form metadata, such as hints for editors.
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    pub attributes: Attributes,
+
+    pub schema: Schema,
+
+    /// Restrictions on the values of the field.
+    #[serde(default, skip_serializing_if = "Constraints::is_empty")]
+    pub constraints: Constraints,
+
+    /// The value used when the field is missing, if the field is optional.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<FieldDefault>,
+
+    /// Whether the field may be left out when serializing, as with
+    /// `#[serde(skip_serializing_if = "...")]`.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub skip_serializing_if: bool,
+}
+
+impl NamedField {
+    /// Creates a new required field without any documentation or constraints.
+    pub fn new<N>(name: N, schema: Schema) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        Self {
+            name: name.into(),
+            docs: None,
+            attributes: Attributes::new(),
+            schema,
+            constraints: Constraints::default(),
+            default: None,
+            skip_serializing_if: false,
+        }
+    }
+
+    /// Returns `true` if the field must be present when deserializing, i.e. it
+    /// has no default.
+    ///
+    /// This doesn't depend on the type of the field: an `Option` field without a
+    /// default is required, even though Serde's derive fills in `None` for missing
+    /// `Option` fields in self-describing formats, and a field of any type with a
+    /// default is optional.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use schematic::{FieldDefault, NamedField, Schema};
+    ///
+    /// let port = NamedField {
+    ///     default: Some(FieldDefault::of(&8080u16)),
+    ///     ..NamedField::new("port", Schema::U16)
+    /// };
+    /// assert!(!port.is_required());
+    /// assert!(NamedField::new("host", Schema::Option(Box::new(Schema::Str))).is_required());
+    /// ```
+    pub fn is_required(&self) -> bool {
+        self.default.is_none()
+    }
+
+    /// Returns `true` if the field is present in every serialized value.
+    pub fn is_always_serialized(&self) -> bool {
+        !self.skip_serializing_if
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Restrictions on the values of a field or newtype, beyond those of its type.
+///
+/// Constraints don't affect the shape of the data, but let tools such as editors
+/// offer sliders for bounded numbers or reject empty names, and are enforced by
+/// [`validate`]. Each constraint only applies to the kinds of values it makes sense
+/// for:
+///
+/// * `min` and `max` apply to integers and floats.
+/// * `min_len` and `max_len` apply to strings, counted in characters, and to
+///   arrays, sequences and maps, counted in elements.
+/// * `pattern` applies to strings.
+///
+/// Constraints on an `Option` apply to the inner value when there is one, so that
+/// an optional name can still be required to be non-empty.
+///
+/// # Examples
+///
+/// ```
+/// use schematic::{Constraints, Number};
+///
+/// let volume = Constraints {
+///     min: Some(Number::Float(0.0)),
+///     max: Some(Number::Float(1.0)),
+///     ..Default::default()
+/// };
+/// assert!(!volume.is_empty());
+/// ```
+///
+/// [`validate`]: validate/index.html
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Constraints {
+    /// The smallest allowed value, inclusive.
+    pub min: Option<Number>,
+
+    /// The largest allowed value, inclusive.
+    pub max: Option<Number>,
+
+    /// The smallest allowed length, inclusive.
+    pub min_len: Option<usize>,
+
+    /// The largest allowed length, inclusive.
+    pub max_len: Option<usize>,
+
+    /// A regular expression that strings must contain a match for.
+    ///
+    /// As in JSON Schema, the expression isn't implicitly anchored, so use `^` and
+    /// `$` to match the whole string.
+    pub pattern: Option<Cow<'static, str>>,
+}
+
+impl Constraints {
+    /// Returns `true` if no constraints are set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The default of a field that may be left out when deserializing, as with
+/// `#[serde(default)]` or `#[serde(default = "path")]`.
+///
+/// When the default can be serialized, it's stored as JSON so that tools can show
+/// or fill in the real value. [`FieldDefault::of`] and [`FieldDefault::of_default`]
+/// build the value from a Rust value:
+///
+/// ```
+/// use schematic::FieldDefault;
+///
+/// assert_eq!(FieldDefault::Value(8080.into()), FieldDefault::of(&8080u16));
+/// assert_eq!(FieldDefault::Value(false.into()), FieldDefault::of_default::<bool>());
+/// ```
+///
+/// [`FieldDefault::of`]: #method.of
+/// [`FieldDefault::of_default`]: #method.of_default
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FieldDefault {
+    /// The field has a default, but its value isn't known, e.g. because it's
+    /// computed at runtime or can't be represented as JSON.
+    Unknown,
+
+    /// The default value, serialized as JSON.
+    Value(serde_json::Value),
+}
+
+impl FieldDefault {
+    /// Serializes `value` as the default, or returns `Unknown` if it can't be
+    /// represented as JSON, such as a map with non-string keys.
+    pub fn of<T: Serialize + ?Sized>(value: &T) -> Self {
+        serde_json::to_value(value).map_or(FieldDefault::Unknown, FieldDefault::Value)
+    }
+
+    /// Serializes the [`Default`] value of `T`, matching a bare `#[serde(default)]`.
+    ///
+    /// [`Default`]: https://doc.rust-lang.org/std/default/trait.Default.html
+    pub fn of_default<T: Serialize + Default>() -> Self {
+        Self::of(&T::default())
+    }
+
+    /// Returns the default value, if it's known.
+    pub fn value(&self) -> Option<&serde_json::Value> {
+        match self {
+            FieldDefault::Unknown => None,
+            FieldDefault::Value(value) => Some(value),
+        }
+    }
+}
+
+/// Free-form metadata attached to structs, variants and fields.
+///
+/// Attributes don't affect the shape of the data or how it's validated. They
+/// carry information for specific tools, such as the display name, tooltip, or
+/// widget that an editor should use for a field. Schematic doesn't interpret any
+/// keys, so tools should pick names that won't clash, e.g. by prefixing them with
+/// the name of the tool. Use the [`attributes!`] macro to build a map:
+///
+/// ```
+/// use schematic::{Attribute, FieldInfo};
+///
+/// let info = FieldInfo {
+///     attributes: schematic::attributes! {
+///         "editor.widget" => "slider",
+///         "editor.group" => "Audio",
+///         "editor.order" => 2,
+///     },
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(Some(&Attribute::from("slider")), info.attributes.get("editor.widget"));
+/// ```
+///
+/// [`attributes!`]: macro.attributes.html
+pub type Attributes = BTreeMap<Cow<'static, str>, Attribute>;
+
+/// The value of an entry in [`Attributes`].
+///
+/// [`Attributes`]: type.Attributes.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash, From, Serialize, Deserialize)]
+pub enum Attribute {
+    Bool(bool),
+    Number(Number),
+    String(Cow<'static, str>),
+}
+
+macro_rules! attribute_from {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for Attribute {
+                fn from(value: $ty) -> Self {
+                    Attribute::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+attribute_from! {
+    &'static str => String,
+    String => String,
+    i32 => Number,
+    i64 => Number,
+    u32 => Number,
+    u64 => Number,
+    f32 => Number,
+    f64 => Number,
+}
+
+/// A numeric bound in [`Constraints`].
+///
+/// Floats are compared by their bits, so that bounds can be compared and hashed
+/// along with the rest of the schema.
+///
+/// [`Constraints`]: struct.Constraints.html
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Number {
+    Int(i128),
+    Float(f64),
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Number::Int(left), Number::Int(right)) => left == right,
+            (Number::Float(left), Number::Float(right)) => left.to_bits() == right.to_bits(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Number {}
+
+macro_rules! number_from {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for Number {
+                fn from(value: $ty) -> Self {
+                    Number::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+number_from! {
+    i8 => Int,
+    i16 => Int,
+    i32 => Int,
+    i64 => Int,
+    i128 => Int,
+    u8 => Int,
+    u16 => Int,
+    u32 => Int,
+    u64 => Int,
+    f32 => Float,
+    f64 => Float,
+}
+
+impl Hash for Number {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Number::Int(value) => (0u8, value).hash(state),
+            Number::Float(value) => (1u8, value.to_bits()).hash(state),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Int(value) => write!(f, "{}", value),
+            Number::Float(value) => write!(f, "{:?}", value),
+        }
+    }
+}
+
 /// Generic representation of a field in a struct, enum variant, or tuple.
 ///
 /// It's often desirable to be able to operate over the fields of different kinds of
@@ -484,10 +1085,157 @@ impl fmt::Display for PrimitiveValue {
 ///   you should invoke it as `type_name!(Baz)`. This restriction may be lifted in
 ///   the future.
 ///
+/// Generic types take their type parameters from the `Describe` impls of the
+/// arguments, so `type_name!(Page<T>)` can be used in the `Describe` impl of a
+/// generic type:
+///
+/// ```
+/// use schematic::{Describe, TypeName};
+///
+/// pub struct Page<T>(Vec<T>);
+///
+/// let name = schematic::type_name!(Page<u8>);
+/// assert_eq!("Page", name.name);
+/// assert_eq!(vec![u8::type_name()], name.type_params);
+/// ```
+///
 /// [`TypeName`]: struct.TypeName.html
 #[macro_export]
 macro_rules! type_name {
+    ($ty:ident < $($param:ty),+ $(,)? >) => {
+        $crate::TypeName::generic(
+            stringify!($ty),
+            module_path!(),
+            $crate::__private::vec![$(<$param as $crate::Describe>::type_name()),+],
+        )
+    };
     ($ty:ty) => {
         $crate::TypeName::new(stringify!($ty), module_path!())
     };
 }
+
+/// Declares placeholder types for the type parameters of generic types.
+///
+/// Each placeholder is an uninhabited type that describes itself as
+/// [`Schema::TypeParam`], so describing a generic type with placeholders as its
+/// arguments gives the definition of the type rather than an instantiation:
+///
+/// ```
+/// use schematic::{Describe, Describer, DescribeStruct, Schema, TypeName};
+///
+/// pub struct Page<T> {
+///     pub items: Vec<T>,
+/// }
+///
+/// impl<T: Describe> Describe for Page<T> {
+///     fn type_name() -> TypeName {
+///         TypeName::generic("Page", module_path!(), vec![T::type_name()])
+///     }
+///
+///     fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+///         let mut describer = describer.describe_struct(Self::type_name())?;
+///         describer.describe_field::<Vec<T>>("items")?;
+///         describer.end()
+///     }
+/// }
+///
+/// schematic::type_param!(T);
+///
+/// let schema = schematic::describe::<Page<T>>();
+/// assert!(schema.is_generic_definition());
+/// assert_eq!(
+///     "Page<T>",
+///     schema.type_name().unwrap().to_string(),
+/// );
+/// ```
+///
+/// [`Schema::TypeParam`]: enum.Schema.html#variant.TypeParam
+#[macro_export]
+macro_rules! type_param {
+    ($($name:ident),+ $(,)?) => {
+        $(
+            #[allow(dead_code)]
+            pub enum $name {}
+
+            impl $crate::Describe for $name {
+                fn type_name() -> $crate::TypeName {
+                    $crate::TypeName::new(stringify!($name), "")
+                }
+
+                fn describe<D: $crate::Describer>(
+                    describer: D,
+                ) -> ::core::result::Result<D::Ok, D::Error> {
+                    describer.describe_type_param(stringify!($name))
+                }
+            }
+        )+
+    };
+}
+
+/// Expands to the [`Layout`] of the specified type.
+///
+/// The first argument is the type, followed by the name of each of its fields in
+/// declaration order. Tuple struct fields are named by index:
+///
+/// ```
+/// #[repr(C)]
+/// pub struct Point {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[repr(C)]
+/// pub struct Pair(u8, u32);
+///
+/// let layout = schematic::layout!(Point, x, y);
+/// assert_eq!(vec![0, 4], layout.field_offsets);
+///
+/// let layout = schematic::layout!(Pair, 0, 1);
+/// assert_eq!(8, layout.size);
+/// ```
+///
+/// Only capture the layout of types with a stable representation, see [`Layout`]
+/// for more.
+///
+/// [`Layout`]: struct.Layout.html
+#[macro_export]
+macro_rules! layout {
+    ($ty:ty $(, $field:tt)* $(,)?) => {
+        $crate::Layout {
+            size: ::core::mem::size_of::<$ty>(),
+            align: ::core::mem::align_of::<$ty>(),
+            field_offsets: $crate::__private::vec![$( ::core::mem::offset_of!($ty, $field), )*],
+        }
+    };
+}
+
+/// Expands to an [`Attributes`] map with the given entries.
+///
+/// Keys are strings, and values are anything that converts into an [`Attribute`],
+/// i.e. booleans, numbers and strings:
+///
+/// ```
+/// use schematic::Attribute;
+///
+/// let attributes = schematic::attributes! {
+///     "editor.label" => "Master volume",
+///     "editor.hidden" => false,
+///     "editor.step" => 0.05,
+/// };
+///
+/// assert_eq!(Some(&Attribute::Bool(false)), attributes.get("editor.hidden"));
+/// ```
+///
+/// [`Attributes`]: type.Attributes.html
+/// [`Attribute`]: enum.Attribute.html
+#[macro_export]
+macro_rules! attributes {
+    ($($key:expr => $value:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut attributes = $crate::Attributes::new();
+        $(
+            attributes.insert($key.into(), $crate::Attribute::from($value));
+        )*
+        attributes
+    }};
+}
@@ -1,12 +1,117 @@
 use crate::{describe::*, schema::*, TypeName};
-use never_type::Never;
-use std::borrow::Cow;
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+/// Rejects schemas that a particular target can't represent.
+type Check = fn(&Schema) -> Result<(), String>;
+
+/// Describes types into a [`Schema`].
+///
+/// This is the describer used by [`describe`] and [`try_describe`]. Describers for
+/// targets that can't represent every schema can be created with
+/// [`SchemaDescriber::with_check`].
+///
+/// [`Schema`]: enum.Schema.html
+/// [`describe`]: fn.describe.html
+/// [`try_describe`]: fn.try_describe.html
+/// [`SchemaDescriber::with_check`]: #method.with_check
+#[derive(Debug, Clone, Default)]
+pub struct SchemaDescriber {
+    check: Option<Check>,
+}
+
+impl SchemaDescriber {
+    /// Creates a describer that accepts every type.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a describer that runs `check` on the schema of every type it
+    /// describes, including the types nested within it.
+    ///
+    /// If `check` returns an error message, describing fails with a [`SchemaError`]
+    /// for the path to the rejected type. For example, a describer for a JSON-only
+    /// target can reject maps with keys that aren't strings:
+    ///
+    /// ```
+    /// use schematic::{Describe, Schema, SchemaDescriber};
+    /// use std::collections::BTreeMap;
+    ///
+    /// fn json_keys(schema: &Schema) -> Result<(), String> {
+    ///     match schema {
+    ///         Schema::Map(map) if !matches!(map.key, Schema::Str | Schema::String(_)) => {
+    ///             Err("map keys must be strings".into())
+    ///         }
+    ///         _ => Ok(()),
+    ///     }
+    /// }
+    ///
+    /// let mut describer = SchemaDescriber::with_check(json_keys);
+    /// let error = Vec::<BTreeMap<u32, bool>>::describe(&mut describer).unwrap_err();
+    /// assert_eq!("map keys must be strings at `/*`", error.to_string());
+    /// ```
+    ///
+    /// [`SchemaError`]: struct.SchemaError.html
+    pub fn with_check(check: fn(&Schema) -> Result<(), String>) -> Self {
+        SchemaDescriber { check: Some(check) }
+    }
+
+    fn accept(&self, schema: Schema) -> Result<Schema, SchemaError> {
+        accept(self.check, schema)
+    }
+}
+
+/// An error returned when a type can't be described.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    /// The path to the type that couldn't be described, e.g. `/servers/*/port`.
+    ///
+    /// Fields and variants are identified by name, tuple elements by index, the
+    /// elements of sequences by `*`, and the keys and values of maps by `<key>` and
+    /// `<value>`. Empty if the described type itself was rejected.
+    pub path: String,
+
+    /// Why the type couldn't be described.
+    pub message: String,
+}
+
+impl SchemaError {
+    /// Prefixes the path of the error with `segment`.
+    fn within(mut self, segment: &str) -> Self {
+        self.path = alloc::format!("/{}{}", segment, self.path);
+        self
+    }
+}
+
+impl DescribeError for SchemaError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        SchemaError {
+            path: String::new(),
+            message: message.to_string(),
+        }
+    }
+}
 
-pub struct SchemaDescriber;
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{} at `{}`", self.message, self.path)
+        }
+    }
+}
 
-impl<'a> Describer for &'a mut SchemaDescriber {
+#[cfg(feature = "std")]
+impl std::error::Error for SchemaError {}
+
+impl Describer for &mut SchemaDescriber {
     type Ok = Schema;
-    type Error = Never;
+    type Error = SchemaError;
 
     type DescribeStruct = StructDescriber;
     type DescribeTupleStruct = TupleStructDescriber;
@@ -14,122 +119,176 @@ impl<'a> Describer for &'a mut SchemaDescriber {
     type DescribeTuple = TupleDescriber;
 
     fn describe_bool(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::Bool)
+        self.accept(Schema::Bool)
     }
 
     fn describe_i8(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::I8)
+        self.accept(Schema::I8)
     }
 
     fn describe_i16(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::I16)
+        self.accept(Schema::I16)
     }
 
     fn describe_i32(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::I32)
+        self.accept(Schema::I32)
     }
 
     fn describe_i64(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::I64)
+        self.accept(Schema::I64)
     }
 
     fn describe_i128(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::I128)
+        self.accept(Schema::I128)
     }
 
     fn describe_isize(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::ISize)
+        self.accept(Schema::ISize)
     }
 
     fn describe_u8(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::U8)
+        self.accept(Schema::U8)
     }
 
     fn describe_u16(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::U16)
+        self.accept(Schema::U16)
     }
 
     fn describe_u32(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::U32)
+        self.accept(Schema::U32)
     }
 
     fn describe_u64(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::U64)
+        self.accept(Schema::U64)
     }
 
     fn describe_u128(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::U128)
+        self.accept(Schema::U128)
     }
 
     fn describe_usize(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::USize)
+        self.accept(Schema::USize)
     }
 
     fn describe_f32(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::F32)
+        self.accept(Schema::F32)
     }
 
     fn describe_f64(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::F64)
+        self.accept(Schema::F64)
     }
 
     fn describe_char(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::Char)
+        self.accept(Schema::Char)
     }
 
     fn describe_str(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::Str)
+        self.accept(Schema::Str)
     }
 
     fn describe_string(self, name: TypeName) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::String(name))
+        self.accept(Schema::String(name))
     }
 
     fn describe_unit(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::Unit)
+        self.accept(Schema::Unit)
     }
 
     fn describe_option<T>(self) -> Result<Self::Ok, Self::Error>
     where
         T: Describe,
     {
-        let inner = T::describe(self)?;
-        Ok(Schema::Option(Box::new(inner)))
+        let inner = T::describe(&mut *self)?;
+        self.accept(Schema::Option(Box::new(inner)))
     }
 
     fn describe_unit_struct(self, name: TypeName) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::UnitStruct(UnitStruct { name }))
+        self.describe_unit_struct_with(name, Default::default())
+    }
+
+    fn describe_unit_struct_with(
+        self,
+        name: TypeName,
+        info: TypeInfo,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.accept(Schema::UnitStruct(UnitStruct {
+            name,
+            docs: info.docs.map(Into::into),
+        }))
     }
 
     fn describe_newtype_struct<T>(self, name: TypeName) -> Result<Self::Ok, Self::Error>
     where
         T: Describe,
     {
-        let inner = T::describe(self)?;
-        Ok(Schema::NewtypeStruct(Box::new(NewtypeStruct {
-            name: name.into(),
+        self.describe_newtype_struct_with::<T>(name, Default::default())
+    }
+
+    fn describe_newtype_struct_with<T>(
+        self,
+        name: TypeName,
+        info: TypeInfo,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Describe,
+    {
+        let inner = T::describe(&mut *self)?;
+        self.accept(Schema::NewtypeStruct(Box::new(NewtypeStruct {
+            name,
+            docs: info.docs.map(Into::into),
             inner,
+            constraints: info.constraints,
         })))
     }
 
+    fn describe_type_param(self, name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.accept(Schema::TypeParam(name.into()))
+    }
+
     fn describe_enum(self, type_name: TypeName) -> Result<Self::DescribeEnum, Self::Error> {
+        self.describe_enum_with(type_name, Default::default())
+    }
+
+    fn describe_enum_with(
+        self,
+        type_name: TypeName,
+        info: TypeInfo,
+    ) -> Result<Self::DescribeEnum, Self::Error> {
         Ok(EnumDescriber {
+            check: self.check,
             type_name,
+            docs: info.docs,
+            repr: None,
             variants: Vec::new(),
+            layout: None,
         })
     }
 
     fn describe_tuple(self) -> Result<Self::DescribeTuple, Self::Error> {
-        Ok(Default::default())
+        Ok(TupleDescriber {
+            check: self.check,
+            elements: Vec::new(),
+        })
     }
 
     fn describe_tuple_struct(
         self,
-        _name: TypeName,
+        name: TypeName,
+    ) -> Result<Self::DescribeTupleStruct, Self::Error> {
+        self.describe_tuple_struct_with(name, Default::default())
+    }
+
+    fn describe_tuple_struct_with(
+        self,
+        type_name: TypeName,
+        info: TypeInfo,
     ) -> Result<Self::DescribeTupleStruct, Self::Error> {
         Ok(TupleStructDescriber {
-            type_name: _name,
+            check: self.check,
+            type_name,
+            docs: info.docs,
             elements: Vec::new(),
+            layout: None,
         })
     }
 
@@ -137,25 +296,24 @@ impl<'a> Describer for &'a mut SchemaDescriber {
     where
         T: Describe,
     {
-        Ok(Schema::Array(Box::new(Array {
-            element: T::describe(self)?,
-            len,
-        })))
+        let element = T::describe(&mut *self).map_err(|error| error.within("*"))?;
+        self.accept(Schema::Array(Box::new(Array { element, len })))
     }
 
     fn describe_slice<T>(self) -> Result<Self::Ok, Self::Error>
     where
         T: Describe,
     {
-        Ok(Schema::Slice(Box::new(T::describe(self)?)))
+        let element = T::describe(&mut *self).map_err(|error| error.within("*"))?;
+        self.accept(Schema::Slice(Box::new(element)))
     }
 
     fn describe_seq<T>(self, name: TypeName, len: Option<usize>) -> Result<Self::Ok, Self::Error>
     where
         T: Describe,
     {
-        let element = T::describe(self)?;
-        Ok(Schema::Seq(Box::new(Sequence { name, element, len })))
+        let element = T::describe(&mut *self).map_err(|error| error.within("*"))?;
+        self.accept(Schema::Seq(Box::new(Sequence { name, element, len })))
     }
 
     fn describe_map<K, V>(self, name: TypeName) -> Result<Self::Ok, Self::Error>
@@ -163,95 +321,150 @@ impl<'a> Describer for &'a mut SchemaDescriber {
         K: Describe,
         V: Describe,
     {
-        Ok(Schema::Map(Box::new(Map {
-            name,
-            key: K::describe(&mut *self)?,
-            value: V::describe(&mut *self)?,
-        })))
+        let key = K::describe(&mut *self).map_err(|error| error.within("<key>"))?;
+        let value = V::describe(&mut *self).map_err(|error| error.within("<value>"))?;
+        self.accept(Schema::Map(Box::new(Map { name, key, value })))
     }
 
     fn describe_struct(self, type_name: TypeName) -> Result<Self::DescribeStruct, Self::Error> {
+        self.describe_struct_with(type_name, Default::default())
+    }
+
+    fn describe_struct_with(
+        self,
+        type_name: TypeName,
+        info: TypeInfo,
+    ) -> Result<Self::DescribeStruct, Self::Error> {
         Ok(StructDescriber {
+            check: self.check,
             type_name,
+            docs: info.docs,
+            attributes: info.attributes,
             fields: Vec::new(),
+            layout: None,
         })
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct StructDescriber {
+    check: Option<Check>,
     type_name: TypeName,
-    fields: Vec<(Cow<'static, str>, Schema)>,
+    docs: Option<&'static str>,
+    attributes: Attributes,
+    fields: Vec<NamedField>,
+    layout: Option<Layout>,
 }
 
 impl DescribeStruct for StructDescriber {
     type Ok = Schema;
-    type Error = Never;
+    type Error = SchemaError;
 
     fn describe_field<T: Describe>(&mut self, name: &'static str) -> Result<(), Self::Error> {
-        let ty = crate::describe::<T>();
-        self.fields.push((name.into(), ty));
+        self.describe_field_with::<T>(name, Default::default())
+    }
+
+    fn describe_field_with<T: Describe>(
+        &mut self,
+        name: &'static str,
+        info: FieldInfo,
+    ) -> Result<(), Self::Error> {
+        let field = named_field::<T>(self.check, name, info).map_err(|error| error.within(name))?;
+        self.fields.push(field);
+        Ok(())
+    }
+
+    fn describe_layout(&mut self, layout: Layout) -> Result<(), Self::Error> {
+        self.layout = Some(layout);
         Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::Struct(Struct {
-            name: self.type_name,
-            fields: self.fields,
-        }))
+        accept(
+            self.check,
+            Schema::Struct(Struct {
+                name: self.type_name,
+                docs: self.docs.map(Into::into),
+                attributes: self.attributes,
+                fields: self.fields,
+                layout: self.layout,
+            }),
+        )
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct TupleStructDescriber {
+    check: Option<Check>,
     type_name: TypeName,
+    docs: Option<&'static str>,
     elements: Vec<Schema>,
+    layout: Option<Layout>,
 }
 
 impl DescribeTupleStruct for TupleStructDescriber {
     type Ok = Schema;
-    type Error = Never;
+    type Error = SchemaError;
 
     fn describe_element<T: Describe>(&mut self) -> Result<(), Self::Error> {
-        self.elements.push(crate::describe::<T>());
+        let index = self.elements.len().to_string();
+        let element = describe_with::<T>(self.check).map_err(|error| error.within(&index))?;
+        self.elements.push(element);
+        Ok(())
+    }
+
+    fn describe_layout(&mut self, layout: Layout) -> Result<(), Self::Error> {
+        self.layout = Some(layout);
         Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::TupleStruct(TupleStruct {
-            name: self.type_name,
-            elements: self.elements,
-        }))
+        accept(
+            self.check,
+            Schema::TupleStruct(TupleStruct {
+                name: self.type_name,
+                docs: self.docs.map(Into::into),
+                elements: self.elements,
+                layout: self.layout,
+            }),
+        )
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct TupleDescriber {
+    check: Option<Check>,
     elements: Vec<Schema>,
 }
 
 impl DescribeTuple for TupleDescriber {
     type Ok = Schema;
-    type Error = Never;
+    type Error = SchemaError;
 
     fn describe_element<T: Describe>(&mut self) -> Result<(), Self::Error> {
-        self.elements.push(crate::describe::<T>());
+        let index = self.elements.len().to_string();
+        let element = describe_with::<T>(self.check).map_err(|error| error.within(&index))?;
+        self.elements.push(element);
         Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::Tuple(self.elements))
+        accept(self.check, Schema::Tuple(self.elements))
     }
 }
 
 pub struct EnumDescriber {
+    check: Option<Check>,
     type_name: TypeName,
+    docs: Option<&'static str>,
+    repr: Option<Primitive>,
     variants: Vec<Variant>,
+    layout: Option<Layout>,
 }
 
 impl DescribeEnum for EnumDescriber {
     type Ok = Schema;
-    type Error = Never;
+    type Error = SchemaError;
 
     type DescribeStructVariant = StructVariantDescriber;
     type DescribeTupleVariant = TupleVariantDescriber;
@@ -260,9 +473,20 @@ impl DescribeEnum for EnumDescriber {
         &mut self,
         name: &'static str,
         discriminant: Option<PrimitiveValue>,
+    ) -> Result<(), Self::Error> {
+        self.describe_unit_variant_with(name, discriminant, Default::default())
+    }
+
+    fn describe_unit_variant_with(
+        &mut self,
+        name: &'static str,
+        discriminant: Option<PrimitiveValue>,
+        info: VariantInfo,
     ) -> Result<(), Self::Error> {
         self.variants.push(Variant::Unit {
             name: name.into(),
+            docs: info.docs.map(Into::into),
+            attributes: info.attributes,
             discriminant,
         });
 
@@ -272,9 +496,20 @@ impl DescribeEnum for EnumDescriber {
     fn start_tuple_variant(
         &mut self,
         name: &'static str,
+    ) -> Result<Self::DescribeTupleVariant, Self::Error> {
+        self.start_tuple_variant_with(name, Default::default())
+    }
+
+    fn start_tuple_variant_with(
+        &mut self,
+        name: &'static str,
+        info: VariantInfo,
     ) -> Result<Self::DescribeTupleVariant, Self::Error> {
         Ok(TupleVariantDescriber {
+            check: self.check,
             name,
+            docs: info.docs,
+            attributes: info.attributes,
             elements: Default::default(),
         })
     }
@@ -285,6 +520,8 @@ impl DescribeEnum for EnumDescriber {
     ) -> Result<(), Self::Error> {
         self.variants.push(Variant::Tuple {
             name: variant.name.into(),
+            docs: variant.docs.map(Into::into),
+            attributes: variant.attributes,
             elements: variant.elements,
         });
 
@@ -294,9 +531,20 @@ impl DescribeEnum for EnumDescriber {
     fn start_struct_variant(
         &mut self,
         name: &'static str,
+    ) -> Result<Self::DescribeStructVariant, Self::Error> {
+        self.start_struct_variant_with(name, Default::default())
+    }
+
+    fn start_struct_variant_with(
+        &mut self,
+        name: &'static str,
+        info: VariantInfo,
     ) -> Result<Self::DescribeStructVariant, Self::Error> {
         Ok(StructVariantDescriber {
+            check: self.check,
             name,
+            docs: info.docs,
+            attributes: info.attributes,
             fields: Default::default(),
         })
     }
@@ -307,48 +555,112 @@ impl DescribeEnum for EnumDescriber {
     ) -> Result<(), Self::Error> {
         self.variants.push(Variant::Struct {
             name: variant.name.into(),
+            docs: variant.docs.map(Into::into),
+            attributes: variant.attributes,
             fields: variant.fields,
         });
 
         Ok(())
     }
 
+    fn describe_repr(&mut self, repr: Primitive) -> Result<(), Self::Error> {
+        self.repr = Some(repr);
+        Ok(())
+    }
+
+    fn describe_layout(&mut self, layout: Layout) -> Result<(), Self::Error> {
+        self.layout = Some(layout);
+        Ok(())
+    }
+
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Schema::Enum(Enum {
-            name: self.type_name,
-            repr: None,
-            variants: self.variants,
-        }))
+        accept(
+            self.check,
+            Schema::Enum(Enum {
+                name: self.type_name,
+                docs: self.docs.map(Into::into),
+                repr: self.repr,
+                variants: self.variants,
+                layout: self.layout,
+            }),
+        )
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct TupleVariantDescriber {
+    check: Option<Check>,
     name: &'static str,
+    docs: Option<&'static str>,
+    attributes: Attributes,
     elements: Vec<Schema>,
 }
 
 impl DescribeTupleVariant for TupleVariantDescriber {
-    type Error = Never;
+    type Error = SchemaError;
 
     fn describe_element<T: Describe>(&mut self) -> Result<(), Self::Error> {
-        self.elements.push(crate::describe::<T>());
+        let index = self.elements.len().to_string();
+        let element = describe_with::<T>(self.check)
+            .map_err(|error| error.within(&index).within(self.name))?;
+        self.elements.push(element);
         Ok(())
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct StructVariantDescriber {
+    check: Option<Check>,
     name: &'static str,
-    fields: Vec<(Cow<'static, str>, Schema)>,
+    docs: Option<&'static str>,
+    attributes: Attributes,
+    fields: Vec<NamedField>,
 }
 
 impl DescribeStructVariant for StructVariantDescriber {
-    type Error = Never;
+    type Error = SchemaError;
 
     fn describe_field<T: Describe>(&mut self, name: &'static str) -> Result<(), Self::Error> {
-        let ty = crate::describe::<T>();
-        self.fields.push((name.into(), ty));
+        self.describe_field_with::<T>(name, Default::default())
+    }
+
+    fn describe_field_with<T: Describe>(
+        &mut self,
+        name: &'static str,
+        info: FieldInfo,
+    ) -> Result<(), Self::Error> {
+        let field = named_field::<T>(self.check, name, info)
+            .map_err(|error| error.within(name).within(self.name))?;
+        self.fields.push(field);
         Ok(())
     }
 }
+
+fn named_field<T: Describe>(
+    check: Option<Check>,
+    name: &'static str,
+    info: FieldInfo,
+) -> Result<NamedField, SchemaError> {
+    Ok(NamedField {
+        name: name.into(),
+        docs: info.docs.map(Into::into),
+        attributes: info.attributes,
+        schema: describe_with::<T>(check)?,
+        constraints: info.constraints,
+        default: info.default,
+        skip_serializing_if: info.skip_serializing_if,
+    })
+}
+
+/// Runs `check` on `schema`, if there is one.
+fn accept(check: Option<Check>, schema: Schema) -> Result<Schema, SchemaError> {
+    match check.map_or(Ok(()), |check| check(&schema)) {
+        Ok(()) => Ok(schema),
+        Err(message) => Err(SchemaError::custom(message)),
+    }
+}
+
+/// Describes `T` with a new describer that runs the same `check`.
+fn describe_with<T: Describe>(check: Option<Check>) -> Result<Schema, SchemaError> {
+    T::describe(&mut SchemaDescriber { check })
+}
@@ -0,0 +1,1046 @@
+//! Generate starter values and config files for a type.
+//!
+//! [`skeleton`] builds the simplest value of a type: zero for numbers, `false`,
+//! empty strings, sequences and maps, `None` for options, and the first variant for
+//! enums, except that fields with a known default use it instead. [`to_toml`] and
+//! [`to_yaml`] then render a value as a config file, with the documentation of
+//! each type, field, and variant written as comments. Fields that are `None` are
+//! written commented-out with the skeleton of their inner value, so the generated
+//! file shows every available setting:
+//!
+//! ```ignore
+//! let schema = schematic::describe::<Config>();
+//! let starter = skeleton::to_toml(&schema, &schematic::skeleton(&schema)?)?;
+//! std::fs::write("config.toml", starter)?;
+//! ```
+//!
+//! [`skeleton`]: fn.skeleton.html
+//! [`to_toml`]: fn.to_toml.html
+//! [`to_yaml`]: fn.to_yaml.html
+
+use crate::{FieldDefault, NamedField, Schema, Value, Variant, VariantValue};
+use serde_json::Value as Json;
+use std::{
+    borrow::Cow,
+    convert::TryFrom,
+    fmt::{self, Write},
+    iter,
+};
+
+/// Returns the simplest value of the type described by `schema`.
+///
+/// Numbers are zero, strings, sequences and maps are empty, options are `None`,
+/// arrays contain the skeleton of their element, and enums use the first variant.
+/// Sequences with a known length contain that many elements. Fields with a known
+/// [`FieldDefault`] are set to their default, as long as it matches the schema.
+///
+/// # Errors
+///
+/// Returns an error if the schema contains an enum without any variants or a type
+/// parameter, outside of an `Option` or an empty collection, since such types have
+/// no values.
+///
+/// # Examples
+///
+/// ```
+/// use schematic::{Schema, Value};
+///
+/// let schema = schematic::describe::<(Option<u8>, [bool; 2])>();
+/// assert_eq!(
+///     Value::Tuple(vec![
+///         Value::Option(None),
+///         Value::Array(vec![Value::Bool(false), Value::Bool(false)]),
+///     ]),
+///     schematic::skeleton(&schema)?,
+/// );
+/// # Ok::<(), schematic::skeleton::Error>(())
+/// ```
+///
+/// [`FieldDefault`]: enum.FieldDefault.html
+pub fn skeleton(schema: &Schema) -> Result<Value, Error> {
+    skeleton_at(schema, "")
+}
+
+fn skeleton_at(schema: &Schema, path: &str) -> Result<Value, Error> {
+    let elements = |schemas: &[Schema]| -> Result<Vec<Value>, Error> {
+        schemas
+            .iter()
+            .enumerate()
+            .map(|(index, schema)| skeleton_at(schema, &format!("{}/{}", path, index)))
+            .collect()
+    };
+
+    Ok(match schema {
+        Schema::Unit => Value::Unit,
+        Schema::Bool => Value::Bool(false),
+        Schema::Char => Value::Char('\0'),
+
+        Schema::I8 => Value::I8(0),
+        Schema::I16 => Value::I16(0),
+        Schema::I32 => Value::I32(0),
+        Schema::I64 => Value::I64(0),
+        Schema::I128 => Value::I128(0),
+        Schema::ISize => Value::ISize(0),
+
+        Schema::U8 => Value::U8(0),
+        Schema::U16 => Value::U16(0),
+        Schema::U32 => Value::U32(0),
+        Schema::U64 => Value::U64(0),
+        Schema::U128 => Value::U128(0),
+        Schema::USize => Value::USize(0),
+
+        Schema::F32 => Value::F32(0.0),
+        Schema::F64 => Value::F64(0.0),
+
+        Schema::Str | Schema::String(_) => Value::String(String::new()),
+
+        Schema::TypeParam(name) => return Err(uninhabited(path, name)),
+
+        Schema::Option(_) => Value::Option(None),
+        Schema::Tuple(schemas) => Value::Tuple(elements(schemas)?),
+        Schema::Array(array) => Value::Array(repeat(&array.element, array.len, path)?),
+        Schema::Slice(_) => Value::Seq(Vec::new()),
+        Schema::Seq(seq) => Value::Seq(repeat(&seq.element, seq.len.unwrap_or(0), path)?),
+        Schema::Map(_) => Value::Map(Vec::new()),
+
+        Schema::UnitStruct(schema) => Value::UnitStruct {
+            name: schema.name.name.clone(),
+        },
+
+        Schema::Struct(schema) => Value::Struct {
+            name: schema.name.name.clone(),
+            fields: skeleton_fields(&schema.fields, path)?,
+        },
+
+        Schema::TupleStruct(schema) => Value::TupleStruct {
+            name: schema.name.name.clone(),
+            elements: elements(&schema.elements)?,
+        },
+
+        Schema::NewtypeStruct(schema) => Value::NewtypeStruct {
+            name: schema.name.name.clone(),
+            inner: Box::new(skeleton_at(&schema.inner, path)?),
+        },
+
+        Schema::Enum(schema) => {
+            let variant = match schema.variants.first() {
+                Some(variant) => variant,
+                None => return Err(uninhabited(path, &schema.name.name)),
+            };
+
+            let path = format!("{}/{}", path, variant.name());
+            let (variant, data) = match variant {
+                Variant::Unit { name, .. } => (name, VariantValue::Unit),
+                Variant::Tuple { name, elements, .. } => (
+                    name,
+                    VariantValue::Tuple(
+                        elements
+                            .iter()
+                            .enumerate()
+                            .map(|(index, schema)| {
+                                skeleton_at(schema, &format!("{}/{}", path, index))
+                            })
+                            .collect::<Result<_, _>>()?,
+                    ),
+                ),
+                Variant::Struct { name, fields, .. } => {
+                    (name, VariantValue::Struct(skeleton_fields(fields, &path)?))
+                }
+            };
+
+            Value::Enum {
+                name: schema.name.name.clone(),
+                variant_index: 0,
+                variant: variant.clone(),
+                data,
+            }
+        }
+    })
+}
+
+/// Returns `len` copies of the skeleton of `element`, which is only built if there
+/// are any, so that empty collections of types without values are fine.
+fn repeat(element: &Schema, len: usize, path: &str) -> Result<Vec<Value>, Error> {
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+
+    Ok(vec![skeleton_at(element, &format!("{}/0", path))?; len])
+}
+
+fn skeleton_fields(
+    fields: &[NamedField],
+    path: &str,
+) -> Result<Vec<(Cow<'static, str>, Value)>, Error> {
+    fields
+        .iter()
+        .map(|field| {
+            let path = format!("{}/{}", path, field.name);
+            Ok((field.name.clone(), skeleton_field(field, &path)?))
+        })
+        .collect()
+}
+
+fn skeleton_field(field: &NamedField, path: &str) -> Result<Value, Error> {
+    match field
+        .default
+        .as_ref()
+        .and_then(FieldDefault::value)
+        .and_then(|default| from_json(&field.schema, default))
+    {
+        Some(value) => Ok(value),
+        None => skeleton_at(&field.schema, path),
+    }
+}
+
+/// Converts a default serialized as JSON back into a value of `schema`, assuming
+/// Serde's default representation, or returns `None` if it doesn't match.
+fn from_json(schema: &Schema, json: &Json) -> Option<Value> {
+    fn int<T: TryFrom<i64> + TryFrom<u64>>(json: &Json) -> Option<T> {
+        match (json.as_i64(), json.as_u64()) {
+            (Some(value), _) => T::try_from(value).ok(),
+            (None, Some(value)) => T::try_from(value).ok(),
+            (None, None) => None,
+        }
+    }
+
+    fn elements<'a>(
+        schemas: impl ExactSizeIterator<Item = &'a Schema>,
+        json: &Json,
+    ) -> Option<Vec<Value>> {
+        match json {
+            Json::Array(elements) if elements.len() == schemas.len() => schemas
+                .zip(elements)
+                .map(|(schema, json)| from_json(schema, json))
+                .collect(),
+            _ => None,
+        }
+    }
+
+    fn fields(fields: &[NamedField], json: &Json) -> Option<Vec<(Cow<'static, str>, Value)>> {
+        let object = json.as_object()?;
+        fields
+            .iter()
+            .map(|field| {
+                let value = match object.get(field.name.as_ref()) {
+                    Some(json) => from_json(&field.schema, json)?,
+                    None => skeleton_field(field, "").ok()?,
+                };
+                Some((field.name.clone(), value))
+            })
+            .collect()
+    }
+
+    Some(match schema {
+        Schema::Unit => json.as_null().map(|()| Value::Unit)?,
+        Schema::Bool => Value::Bool(json.as_bool()?),
+        Schema::Char => {
+            let mut chars = json.as_str()?.chars();
+            match (chars.next(), chars.next()) {
+                (Some(value), None) => Value::Char(value),
+                _ => return None,
+            }
+        }
+
+        Schema::I8 => Value::I8(int(json)?),
+        Schema::I16 => Value::I16(int(json)?),
+        Schema::I32 => Value::I32(int(json)?),
+        Schema::I64 => Value::I64(int(json)?),
+        Schema::I128 => Value::I128(int(json)?),
+        Schema::ISize => Value::ISize(int(json)?),
+
+        Schema::U8 => Value::U8(int(json)?),
+        Schema::U16 => Value::U16(int(json)?),
+        Schema::U32 => Value::U32(int(json)?),
+        Schema::U64 => Value::U64(int(json)?),
+        Schema::U128 => Value::U128(int(json)?),
+        Schema::USize => Value::USize(int(json)?),
+
+        Schema::F32 => Value::F32(json.as_f64()? as f32),
+        Schema::F64 => Value::F64(json.as_f64()?),
+
+        Schema::Str | Schema::String(_) => Value::String(json.as_str()?.to_owned()),
+        Schema::TypeParam(_) => return None,
+
+        Schema::Option(inner) => match json {
+            Json::Null => Value::Option(None),
+            json => Value::Option(Some(Box::new(from_json(inner, json)?))),
+        },
+        Schema::Tuple(schemas) => Value::Tuple(elements(schemas.iter(), json)?),
+        Schema::Array(array) => {
+            Value::Array(elements(iter::repeat_n(&array.element, array.len), json)?)
+        }
+        Schema::Slice(element) => Value::Seq(
+            json.as_array()?
+                .iter()
+                .map(|json| from_json(element, json))
+                .collect::<Option<_>>()?,
+        ),
+        Schema::Seq(seq) => Value::Seq(
+            json.as_array()?
+                .iter()
+                .map(|json| from_json(&seq.element, json))
+                .collect::<Option<_>>()?,
+        ),
+        Schema::Map(map) => Value::Map(
+            json.as_object()?
+                .iter()
+                .map(|(key, json)| {
+                    // Keys that aren't strings are serialized as strings in JSON.
+                    let key = from_json(&map.key, &Json::String(key.clone()))
+                        .or_else(|| from_json(&map.key, &serde_json::from_str(key).ok()?))?;
+                    Some((key, from_json(&map.value, json)?))
+                })
+                .collect::<Option<_>>()?,
+        ),
+
+        Schema::UnitStruct(schema) => {
+            json.as_null()?;
+            Value::UnitStruct {
+                name: schema.name.name.clone(),
+            }
+        }
+
+        Schema::Struct(schema) => Value::Struct {
+            name: schema.name.name.clone(),
+            fields: fields(&schema.fields, json)?,
+        },
+
+        Schema::TupleStruct(schema) => Value::TupleStruct {
+            name: schema.name.name.clone(),
+            elements: elements(schema.elements.iter(), json)?,
+        },
+
+        Schema::NewtypeStruct(schema) => Value::NewtypeStruct {
+            name: schema.name.name.clone(),
+            inner: Box::new(from_json(&schema.inner, json)?),
+        },
+
+        Schema::Enum(schema) => {
+            let (name, data) = match json {
+                Json::String(name) => (name, None),
+                Json::Object(object) if object.len() == 1 => {
+                    let (name, data) = object.iter().next()?;
+                    (name, Some(data))
+                }
+                _ => return None,
+            };
+            let variant_index = schema
+                .variants
+                .iter()
+                .position(|variant| variant.name() == name)?;
+
+            let (variant, data) = match (&schema.variants[variant_index], data) {
+                (Variant::Unit { name, .. }, None) => (name, VariantValue::Unit),
+                (Variant::Tuple { name, elements, .. }, Some(data)) if elements.len() == 1 => (
+                    name,
+                    VariantValue::Tuple(vec![from_json(&elements[0], data)?]),
+                ),
+                (
+                    Variant::Tuple {
+                        name,
+                        elements: schemas,
+                        ..
+                    },
+                    Some(data),
+                ) => (name, VariantValue::Tuple(elements(schemas.iter(), data)?)),
+                (
+                    Variant::Struct {
+                        name,
+                        fields: schemas,
+                        ..
+                    },
+                    Some(data),
+                ) => (name, VariantValue::Struct(fields(schemas, data)?)),
+                _ => return None,
+            };
+
+            Value::Enum {
+                name: schema.name.name.clone(),
+                variant_index: u32::try_from(variant_index).ok()?,
+                variant: variant.clone(),
+                data,
+            }
+        }
+    })
+}
+
+/// Renders `value` as a TOML document, with documentation from `schema` written as
+/// comments.
+///
+/// The value must be a struct or map, since the root of a TOML document is always
+/// a table. Nested structs and maps are written as sections, and fields that are
+/// `None` are written commented-out.
+///
+/// # Errors
+///
+/// Returns an error if `value` doesn't match `schema`, or if it contains values
+/// that TOML can't represent, such as `()`, `None` outside of a struct, or
+/// integers that don't fit in an `i64`.
+///
+/// # Examples
+///
+/// ```
+/// use schematic::{NamedField, Schema, Struct, TypeName};
+///
+/// let schema = Schema::Struct(Struct {
+///     name: TypeName::new("Config", "my_crate"),
+///     docs: Some(" Settings for the server.".into()),
+///     attributes: Default::default(),
+///     fields: vec![
+///         NamedField::new("port", Schema::U16),
+///         NamedField::new("host", Schema::Option(Box::new(Schema::Str))),
+///     ],
+///     layout: None,
+/// });
+///
+/// let toml = schematic::skeleton::to_toml(&schema, &schematic::skeleton(&schema)?)?;
+/// assert_eq!("# Settings for the server.\n\nport = 0\n# host = \"\"\n", toml);
+/// # Ok::<(), schematic::skeleton::Error>(())
+/// ```
+pub fn to_toml(schema: &Schema, value: &Value) -> Result<String, Error> {
+    let entries = match node(schema, value, "")? {
+        Node::Table(entries) => entries,
+        _ => return Err(unsupported("", "a value that isn't a table")),
+    };
+
+    let mut output = String::new();
+    if let Some(docs) = schema.docs() {
+        comment(&mut output, 0, Some(docs));
+        output.push('\n');
+    }
+    write_toml_table(&mut output, &mut Vec::new(), &entries, "")?;
+
+    Ok(output)
+}
+
+/// Renders `value` as a YAML document, with documentation from `schema` written as
+/// comments.
+///
+/// Structs, maps and non-empty sequences are written in block style, and fields
+/// that are `None` are written commented-out.
+///
+/// # Errors
+///
+/// Returns an error if `value` doesn't match `schema`.
+///
+/// # Examples
+///
+/// ```
+/// use schematic::{NamedField, Schema, Struct, TypeName};
+///
+/// let schema = Schema::Struct(Struct {
+///     name: TypeName::new("Config", "my_crate"),
+///     docs: None,
+///     attributes: Default::default(),
+///     fields: vec![
+///         NamedField::new("port", Schema::U16),
+///         NamedField::new("tags", schematic::describe::<Vec<String>>()),
+///     ],
+///     layout: None,
+/// });
+///
+/// let yaml = schematic::skeleton::to_yaml(&schema, &schematic::skeleton(&schema)?)?;
+/// assert_eq!("port: 0\ntags: []\n", yaml);
+/// # Ok::<(), schematic::skeleton::Error>(())
+/// ```
+pub fn to_yaml(schema: &Schema, value: &Value) -> Result<String, Error> {
+    let node = node(schema, value, "")?;
+
+    let mut output = String::new();
+    comment(&mut output, 0, schema.docs());
+    match &node {
+        Node::Table(entries) if !entries.is_empty() => write_yaml_table(&mut output, 0, entries),
+        Node::List(elements) if !elements.is_empty() => write_yaml_list(&mut output, 0, elements),
+        node => {
+            output.push_str(&yaml_inline(node));
+            output.push('\n');
+        }
+    }
+
+    Ok(output)
+}
+
+/// An error building a skeleton, or rendering a value as a config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The value doesn't match the schema.
+    Mismatch {
+        /// The path to the value, e.g. `/servers/0/port`. Fields, variants and map
+        /// entries are identified by name, and elements by index.
+        path: String,
+    },
+
+    /// The schema contains a type without any values, such as an enum without
+    /// variants or a type parameter, so there's no skeleton to build.
+    Uninhabited {
+        /// The path to the value.
+        path: String,
+
+        /// The name of the type.
+        ty: String,
+    },
+
+    /// The value can't be represented in the output format.
+    Unsupported {
+        /// The path to the value.
+        path: String,
+
+        /// A description of the value.
+        value: &'static str,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Mismatch { path } => {
+                write!(
+                    f,
+                    "value doesn't match the schema at `{}`",
+                    display_path(path)
+                )
+            }
+
+            Error::Uninhabited { path, ty } => {
+                write!(f, "type `{}` has no values at `{}`", ty, display_path(path))
+            }
+
+            Error::Unsupported { path, value } => {
+                write!(f, "cannot represent {} at `{}`", value, display_path(path))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "/"
+    } else {
+        path
+    }
+}
+
+fn unsupported(path: &str, value: &'static str) -> Error {
+    Error::Unsupported {
+        path: path.into(),
+        value,
+    }
+}
+
+fn uninhabited(path: &str, ty: &str) -> Error {
+    Error::Uninhabited {
+        path: path.into(),
+        ty: ty.into(),
+    }
+}
+
+fn mismatch(path: &str) -> Error {
+    Error::Mismatch { path: path.into() }
+}
+
+/// A value in the data model shared by TOML and YAML.
+enum Node<'a> {
+    Null,
+    Bool(bool),
+    Integer(i128),
+    Float(f64),
+    String(String),
+    List(Vec<Node<'a>>),
+    Table(Vec<Entry<'a>>),
+}
+
+struct Entry<'a> {
+    key: String,
+    docs: Option<&'a str>,
+    node: Node<'a>,
+
+    /// The skeleton of the inner value of optional fields that are `None`.
+    example: Option<Node<'a>>,
+}
+
+fn node<'a>(schema: &'a Schema, value: &Value, path: &str) -> Result<Node<'a>, Error> {
+    let node = match (schema, value) {
+        (Schema::Unit, Value::Unit) => Node::Null,
+        (Schema::Bool, Value::Bool(value)) => Node::Bool(*value),
+        (Schema::Char, Value::Char(value)) => Node::String(value.to_string()),
+
+        (Schema::I8, Value::I8(value)) => Node::Integer((*value).into()),
+        (Schema::I16, Value::I16(value)) => Node::Integer((*value).into()),
+        (Schema::I32, Value::I32(value)) => Node::Integer((*value).into()),
+        (Schema::I64, Value::I64(value)) => Node::Integer((*value).into()),
+        (Schema::I128, Value::I128(value)) => Node::Integer(*value),
+        (Schema::ISize, Value::ISize(value)) => Node::Integer(*value as i128),
+
+        (Schema::U8, Value::U8(value)) => Node::Integer((*value).into()),
+        (Schema::U16, Value::U16(value)) => Node::Integer((*value).into()),
+        (Schema::U32, Value::U32(value)) => Node::Integer((*value).into()),
+        (Schema::U64, Value::U64(value)) => Node::Integer((*value).into()),
+        (Schema::U128, Value::U128(value)) => Node::Integer(
+            i128::try_from(*value)
+                .map_err(|_| unsupported(path, "an integer larger than `i128::MAX`"))?,
+        ),
+        (Schema::USize, Value::USize(value)) => Node::Integer(*value as i128),
+
+        (Schema::F32, Value::F32(value)) => Node::Float((*value).into()),
+        (Schema::F64, Value::F64(value)) => Node::Float(*value),
+
+        (Schema::Str, Value::String(value)) | (Schema::String(_), Value::String(value)) => {
+            Node::String(value.clone())
+        }
+
+        (Schema::Option(_), Value::Option(None)) => Node::Null,
+        (Schema::Option(inner), Value::Option(Some(value))) => node(inner, value, path)?,
+
+        (Schema::Tuple(elements), Value::Tuple(values)) => list(elements, values, path)?,
+
+        (Schema::Array(array), Value::Array(values)) if values.len() == array.len => {
+            list(iter::repeat_n(&array.element, values.len()), values, path)?
+        }
+
+        (Schema::Slice(element), Value::Seq(values)) => {
+            list(iter::repeat_n(&**element, values.len()), values, path)?
+        }
+
+        (Schema::Seq(seq), Value::Seq(values)) if seq.len.is_none_or(|len| len == values.len()) => {
+            list(iter::repeat_n(&seq.element, values.len()), values, path)?
+        }
+
+        (Schema::Map(map), Value::Map(entries)) => Node::Table(
+            entries
+                .iter()
+                .map(|(key, value)| {
+                    let key = map_key(key, path)?;
+                    let path = format!("{}/{}", path, key);
+                    Ok(Entry {
+                        node: node(&map.value, value, &path)?,
+                        key,
+                        docs: None,
+                        example: None,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+
+        (Schema::UnitStruct(_), Value::UnitStruct { .. }) => Node::Null,
+
+        (Schema::Struct(schema), Value::Struct { fields, .. }) => {
+            Node::Table(table(&schema.fields, fields, path)?)
+        }
+
+        (Schema::TupleStruct(schema), Value::TupleStruct { elements, .. }) => {
+            list(&schema.elements, elements, path)?
+        }
+
+        (Schema::NewtypeStruct(schema), Value::NewtypeStruct { inner, .. }) => {
+            node(&schema.inner, inner, path)?
+        }
+
+        (
+            Schema::Enum(schema),
+            Value::Enum {
+                variant_index,
+                data,
+                ..
+            },
+        ) => {
+            let variant = schema
+                .variants
+                .get(*variant_index as usize)
+                .ok_or_else(|| mismatch(path))?;
+            let path = format!("{}/{}", path, variant.name());
+
+            // Variants are externally tagged, the same as Serde's default.
+            let inner = match (variant, data) {
+                (Variant::Unit { .. }, VariantValue::Unit) => {
+                    return Ok(Node::String(variant.name().into()));
+                }
+
+                (Variant::Tuple { elements, .. }, VariantValue::Tuple(values))
+                    if elements.len() == 1 && values.len() == 1 =>
+                {
+                    node(&elements[0], &values[0], &path)?
+                }
+
+                (Variant::Tuple { elements, .. }, VariantValue::Tuple(values)) => {
+                    list(elements, values, &path)?
+                }
+
+                (Variant::Struct { fields, .. }, VariantValue::Struct(values)) => {
+                    Node::Table(table(fields, values, &path)?)
+                }
+
+                _ => return Err(mismatch(&path)),
+            };
+
+            Node::Table(vec![Entry {
+                key: variant.name().into(),
+                docs: variant.docs(),
+                node: inner,
+                example: None,
+            }])
+        }
+
+        _ => return Err(mismatch(path)),
+    };
+
+    Ok(node)
+}
+
+fn list<'a, I>(schemas: I, values: &[Value], path: &str) -> Result<Node<'a>, Error>
+where
+    I: IntoIterator<Item = &'a Schema>,
+    I::IntoIter: ExactSizeIterator,
+{
+    let schemas = schemas.into_iter();
+    if schemas.len() != values.len() {
+        return Err(mismatch(path));
+    }
+
+    schemas
+        .zip(values)
+        .enumerate()
+        .map(|(index, (schema, value))| node(schema, value, &format!("{}/{}", path, index)))
+        .collect::<Result<_, _>>()
+        .map(Node::List)
+}
+
+fn table<'a>(
+    fields: &'a [NamedField],
+    values: &[(Cow<'static, str>, Value)],
+    path: &str,
+) -> Result<Vec<Entry<'a>>, Error> {
+    if fields.len() != values.len() {
+        return Err(mismatch(path));
+    }
+
+    fields
+        .iter()
+        .zip(values)
+        .map(|(field, (name, value))| {
+            let path = format!("{}/{}", path, field.name);
+            if field.name != *name {
+                return Err(mismatch(&path));
+            }
+
+            let example = match (&field.schema, value) {
+                // Options of types without values are `None` and have no example.
+                (Schema::Option(inner), Value::Option(None)) => match skeleton_at(inner, &path) {
+                    Ok(example) => Some(node(inner, &example, &path)?),
+                    Err(_) => None,
+                },
+                _ => None,
+            };
+
+            Ok(Entry {
+                key: field.name.to_string(),
+                docs: field.docs.as_deref(),
+                node: node(&field.schema, value, &path)?,
+                example,
+            })
+        })
+        .collect()
+}
+
+/// Returns the string used as the key for a map entry.
+fn map_key(key: &Value, path: &str) -> Result<String, Error> {
+    let key = match key {
+        Value::String(key) => key.clone(),
+        Value::Char(key) => key.to_string(),
+        Value::Bool(key) => key.to_string(),
+
+        Value::I8(key) => key.to_string(),
+        Value::I16(key) => key.to_string(),
+        Value::I32(key) => key.to_string(),
+        Value::I64(key) => key.to_string(),
+        Value::I128(key) => key.to_string(),
+        Value::ISize(key) => key.to_string(),
+
+        Value::U8(key) => key.to_string(),
+        Value::U16(key) => key.to_string(),
+        Value::U32(key) => key.to_string(),
+        Value::U64(key) => key.to_string(),
+        Value::U128(key) => key.to_string(),
+        Value::USize(key) => key.to_string(),
+
+        Value::NewtypeStruct { inner, .. } => map_key(inner, path)?,
+
+        Value::Enum {
+            variant,
+            data: VariantValue::Unit,
+            ..
+        } => variant.to_string(),
+
+        _ => return Err(unsupported(path, "a map key that isn't a string")),
+    };
+
+    Ok(key)
+}
+
+/// Writes `docs` as a comment, one line at a time.
+fn comment(output: &mut String, indent: usize, docs: Option<&str>) {
+    for line in docs.unwrap_or_default().lines() {
+        // Doc comments usually start with a space after the `///`.
+        let line = line.strip_prefix(' ').unwrap_or(line).trim_end();
+        if line.is_empty() {
+            writeln!(output, "{:indent$}#", "", indent = indent).unwrap();
+        } else {
+            writeln!(output, "{:indent$}# {}", "", line, indent = indent).unwrap();
+        }
+    }
+}
+
+/// Writes `value` as a double-quoted string, which uses the same escapes in TOML
+/// and YAML.
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => write!(quoted, "\\u{:04X}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Returns `true` for nodes written as a `[section]` or `[[section]]`.
+fn is_toml_section(node: &Node<'_>) -> bool {
+    match node {
+        Node::Table(_) => true,
+        Node::List(elements) => {
+            !elements.is_empty() && elements.iter().all(|node| matches!(node, Node::Table(_)))
+        }
+        _ => false,
+    }
+}
+
+fn write_toml_table(
+    output: &mut String,
+    keys: &mut Vec<String>,
+    entries: &[Entry<'_>],
+    path: &str,
+) -> Result<(), Error> {
+    // Key/value pairs have to come before any sections, otherwise they'd belong to
+    // the last section.
+    for entry in entries.iter().filter(|entry| !is_toml_section(&entry.node)) {
+        let path = format!("{}/{}", path, entry.key);
+        let key = toml_key(&entry.key);
+        match (&entry.node, &entry.example) {
+            // TOML has no null, so `None` is left out, or commented-out if there's
+            // an example.
+            (Node::Null, example) => {
+                if let Some(Ok(example)) = example.as_ref().map(|node| toml_inline(node, &path)) {
+                    comment(output, 0, entry.docs);
+                    writeln!(output, "# {} = {}", key, example).unwrap();
+                }
+            }
+
+            (node, _) => {
+                let value = toml_inline(node, &path)?;
+                comment(output, 0, entry.docs);
+                writeln!(output, "{} = {}", key, value).unwrap();
+            }
+        }
+    }
+
+    for entry in entries.iter().filter(|entry| is_toml_section(&entry.node)) {
+        let path = format!("{}/{}", path, entry.key);
+        keys.push(toml_key(&entry.key));
+
+        match &entry.node {
+            Node::Table(table) => {
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+                comment(output, 0, entry.docs);
+                writeln!(output, "[{}]", keys.join(".")).unwrap();
+                write_toml_table(output, keys, table, &path)?;
+            }
+
+            Node::List(elements) => {
+                for (index, element) in elements.iter().enumerate() {
+                    if let Node::Table(table) = element {
+                        if !output.is_empty() {
+                            output.push('\n');
+                        }
+                        if index == 0 {
+                            comment(output, 0, entry.docs);
+                        }
+                        writeln!(output, "[[{}]]", keys.join(".")).unwrap();
+                        write_toml_table(output, keys, table, &format!("{}/{}", path, index))?;
+                    }
+                }
+            }
+
+            _ => unreachable!("only tables and lists of tables are sections"),
+        }
+
+        keys.pop();
+    }
+
+    Ok(())
+}
+
+fn toml_inline(node: &Node<'_>, path: &str) -> Result<String, Error> {
+    let value = match node {
+        Node::Null => return Err(unsupported(path, "a null value")),
+        Node::Bool(value) => value.to_string(),
+        Node::Integer(value) => i64::try_from(*value)
+            .map_err(|_| unsupported(path, "an integer outside the range of `i64`"))?
+            .to_string(),
+        Node::Float(value) if value.is_nan() => "nan".into(),
+        Node::Float(value) if value.is_infinite() && *value > 0.0 => "inf".into(),
+        Node::Float(value) if value.is_infinite() => "-inf".into(),
+        Node::Float(value) => format!("{:?}", value),
+        Node::String(value) => quote(value),
+
+        Node::List(elements) => {
+            let elements = elements
+                .iter()
+                .enumerate()
+                .map(|(index, element)| toml_inline(element, &format!("{}/{}", path, index)))
+                .collect::<Result<Vec<_>, _>>()?;
+            format!("[{}]", elements.join(", "))
+        }
+
+        Node::Table(entries) => {
+            let mut pairs = Vec::with_capacity(entries.len());
+            for entry in entries {
+                if let (Node::Null, Some(_)) = (&entry.node, &entry.example) {
+                    continue;
+                }
+
+                let path = format!("{}/{}", path, entry.key);
+                let value = toml_inline(&entry.node, &path)?;
+                pairs.push(format!("{} = {}", toml_key(&entry.key), value));
+            }
+
+            if pairs.is_empty() {
+                "{}".into()
+            } else {
+                format!("{{ {} }}", pairs.join(", "))
+            }
+        }
+    };
+
+    Ok(value)
+}
+
+fn toml_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    if bare {
+        key.into()
+    } else {
+        quote(key)
+    }
+}
+
+fn write_yaml_table(output: &mut String, indent: usize, entries: &[Entry<'_>]) {
+    for entry in entries {
+        let key = yaml_key(&entry.key);
+        comment(output, indent, entry.docs);
+        match (&entry.node, &entry.example) {
+            (Node::Null, Some(example)) => {
+                let example = yaml_inline(example);
+                writeln!(
+                    output,
+                    "{:indent$}# {}: {}",
+                    "",
+                    key,
+                    example,
+                    indent = indent
+                )
+                .unwrap();
+            }
+
+            (node, _) => {
+                write!(output, "{:indent$}{}:", "", key, indent = indent).unwrap();
+                write_yaml_value(output, indent, node);
+            }
+        }
+    }
+}
+
+fn write_yaml_list(output: &mut String, indent: usize, elements: &[Node<'_>]) {
+    for element in elements {
+        write!(output, "{:indent$}-", "", indent = indent).unwrap();
+        write_yaml_value(output, indent, element);
+    }
+}
+
+/// Writes the value following a key or a list item marker, either on the same line
+/// or as an indented block.
+fn write_yaml_value(output: &mut String, indent: usize, node: &Node<'_>) {
+    match node {
+        Node::Table(entries) if !entries.is_empty() => {
+            output.push('\n');
+            write_yaml_table(output, indent + 2, entries);
+        }
+
+        Node::List(elements) if !elements.is_empty() => {
+            output.push('\n');
+            write_yaml_list(output, indent + 2, elements);
+        }
+
+        node => writeln!(output, " {}", yaml_inline(node)).unwrap(),
+    }
+}
+
+fn yaml_inline(node: &Node<'_>) -> String {
+    match node {
+        Node::Null => "null".into(),
+        Node::Bool(value) => value.to_string(),
+        Node::Integer(value) => value.to_string(),
+        Node::Float(value) if value.is_nan() => ".nan".into(),
+        Node::Float(value) if value.is_infinite() && *value > 0.0 => ".inf".into(),
+        Node::Float(value) if value.is_infinite() => "-.inf".into(),
+        Node::Float(value) => format!("{:?}", value),
+        Node::String(value) => quote(value),
+
+        Node::List(elements) => {
+            let elements = elements.iter().map(yaml_inline).collect::<Vec<_>>();
+            format!("[{}]", elements.join(", "))
+        }
+
+        Node::Table(entries) => {
+            let pairs = entries
+                .iter()
+                .filter(|entry| !matches!((&entry.node, &entry.example), (Node::Null, Some(_))))
+                .map(|entry| format!("{}: {}", yaml_key(&entry.key), yaml_inline(&entry.node)))
+                .collect::<Vec<_>>();
+            format!("{{{}}}", pairs.join(", "))
+        }
+    }
+}
+
+fn yaml_key(key: &str) -> String {
+    const RESERVED: &[&str] = &["true", "false", "null", "yes", "no", "on", "off", "y", "n"];
+
+    let plain = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && !RESERVED.contains(&&*key.to_ascii_lowercase());
+
+    if plain {
+        key.into()
+    } else {
+        quote(key)
+    }
+}
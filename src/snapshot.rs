@@ -0,0 +1,231 @@
+//! Snapshot tests for the shape of types.
+//!
+//! [`assert_schema_snapshot!`] fails a test when the schema of a type no longer
+//! matches the snapshot committed under `tests/snapshots/` in the crate being
+//! tested, which catches unintentional changes to the serialized shape of public
+//! types:
+//!
+//! ```ignore
+//! #[test]
+//! fn config_schema() {
+//!     schematic::assert_schema_snapshot!(Config);
+//! }
+//! ```
+//!
+//! Snapshots are stored as pretty-printed JSON. When a snapshot is missing or
+//! doesn't match, the new schema is written next to it with an additional `.new`
+//! extension, and the test fails with a diff of the two. To accept the change,
+//! either rename the `.new` file or run the tests with the environment variable
+//! `SCHEMATIC_UPDATE_SNAPSHOTS=1`, which overwrites the snapshots instead.
+//!
+//! [`assert_schema_snapshot!`]: ../macro.assert_schema_snapshot.html
+
+use crate::Schema;
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The environment variable that makes snapshot assertions write snapshots instead
+/// of comparing against them.
+pub const UPDATE_VAR: &str = "SCHEMATIC_UPDATE_SNAPSHOTS";
+
+/// Asserts that the schema of a type matches its snapshot.
+///
+/// The snapshot is stored at `tests/snapshots/<name>.json` relative to the root of
+/// the crate being tested. The name defaults to the type as written, e.g.
+/// `Vec<u8>` is stored as `Vec_u8.json`, and can be given as a second argument:
+///
+/// ```ignore
+/// schematic::assert_schema_snapshot!(Vec<Event>, "events");
+/// ```
+///
+/// See the [`snapshot`] module for how to update snapshots.
+///
+/// [`snapshot`]: snapshot/index.html
+#[macro_export]
+macro_rules! assert_schema_snapshot {
+    ($ty:ty $(,)?) => {
+        $crate::assert_schema_snapshot!($ty, stringify!($ty))
+    };
+
+    ($ty:ty, $name:expr $(,)?) => {
+        $crate::snapshot::assert_snapshot(
+            &$crate::describe::<$ty>(),
+            &$crate::snapshot::path(env!("CARGO_MANIFEST_DIR"), $name),
+        )
+    };
+}
+
+/// Returns the path of the snapshot called `name` for the crate at `manifest_dir`.
+///
+/// Characters that aren't valid in file names on every platform are replaced with
+/// underscores.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// assert_eq!(
+///     Path::new("my_crate/tests/snapshots/Vec_u8.json"),
+///     schematic::snapshot::path("my_crate", "Vec<u8>"),
+/// );
+/// ```
+pub fn path(manifest_dir: &str, name: &str) -> PathBuf {
+    let mut file_name = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+            file_name.push(c);
+        } else if !file_name.is_empty() && !file_name.ends_with('_') {
+            file_name.push('_');
+        }
+    }
+
+    let file_name = file_name.trim_end_matches('_');
+    Path::new(manifest_dir)
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.json", file_name))
+}
+
+/// Serializes `schema` in the format used for snapshots.
+pub fn to_snapshot(schema: &Schema) -> String {
+    // NOTE: The unwrap here will never panic because `Schema` only contains data
+    // that can be represented in JSON.
+    serde_json::to_string_pretty(schema).unwrap() + "\n"
+}
+
+/// Asserts that `schema` matches the snapshot stored at `path`.
+///
+/// If the environment variable named by [`UPDATE_VAR`] is set to anything other
+/// than `0`, the snapshot is overwritten instead.
+///
+/// # Panics
+///
+/// Panics if the snapshot is missing or doesn't match `schema`, after writing the
+/// new snapshot to `path` with an additional `.new` extension.
+///
+/// [`UPDATE_VAR`]: constant.UPDATE_VAR.html
+pub fn assert_snapshot(schema: &Schema, path: &Path) {
+    let actual = to_snapshot(schema);
+
+    let mut new_path = path.as_os_str().to_owned();
+    new_path.push(".new");
+    let new_path = PathBuf::from(new_path);
+
+    if env::var_os(UPDATE_VAR).is_some_and(|value| value != "0") {
+        write(path, &actual);
+        let _ = fs::remove_file(&new_path);
+        return;
+    }
+
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => expected,
+
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            write(&new_path, &actual);
+            panic!(
+                "missing schema snapshot {}\n\nThe schema was written to {}. Rename it to accept \
+                 it, or rerun with {}=1.",
+                path.display(),
+                new_path.display(),
+                UPDATE_VAR,
+            );
+        }
+
+        Err(error) => panic!("failed to read {}: {}", path.display(), error),
+    };
+
+    if expected == actual {
+        let _ = fs::remove_file(&new_path);
+        return;
+    }
+
+    write(&new_path, &actual);
+    panic!(
+        "schema doesn't match snapshot {}\n\n{}\nThe new schema was written to {}. Rename it \
+         to accept the change, or rerun with {}=1.",
+        path.display(),
+        diff(&expected, &actual),
+        new_path.display(),
+        UPDATE_VAR,
+    );
+}
+
+fn write(path: &Path, contents: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|error| panic!("failed to create {}: {}", parent.display(), error));
+    }
+
+    fs::write(path, contents)
+        .unwrap_or_else(|error| panic!("failed to write {}: {}", path.display(), error));
+}
+
+/// The number of unchanged lines shown around each change.
+const CONTEXT: usize = 2;
+
+/// Returns a line-based diff from `old` to `new`, showing removed lines with `-`,
+/// added lines with `+`, and separating groups of changes with `@@`.
+pub fn diff(old: &str, new: &str) -> String {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+
+    // `lengths[i][j]` is the length of the longest common subsequence of `old[i..]`
+    // and `new[j..]`.
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            lines.push(('-', old[i]));
+            i += 1;
+        } else {
+            lines.push(('+', new[j]));
+            j += 1;
+        }
+    }
+
+    let changed = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (kind, _))| *kind != ' ')
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+
+    let mut output = String::new();
+    let mut end = None;
+    for (index, (kind, line)) in lines.iter().enumerate() {
+        let near_change = changed
+            .iter()
+            .any(|&changed| index + CONTEXT >= changed && index <= changed + CONTEXT);
+        if !near_change {
+            continue;
+        }
+
+        if end.is_none_or(|end| end + 1 != index) {
+            output.push_str("@@\n");
+        }
+        output.push(*kind);
+        output.push_str(line);
+        output.push('\n');
+        end = Some(index);
+    }
+
+    output
+}
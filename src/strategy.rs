@@ -0,0 +1,240 @@
+//! Property-based testing strategies generated from schemas.
+//!
+//! [`strategy`] turns a [`Schema`] into a [proptest] strategy that generates
+//! [`Value`]s of the described type. Since values serialize the same way as the
+//! original type, they can be fed to the deserializer of any Serde format to check
+//! that structurally valid data is always accepted:
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use schematic::Describe;
+//!
+//! # fn check<T: Describe + serde::de::DeserializeOwned>() {
+//! let strategy = schematic::strategy::strategy(&schematic::describe::<T>()).unwrap();
+//! proptest!(|(value in &strategy)| {
+//!     let json = serde_json::to_string(&value).unwrap();
+//!     prop_assert!(serde_json::from_str::<T>(&json).is_ok());
+//! });
+//! # }
+//! # check::<Vec<Option<u8>>>();
+//! ```
+//!
+//! The generated values respect the shape of the schema: integers cover the full
+//! range of their type, `Option`s are either `None` or `Some`, arrays and
+//! sequences with a known length have exactly that many elements, and enums pick
+//! any one of their variants. Sequences and maps without a known length have up
+//! to [`MAX_LEN`] entries, and the keys of maps are distinct. Floating-point
+//! numbers are always finite, since most formats can't represent NaN or infinity.
+//!
+//! Types without any values, such as enums without variants and type parameters,
+//! are never generated: `Option`s of them are always `None`, sequences and maps
+//! of them are empty, and enums skip variants that contain them. If the schema
+//! itself has no values, there's no strategy at all.
+//!
+//! This module is only available with the `proptest` feature.
+//!
+//! [`strategy`]: fn.strategy.html
+//! [`Schema`]: ../enum.Schema.html
+//! [`Value`]: ../enum.Value.html
+//! [`MAX_LEN`]: constant.MAX_LEN.html
+//! [proptest]: https://crates.io/crates/proptest
+
+use crate::{NamedField, Schema, Value, Variant, VariantValue};
+use proptest::{
+    collection,
+    num::{f32, f64},
+    prelude::*,
+    strategy::Union,
+};
+use std::{borrow::Cow, ops::RangeInclusive};
+
+/// The maximum number of entries generated for sequences and maps without a known
+/// length.
+pub const MAX_LEN: usize = 8;
+
+/// Returns a strategy that generates values of the type described by `schema`, or
+/// `None` if the type has no values, e.g. because it's an enum without variants or
+/// a type parameter.
+pub fn strategy(schema: &Schema) -> Option<BoxedStrategy<Value>> {
+    Some(match schema {
+        Schema::Unit => Just(Value::Unit).boxed(),
+        Schema::Bool => any::<bool>().prop_map(Value::Bool).boxed(),
+        Schema::Char => any::<char>().prop_map(Value::Char).boxed(),
+
+        Schema::I8 => any::<i8>().prop_map(Value::I8).boxed(),
+        Schema::I16 => any::<i16>().prop_map(Value::I16).boxed(),
+        Schema::I32 => any::<i32>().prop_map(Value::I32).boxed(),
+        Schema::I64 => any::<i64>().prop_map(Value::I64).boxed(),
+        Schema::I128 => any::<i128>().prop_map(Value::I128).boxed(),
+        Schema::ISize => any::<isize>().prop_map(Value::ISize).boxed(),
+
+        Schema::U8 => any::<u8>().prop_map(Value::U8).boxed(),
+        Schema::U16 => any::<u16>().prop_map(Value::U16).boxed(),
+        Schema::U32 => any::<u32>().prop_map(Value::U32).boxed(),
+        Schema::U64 => any::<u64>().prop_map(Value::U64).boxed(),
+        Schema::U128 => any::<u128>().prop_map(Value::U128).boxed(),
+        Schema::USize => any::<usize>().prop_map(Value::USize).boxed(),
+
+        Schema::F32 => (f32::POSITIVE | f32::NEGATIVE | f32::NORMAL | f32::SUBNORMAL | f32::ZERO)
+            .prop_map(Value::F32)
+            .boxed(),
+        Schema::F64 => (f64::POSITIVE | f64::NEGATIVE | f64::NORMAL | f64::SUBNORMAL | f64::ZERO)
+            .prop_map(Value::F64)
+            .boxed(),
+
+        Schema::Str | Schema::String(_) => any::<String>().prop_map(Value::String).boxed(),
+
+        Schema::TypeParam(_) => return None,
+
+        Schema::Option(inner) => match strategy(inner) {
+            Some(inner) => proptest::option::of(inner)
+                .prop_map(|value| Value::Option(value.map(Box::new)))
+                .boxed(),
+            None => Just(Value::Option(None)).boxed(),
+        },
+
+        Schema::Tuple(elements) => elements_strategy(elements)?.prop_map(Value::Tuple).boxed(),
+
+        Schema::Array(array) => repeat(&array.element, array.len..=array.len)?
+            .prop_map(Value::Array)
+            .boxed(),
+
+        Schema::Slice(element) => repeat(element, 0..=MAX_LEN)?.prop_map(Value::Seq).boxed(),
+
+        Schema::Seq(seq) => {
+            let len = match seq.len {
+                Some(len) => len..=len,
+                None => 0..=MAX_LEN,
+            };
+            repeat(&seq.element, len)?.prop_map(Value::Seq).boxed()
+        }
+
+        Schema::Map(map) => match (strategy(&map.key), strategy(&map.value)) {
+            (Some(key), Some(value)) => collection::vec((key, value), 0..=MAX_LEN)
+                .prop_map(|mut entries| {
+                    // Keys are compared by value, since `Value` can't be ordered or hashed.
+                    let mut index = 0;
+                    while index < entries.len() {
+                        if entries[..index]
+                            .iter()
+                            .any(|(key, _)| *key == entries[index].0)
+                        {
+                            entries.remove(index);
+                        } else {
+                            index += 1;
+                        }
+                    }
+
+                    Value::Map(entries)
+                })
+                .boxed(),
+            _ => Just(Value::Map(Vec::new())).boxed(),
+        },
+
+        Schema::UnitStruct(schema) => Just(Value::UnitStruct {
+            name: schema.name.name.clone(),
+        })
+        .boxed(),
+
+        Schema::Struct(schema) => {
+            let name = schema.name.name.clone();
+            fields_strategy(&schema.fields)?
+                .prop_map(move |fields| Value::Struct {
+                    name: name.clone(),
+                    fields,
+                })
+                .boxed()
+        }
+
+        Schema::TupleStruct(schema) => {
+            let name = schema.name.name.clone();
+            elements_strategy(&schema.elements)?
+                .prop_map(move |elements| Value::TupleStruct {
+                    name: name.clone(),
+                    elements,
+                })
+                .boxed()
+        }
+
+        Schema::NewtypeStruct(schema) => {
+            let name = schema.name.name.clone();
+            strategy(&schema.inner)?
+                .prop_map(move |inner| Value::NewtypeStruct {
+                    name: name.clone(),
+                    inner: Box::new(inner),
+                })
+                .boxed()
+        }
+
+        Schema::Enum(schema) => {
+            // Variants containing a type without values can't be generated.
+            let variants = schema
+                .variants
+                .iter()
+                .enumerate()
+                .filter_map(|(index, variant)| {
+                    let name = schema.name.name.clone();
+                    let (variant_name, data) = match variant {
+                        Variant::Unit { name, .. } => (name, Just(VariantValue::Unit).boxed()),
+                        Variant::Tuple { name, elements, .. } => (
+                            name,
+                            elements_strategy(elements)?
+                                .prop_map(VariantValue::Tuple)
+                                .boxed(),
+                        ),
+                        Variant::Struct { name, fields, .. } => (
+                            name,
+                            fields_strategy(fields)?
+                                .prop_map(VariantValue::Struct)
+                                .boxed(),
+                        ),
+                    };
+                    let variant_name = variant_name.clone();
+
+                    Some(
+                        data.prop_map(move |data| Value::Enum {
+                            name: name.clone(),
+                            variant_index: index as u32,
+                            variant: variant_name.clone(),
+                            data,
+                        })
+                        .boxed(),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            if variants.is_empty() {
+                return None;
+            }
+
+            Union::new(variants).boxed()
+        }
+    })
+}
+
+/// Returns a strategy for `len` elements of type `element`, which only generates
+/// empty sequences if the element type has no values.
+fn repeat(element: &Schema, len: RangeInclusive<usize>) -> Option<BoxedStrategy<Vec<Value>>> {
+    match strategy(element) {
+        Some(element) => Some(collection::vec(element, len).boxed()),
+        None if *len.start() == 0 => Some(Just(Vec::new()).boxed()),
+        None => None,
+    }
+}
+
+fn elements_strategy(elements: &[Schema]) -> Option<impl Strategy<Value = Vec<Value>>> {
+    elements.iter().map(strategy).collect::<Option<Vec<_>>>()
+}
+
+fn fields_strategy(
+    fields: &[NamedField],
+) -> Option<impl Strategy<Value = Vec<(Cow<'static, str>, Value)>>> {
+    fields
+        .iter()
+        .map(|field| {
+            let name = field.name.clone();
+            strategy(&field.schema)
+                .map(move |value| value.prop_map(move |value| (name.clone(), value)))
+        })
+        .collect::<Option<Vec<_>>>()
+}
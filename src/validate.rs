@@ -0,0 +1,456 @@
+//! Check dynamic values against a schema and its constraints.
+//!
+//! [`validate`] checks that a [`Value`] has the shape described by a schema, and
+//! that fields and newtypes satisfy their [`Constraints`]. This lets tools that
+//! edit data without the original Rust types, such as a level editor, reject
+//! values before they're saved:
+//!
+//! ```
+//! use schematic::{validate::Error, Constraints, NamedField, Number, Schema, Struct, TypeName, Value};
+//!
+//! let schema = Schema::Struct(Struct {
+//!     name: TypeName::new("Sound", "my_game"),
+//!     docs: None,
+//!     attributes: Default::default(),
+//!     fields: vec![NamedField {
+//!         constraints: Constraints {
+//!             min: Some(Number::Float(0.0)),
+//!             max: Some(Number::Float(1.0)),
+//!             ..Default::default()
+//!         },
+//!         ..NamedField::new("volume", Schema::F32)
+//!     }],
+//!     layout: None,
+//! });
+//!
+//! let value = Value::Struct {
+//!     name: "Sound".into(),
+//!     fields: vec![("volume".into(), Value::F32(1.5))],
+//! };
+//!
+//! assert_eq!(
+//!     Err(vec![Error::TooLarge {
+//!         path: "/volume".into(),
+//!         max: Number::Float(1.0),
+//!     }]),
+//!     schematic::validate::validate(&schema, &value),
+//! );
+//! ```
+//!
+//! Errors identify the offending value by its path, e.g. `/servers/0/port`, in the
+//! same form used by [`skeleton::Error`].
+//!
+//! [`validate`]: fn.validate.html
+//! [`Value`]: ../enum.Value.html
+//! [`Constraints`]: ../struct.Constraints.html
+//! [`skeleton::Error`]: ../skeleton/enum.Error.html
+
+use crate::{Constraints, NamedField, Number, Schema, Value, Variant, VariantValue};
+use regex::Regex;
+use std::{borrow::Cow, cmp::Ordering, collections::HashMap, convert::TryFrom, fmt, iter};
+
+/// Checks that `value` matches `schema` and satisfies all of its constraints.
+///
+/// # Errors
+///
+/// Returns every constraint that isn't satisfied. If part of the value doesn't
+/// match the shape of the schema, a single [`Error::Mismatch`] is reported for it
+/// and nothing within it is checked.
+///
+/// [`Error::Mismatch`]: enum.Error.html#variant.Mismatch
+pub fn validate(schema: &Schema, value: &Value) -> Result<(), Vec<Error>> {
+    let mut validator = Validator {
+        errors: Vec::new(),
+        patterns: HashMap::new(),
+    };
+    validator.value(schema, value, "");
+
+    if validator.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(validator.errors)
+    }
+}
+
+/// A value that doesn't satisfy a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The value doesn't match the shape of the schema.
+    Mismatch { path: String },
+
+    /// A number is less than the `min` constraint.
+    TooSmall { path: String, min: Number },
+
+    /// A number is greater than the `max` constraint.
+    TooLarge { path: String, max: Number },
+
+    /// A string or collection is shorter than the `min_len` constraint.
+    TooShort {
+        path: String,
+        min_len: usize,
+        len: usize,
+    },
+
+    /// A string or collection is longer than the `max_len` constraint.
+    TooLong {
+        path: String,
+        max_len: usize,
+        len: usize,
+    },
+
+    /// A string doesn't match the `pattern` constraint.
+    NoMatch { path: String, pattern: String },
+
+    /// The `pattern` constraint isn't a valid regular expression.
+    InvalidPattern {
+        path: String,
+        pattern: String,
+        message: String,
+    },
+}
+
+impl Error {
+    /// Returns the path to the value, e.g. `/servers/0/port`.
+    ///
+    /// Fields, variants and map entries with string keys are identified by name,
+    /// and elements by index. The root value is the empty string.
+    pub fn path(&self) -> &str {
+        match self {
+            Error::Mismatch { path }
+            | Error::TooSmall { path, .. }
+            | Error::TooLarge { path, .. }
+            | Error::TooShort { path, .. }
+            | Error::TooLong { path, .. }
+            | Error::NoMatch { path, .. }
+            | Error::InvalidPattern { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = match self.path() {
+            "" => "/",
+            path => path,
+        };
+
+        match self {
+            Error::Mismatch { .. } => write!(f, "value doesn't match the schema at `{}`", path),
+
+            Error::TooSmall { min, .. } => {
+                write!(f, "value at `{}` is less than the minimum of {}", path, min)
+            }
+
+            Error::TooLarge { max, .. } => {
+                write!(
+                    f,
+                    "value at `{}` is greater than the maximum of {}",
+                    path, max
+                )
+            }
+
+            Error::TooShort { min_len, len, .. } => write!(
+                f,
+                "value at `{}` has length {}, less than the minimum of {}",
+                path, len, min_len,
+            ),
+
+            Error::TooLong { max_len, len, .. } => write!(
+                f,
+                "value at `{}` has length {}, greater than the maximum of {}",
+                path, len, max_len,
+            ),
+
+            Error::NoMatch { pattern, .. } => {
+                write!(f, "value at `{}` doesn't match `{}`", path, pattern)
+            }
+
+            Error::InvalidPattern {
+                pattern, message, ..
+            } => write!(
+                f,
+                "invalid pattern `{}` for the value at `{}`: {}",
+                pattern, path, message,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+struct Validator<'a> {
+    errors: Vec<Error>,
+
+    /// Compiled patterns, or the error message for invalid ones.
+    patterns: HashMap<&'a str, Result<Regex, String>>,
+}
+
+impl<'a> Validator<'a> {
+    fn value(&mut self, schema: &'a Schema, value: &Value, path: &str) {
+        match (schema, value) {
+            (Schema::Unit, Value::Unit)
+            | (Schema::Bool, Value::Bool(_))
+            | (Schema::Char, Value::Char(_))
+            | (Schema::I8, Value::I8(_))
+            | (Schema::I16, Value::I16(_))
+            | (Schema::I32, Value::I32(_))
+            | (Schema::I64, Value::I64(_))
+            | (Schema::I128, Value::I128(_))
+            | (Schema::ISize, Value::ISize(_))
+            | (Schema::U8, Value::U8(_))
+            | (Schema::U16, Value::U16(_))
+            | (Schema::U32, Value::U32(_))
+            | (Schema::U64, Value::U64(_))
+            | (Schema::U128, Value::U128(_))
+            | (Schema::USize, Value::USize(_))
+            | (Schema::F32, Value::F32(_))
+            | (Schema::F64, Value::F64(_))
+            | (Schema::Str, Value::String(_))
+            | (Schema::String(_), Value::String(_))
+            | (Schema::Option(_), Value::Option(None))
+            | (Schema::UnitStruct(_), Value::UnitStruct { .. }) => {}
+
+            (Schema::Option(inner), Value::Option(Some(value))) => self.value(inner, value, path),
+
+            (Schema::Tuple(elements), Value::Tuple(values)) => self.list(elements, values, path),
+
+            (Schema::Array(array), Value::Array(values)) if values.len() == array.len => {
+                self.list(iter::repeat_n(&array.element, values.len()), values, path)
+            }
+
+            (Schema::Slice(element), Value::Seq(values)) => {
+                self.list(iter::repeat_n(&**element, values.len()), values, path)
+            }
+
+            (Schema::Seq(seq), Value::Seq(values))
+                if seq.len.is_none_or(|len| len == values.len()) =>
+            {
+                self.list(iter::repeat_n(&seq.element, values.len()), values, path)
+            }
+
+            (Schema::Map(map), Value::Map(entries)) => {
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    let path = match key {
+                        Value::String(key) => format!("{}/{}", path, key),
+                        _ => format!("{}/{}", path, index),
+                    };
+                    self.value(&map.key, key, &path);
+                    self.value(&map.value, value, &path);
+                }
+            }
+
+            (Schema::Struct(schema), Value::Struct { fields, .. }) => {
+                self.fields(&schema.fields, fields, path)
+            }
+
+            (Schema::TupleStruct(schema), Value::TupleStruct { elements, .. }) => {
+                self.list(&schema.elements, elements, path)
+            }
+
+            (Schema::NewtypeStruct(schema), Value::NewtypeStruct { inner, .. }) => {
+                self.value(&schema.inner, inner, path);
+                self.constraints(&schema.constraints, inner, path);
+            }
+
+            (
+                Schema::Enum(schema),
+                Value::Enum {
+                    variant_index,
+                    data,
+                    ..
+                },
+            ) => {
+                let variant = match schema.variants.get(*variant_index as usize) {
+                    Some(variant) => variant,
+                    None => return self.mismatch(path),
+                };
+                let path = format!("{}/{}", path, variant.name());
+
+                match (variant, data) {
+                    (Variant::Unit { .. }, VariantValue::Unit) => {}
+
+                    (Variant::Tuple { elements, .. }, VariantValue::Tuple(values)) => {
+                        self.list(elements, values, &path)
+                    }
+
+                    (Variant::Struct { fields, .. }, VariantValue::Struct(values)) => {
+                        self.fields(fields, values, &path)
+                    }
+
+                    _ => self.mismatch(&path),
+                }
+            }
+
+            _ => self.mismatch(path),
+        }
+    }
+
+    fn list<I>(&mut self, schemas: I, values: &[Value], path: &str)
+    where
+        I: IntoIterator<Item = &'a Schema>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let schemas = schemas.into_iter();
+        if schemas.len() != values.len() {
+            return self.mismatch(path);
+        }
+
+        for (index, (schema, value)) in schemas.zip(values).enumerate() {
+            self.value(schema, value, &format!("{}/{}", path, index));
+        }
+    }
+
+    fn fields(
+        &mut self,
+        fields: &'a [NamedField],
+        values: &[(Cow<'static, str>, Value)],
+        path: &str,
+    ) {
+        if fields.len() != values.len() {
+            return self.mismatch(path);
+        }
+
+        for (field, (name, value)) in fields.iter().zip(values) {
+            let path = format!("{}/{}", path, field.name);
+            if field.name != *name {
+                self.mismatch(&path);
+                continue;
+            }
+
+            self.value(&field.schema, value, &path);
+            self.constraints(&field.constraints, value, &path);
+        }
+    }
+
+    /// Checks `constraints` against `value`, which has already been checked against
+    /// its schema.
+    fn constraints(&mut self, constraints: &'a Constraints, value: &Value, path: &str) {
+        if constraints.is_empty() {
+            return;
+        }
+
+        // Constraints apply to the value within options and newtypes.
+        let mut value = value;
+        loop {
+            value = match value {
+                Value::Option(None) => return,
+                Value::Option(Some(inner)) | Value::NewtypeStruct { inner, .. } => inner,
+                _ => break,
+            };
+        }
+
+        if let Some(number) = number(value) {
+            if let Some(min) = constraints.min {
+                if matches!(compare(number, min), None | Some(Ordering::Less)) {
+                    self.errors.push(Error::TooSmall {
+                        path: path.into(),
+                        min,
+                    });
+                }
+            }
+
+            if let Some(max) = constraints.max {
+                if matches!(compare(number, max), None | Some(Ordering::Greater)) {
+                    self.errors.push(Error::TooLarge {
+                        path: path.into(),
+                        max,
+                    });
+                }
+            }
+        }
+
+        let len = match value {
+            Value::String(value) => Some(value.chars().count()),
+            Value::Array(elements) | Value::Seq(elements) => Some(elements.len()),
+            Value::Map(entries) => Some(entries.len()),
+            _ => None,
+        };
+
+        if let Some(len) = len {
+            if let Some(min_len) = constraints.min_len.filter(|&min_len| len < min_len) {
+                self.errors.push(Error::TooShort {
+                    path: path.into(),
+                    min_len,
+                    len,
+                });
+            }
+
+            if let Some(max_len) = constraints.max_len.filter(|&max_len| len > max_len) {
+                self.errors.push(Error::TooLong {
+                    path: path.into(),
+                    max_len,
+                    len,
+                });
+            }
+        }
+
+        if let (Some(pattern), Value::String(value)) = (&constraints.pattern, value) {
+            let error = match self
+                .patterns
+                .entry(pattern)
+                .or_insert_with(|| Regex::new(pattern).map_err(|error| error.to_string()))
+            {
+                Ok(regex) if regex.is_match(value) => None,
+
+                Ok(_) => Some(Error::NoMatch {
+                    path: path.into(),
+                    pattern: pattern.to_string(),
+                }),
+
+                Err(message) => Some(Error::InvalidPattern {
+                    path: path.into(),
+                    pattern: pattern.to_string(),
+                    message: message.clone(),
+                }),
+            };
+
+            self.errors.extend(error);
+        }
+    }
+
+    fn mismatch(&mut self, path: &str) {
+        self.errors.push(Error::Mismatch { path: path.into() });
+    }
+}
+
+/// Returns the value of integers and floats as a `Number`.
+fn number(value: &Value) -> Option<Number> {
+    let number = match *value {
+        Value::I8(value) => Number::Int(value.into()),
+        Value::I16(value) => Number::Int(value.into()),
+        Value::I32(value) => Number::Int(value.into()),
+        Value::I64(value) => Number::Int(value.into()),
+        Value::I128(value) => Number::Int(value),
+        Value::ISize(value) => Number::Int(value as i128),
+
+        Value::U8(value) => Number::Int(value.into()),
+        Value::U16(value) => Number::Int(value.into()),
+        Value::U32(value) => Number::Int(value.into()),
+        Value::U64(value) => Number::Int(value.into()),
+        Value::U128(value) => match i128::try_from(value) {
+            Ok(value) => Number::Int(value),
+            Err(_) => Number::Float(value as f64),
+        },
+        Value::USize(value) => Number::Int(value as i128),
+
+        Value::F32(value) => Number::Float(value.into()),
+        Value::F64(value) => Number::Float(value),
+
+        _ => return None,
+    };
+
+    Some(number)
+}
+
+/// Compares two numbers, returning `None` if either is NaN.
+fn compare(left: Number, right: Number) -> Option<Ordering> {
+    let float = |number| match number {
+        Number::Int(value) => value as f64,
+        Number::Float(value) => value,
+    };
+
+    match (left, right) {
+        (Number::Int(left), Number::Int(right)) => Some(left.cmp(&right)),
+        _ => float(left).partial_cmp(&float(right)),
+    }
+}
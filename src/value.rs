@@ -0,0 +1,234 @@
+use serde::ser::{
+    Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+    SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+use std::{borrow::Cow, collections::BTreeSet, sync::Mutex};
+
+/// A dynamically-typed value of a type described by a [`Schema`].
+///
+/// Values mirror the structure of the schema they belong to, including the names
+/// of user-defined types, so that serializing a value produces the same output as
+/// serializing an instance of the original Rust type. This makes it possible to
+/// produce data for a type without having the type itself, e.g. to test the
+/// deserializer of another service.
+///
+/// Fixed-length arrays are kept distinct from sequences, since Serde serializes
+/// them as tuples. Tuple variants with a single element are serialized as newtype
+/// variants, matching how Serde's derive handles variants like `Foo(T)`.
+///
+/// [`Schema`]: enum.Schema.html
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Unit,
+    Bool(bool),
+    Char(char),
+
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    ISize(isize),
+
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    USize(usize),
+
+    F32(f32),
+    F64(f64),
+
+    /// A value of either `Schema::Str` or `Schema::String`.
+    String(String),
+
+    Option(Option<Box<Value>>),
+    Tuple(Vec<Value>),
+    Array(Vec<Value>),
+
+    /// A value of either `Schema::Slice` or `Schema::Seq`.
+    Seq(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+
+    UnitStruct {
+        name: Cow<'static, str>,
+    },
+    Struct {
+        name: Cow<'static, str>,
+        fields: Vec<(Cow<'static, str>, Value)>,
+    },
+    TupleStruct {
+        name: Cow<'static, str>,
+        elements: Vec<Value>,
+    },
+    NewtypeStruct {
+        name: Cow<'static, str>,
+        inner: Box<Value>,
+    },
+
+    Enum {
+        name: Cow<'static, str>,
+
+        /// The position of the variant in the enum's list of variants.
+        variant_index: u32,
+        variant: Cow<'static, str>,
+        data: VariantValue,
+    },
+}
+
+/// The data of an enum variant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariantValue {
+    Unit,
+    Tuple(Vec<Value>),
+    Struct(Vec<(Cow<'static, str>, Value)>),
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Unit => serializer.serialize_unit(),
+            Value::Bool(value) => serializer.serialize_bool(*value),
+            Value::Char(value) => serializer.serialize_char(*value),
+
+            Value::I8(value) => serializer.serialize_i8(*value),
+            Value::I16(value) => serializer.serialize_i16(*value),
+            Value::I32(value) => serializer.serialize_i32(*value),
+            Value::I64(value) => serializer.serialize_i64(*value),
+            Value::I128(value) => serializer.serialize_i128(*value),
+            Value::ISize(value) => value.serialize(serializer),
+
+            Value::U8(value) => serializer.serialize_u8(*value),
+            Value::U16(value) => serializer.serialize_u16(*value),
+            Value::U32(value) => serializer.serialize_u32(*value),
+            Value::U64(value) => serializer.serialize_u64(*value),
+            Value::U128(value) => serializer.serialize_u128(*value),
+            Value::USize(value) => value.serialize(serializer),
+
+            Value::F32(value) => serializer.serialize_f32(*value),
+            Value::F64(value) => serializer.serialize_f64(*value),
+
+            Value::String(value) => serializer.serialize_str(value),
+
+            Value::Option(None) => serializer.serialize_none(),
+            Value::Option(Some(value)) => serializer.serialize_some(&**value),
+
+            Value::Tuple(elements) | Value::Array(elements) => {
+                let mut serializer = serializer.serialize_tuple(elements.len())?;
+                for element in elements {
+                    serializer.serialize_element(element)?;
+                }
+                serializer.end()
+            }
+
+            Value::Seq(elements) => {
+                let mut serializer = serializer.serialize_seq(Some(elements.len()))?;
+                for element in elements {
+                    serializer.serialize_element(element)?;
+                }
+                serializer.end()
+            }
+
+            Value::Map(entries) => {
+                let mut serializer = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    serializer.serialize_entry(key, value)?;
+                }
+                serializer.end()
+            }
+
+            Value::UnitStruct { name } => serializer.serialize_unit_struct(static_str(name)),
+
+            Value::Struct { name, fields } => {
+                let mut serializer = serializer.serialize_struct(static_str(name), fields.len())?;
+                for (name, value) in fields {
+                    serializer.serialize_field(static_str(name), value)?;
+                }
+                serializer.end()
+            }
+
+            Value::TupleStruct { name, elements } => {
+                let mut serializer =
+                    serializer.serialize_tuple_struct(static_str(name), elements.len())?;
+                for element in elements {
+                    serializer.serialize_field(element)?;
+                }
+                serializer.end()
+            }
+
+            Value::NewtypeStruct { name, inner } => {
+                serializer.serialize_newtype_struct(static_str(name), &**inner)
+            }
+
+            Value::Enum {
+                name,
+                variant_index,
+                variant,
+                data,
+            } => {
+                let name = static_str(name);
+                let variant = static_str(variant);
+
+                match data {
+                    VariantValue::Unit => {
+                        serializer.serialize_unit_variant(name, *variant_index, variant)
+                    }
+
+                    VariantValue::Tuple(elements) if elements.len() == 1 => serializer
+                        .serialize_newtype_variant(name, *variant_index, variant, &elements[0]),
+
+                    VariantValue::Tuple(elements) => {
+                        let mut serializer = serializer.serialize_tuple_variant(
+                            name,
+                            *variant_index,
+                            variant,
+                            elements.len(),
+                        )?;
+                        for element in elements {
+                            serializer.serialize_field(element)?;
+                        }
+                        serializer.end()
+                    }
+
+                    VariantValue::Struct(fields) => {
+                        let mut serializer = serializer.serialize_struct_variant(
+                            name,
+                            *variant_index,
+                            variant,
+                            fields.len(),
+                        )?;
+                        for (name, value) in fields {
+                            serializer.serialize_field(static_str(name), value)?;
+                        }
+                        serializer.end()
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns `name` with the `'static` lifetime required by Serde.
+///
+/// Names that come from Rust types are already `'static`. Names built at runtime,
+/// e.g. by importing a schema, are leaked, but only once per distinct name.
+#[allow(clippy::ptr_arg)] // Borrowed names are already `'static`.
+fn static_str(name: &Cow<'static, str>) -> &'static str {
+    static NAMES: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+    match name {
+        Cow::Borrowed(name) => name,
+        Cow::Owned(name) => {
+            let mut names = NAMES.lock().unwrap_or_else(|error| error.into_inner());
+            match names.get(name.as_str()) {
+                Some(name) => name,
+                None => {
+                    let name: &'static str = Box::leak(name.clone().into_boxed_str());
+                    names.insert(name);
+                    name
+                }
+            }
+        }
+    }
+}
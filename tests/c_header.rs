@@ -0,0 +1,243 @@
+#![cfg(feature = "std")]
+
+use pretty_assertions::assert_eq;
+use schematic::*;
+
+#[repr(C)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Describe for Point {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Point)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<f32>("x")?;
+        describer.describe_field::<f32>("y")?;
+        describer.describe_layout(schematic::layout!(Point, x, y))?;
+        describer.end()
+    }
+}
+
+#[repr(C)]
+pub struct Polygon {
+    pub id: u8,
+    pub points: [Point; 3],
+}
+
+impl Describe for Polygon {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Polygon)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<u8>("id")?;
+        describer.describe_field::<[Point; 3]>("points")?;
+        describer.describe_layout(schematic::layout!(Polygon, id, points))?;
+        describer.end()
+    }
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+pub enum Shape {
+    Empty,
+    Circle(f32),
+    Rect { width: f32, height: f32 },
+}
+
+impl Describe for Shape {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Shape)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_enum(Self::type_name())?;
+        describer.describe_unit_variant("Empty", None)?;
+
+        let mut variant = describer.start_tuple_variant("Circle")?;
+        variant.describe_element::<f32>()?;
+        describer.end_tuple_variant(variant)?;
+
+        let mut variant = describer.start_struct_variant("Rect")?;
+        variant.describe_field::<f32>("width")?;
+        variant.describe_field::<f32>("height")?;
+        describer.end_struct_variant(variant)?;
+
+        describer.describe_layout(schematic::layout!(Shape))?;
+        describer.end()
+    }
+}
+
+#[allow(dead_code)]
+#[repr(u8)]
+pub enum Packet {
+    Ping,
+    Data(u8, u32),
+}
+
+impl Describe for Packet {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Packet)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_enum(Self::type_name())?;
+        describer.describe_repr(Primitive::U8)?;
+        describer.describe_unit_variant("Ping", None)?;
+
+        let mut variant = describer.start_tuple_variant("Data")?;
+        variant.describe_element::<u8>()?;
+        variant.describe_element::<u32>()?;
+        describer.end_tuple_variant(variant)?;
+
+        describer.describe_layout(schematic::layout!(Packet))?;
+        describer.end()
+    }
+}
+
+#[test]
+fn capture_layout() {
+    let schema = schematic::describe::<Polygon>();
+    let layout = schema.as_struct().unwrap().layout.as_ref().unwrap();
+
+    assert_eq!(28, layout.size);
+    assert_eq!(4, layout.align);
+    assert_eq!(vec![0, 4], layout.field_offsets);
+}
+
+#[test]
+fn struct_header() {
+    let header = c_header::generate(&schematic::describe::<Polygon>()).unwrap();
+
+    let expected = r#"#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+typedef struct Point {
+    float x;
+    float y;
+} Point;
+
+_Static_assert(sizeof(Point) == 8, "size of Point");
+_Static_assert(_Alignof(Point) == 4, "alignment of Point");
+_Static_assert(offsetof(Point, x) == 0, "offset of Point.x");
+_Static_assert(offsetof(Point, y) == 4, "offset of Point.y");
+
+typedef struct Polygon {
+    uint8_t id;
+    Point points[3];
+} Polygon;
+
+_Static_assert(sizeof(Polygon) == 28, "size of Polygon");
+_Static_assert(_Alignof(Polygon) == 4, "alignment of Polygon");
+_Static_assert(offsetof(Polygon, id) == 0, "offset of Polygon.id");
+_Static_assert(offsetof(Polygon, points) == 4, "offset of Polygon.points");
+"#;
+
+    assert_eq!(expected, header);
+}
+
+#[test]
+fn tagged_union_header() {
+    let header = c_header::generate(&schematic::describe::<Shape>()).unwrap();
+
+    let expected = r#"#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+typedef enum Shape_Tag {
+    Shape_Empty,
+    Shape_Circle,
+    Shape_Rect,
+} Shape_Tag;
+
+typedef struct Shape_Circle_Body {
+    float _0;
+} Shape_Circle_Body;
+
+typedef struct Shape_Rect_Body {
+    float width;
+    float height;
+} Shape_Rect_Body;
+
+typedef union Shape_Body {
+    Shape_Circle_Body Circle;
+    Shape_Rect_Body Rect;
+} Shape_Body;
+
+typedef struct Shape {
+    Shape_Tag tag;
+    Shape_Body body;
+} Shape;
+
+_Static_assert(sizeof(Shape) == 12, "size of Shape");
+_Static_assert(_Alignof(Shape) == 4, "alignment of Shape");
+"#;
+
+    assert_eq!(expected, header);
+}
+
+#[test]
+fn reject_ambiguous_repr() {
+    // `#[repr(u8)]` and `#[repr(C, u8)]` both describe themselves as `u8`, but lay
+    // out their variants differently.
+    assert_eq!(
+        Err(c_header::Error::AmbiguousRepr(type_name!(Packet))),
+        c_header::generate(&schematic::describe::<Packet>()),
+    );
+}
+
+#[test]
+fn reject_unsupported() {
+    let schema = Schema::Struct(Struct {
+        name: type_name!(Named),
+        docs: None,
+        attributes: Attributes::new(),
+        fields: vec![NamedField::new("name", schematic::describe::<String>())],
+        layout: None,
+    });
+
+    assert_eq!(
+        Err(c_header::Error::Unsupported(Box::new(
+            schematic::describe::<String>()
+        ))),
+        c_header::generate(&schema),
+    );
+}
+
+#[test]
+fn reject_mismatched_layout() {
+    let layout = Layout {
+        size: 4,
+        align: 4,
+        field_offsets: vec![0],
+    };
+
+    // A struct without fields can't have field offsets either.
+    let schema = Schema::Struct(Struct {
+        name: type_name!(Empty),
+        docs: None,
+        attributes: Attributes::new(),
+        fields: Vec::new(),
+        layout: Some(layout.clone()),
+    });
+    assert_eq!(
+        Err(c_header::Error::InvalidLayout(type_name!(Empty))),
+        c_header::generate(&schema),
+    );
+
+    let mut schema = schematic::describe::<Shape>();
+    if let Schema::Enum(schema) = &mut schema {
+        schema.layout = Some(layout);
+    }
+    assert_eq!(
+        Err(c_header::Error::InvalidLayout(type_name!(Shape))),
+        c_header::generate(&schema),
+    );
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "std")]
+
+use pretty_assertions::assert_eq;
+use schematic::{cache::SchemaCache, *};
+use std::{collections::HashMap, sync::Arc, thread};
+
+pub struct Message {
+    pub id: u64,
+    pub tags: HashMap<String, Vec<String>>,
+}
+
+impl Describe for Message {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Message)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<u64>("id")?;
+        describer.describe_field::<HashMap<String, Vec<String>>>("tags")?;
+        describer.end()
+    }
+}
+
+#[test]
+fn cache_shares_schemas() {
+    let cache = SchemaCache::new();
+    assert!(cache.is_empty());
+    assert!(!cache.contains::<Message>());
+
+    let first = cache.get::<Message>();
+    let second = cache.get::<Message>();
+    assert_eq!(schematic::describe::<Message>(), *first);
+    assert!(Arc::ptr_eq(&first, &second));
+    assert!(cache.contains::<Message>());
+
+    // Each type gets its own entry.
+    assert_eq!(Schema::U64, *cache.get::<u64>());
+    assert_eq!(2, cache.len());
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(schematic::describe::<Message>(), *first);
+    assert!(!Arc::ptr_eq(&first, &cache.get::<Message>()));
+}
+
+#[test]
+fn cache_across_threads() {
+    static CACHE: SchemaCache = SchemaCache::new();
+
+    let schemas = (0..4)
+        .map(|_| thread::spawn(|| CACHE.get::<Message>()))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(1, CACHE.len());
+    assert!(schemas
+        .iter()
+        .all(|schema| Arc::ptr_eq(schema, &CACHE.get::<Message>())));
+}
+
+#[test]
+fn describe_cached_matches_describe() {
+    let schema = schematic::describe_cached::<Message>();
+    assert_eq!(schematic::describe::<Message>(), *schema);
+    assert!(Arc::ptr_eq(
+        &schema,
+        &schematic::describe_cached::<Message>()
+    ));
+}
@@ -0,0 +1,166 @@
+#![cfg(feature = "cli")]
+
+use pretty_assertions::assert_eq;
+use schematic::{doc, Attributes, NamedField, Schema, Struct, TypeName};
+use std::{fs, path::PathBuf, process::Command};
+
+fn schema() -> Schema {
+    Schema::Struct(Struct {
+        name: TypeName::new("Config", "cli"),
+        docs: Some(" Settings for the server.".into()),
+        attributes: Attributes::new(),
+        fields: vec![
+            NamedField::new("port", Schema::U16),
+            NamedField::new("tags", schematic::describe::<Vec<String>>()),
+        ],
+        layout: None,
+    })
+}
+
+/// Writes the schema JSON to a fresh directory for the test.
+fn setup(test: &str) -> (PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("schematic-cli-{}", test));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let input = dir.join("config.json");
+    fs::write(&input, serde_json::to_string(&schema()).unwrap()).unwrap();
+    (dir, input)
+}
+
+fn schematic() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_schematic"))
+}
+
+#[test]
+fn convert_to_stdout() {
+    let (_, input) = setup("stdout");
+    let output = schematic()
+        .args(["convert", "--format", "markdown"])
+        .arg(&input)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        doc::generate(&schema(), doc::Format::Markdown),
+        String::from_utf8(output.stdout).unwrap(),
+    );
+}
+
+#[test]
+fn convert_to_out_dir() {
+    let (dir, input) = setup("out-dir");
+    let out_dir = dir.join("generated");
+    let status = schematic()
+        .args([
+            "convert",
+            "-f",
+            "rust",
+            "-f",
+            "toml",
+            "-f",
+            "json-schema",
+            "--out-dir",
+        ])
+        .arg(&out_dir)
+        .arg(&input)
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+    assert_eq!(
+        schematic::rust::generate(&schema()).unwrap(),
+        fs::read_to_string(out_dir.join("config.rs")).unwrap(),
+    );
+    assert_eq!(
+        "# Settings for the server.\n\nport = 0\ntags = []\n",
+        fs::read_to_string(out_dir.join("config.toml")).unwrap(),
+    );
+    assert_eq!(
+        schema().to_json_schema().unwrap(),
+        serde_json::from_str::<serde_json::Value>(
+            &fs::read_to_string(out_dir.join("config.schema.json")).unwrap()
+        )
+        .unwrap(),
+    );
+
+    // Dots in the name of the input are kept in the names of the outputs.
+    let versioned = dir.join("config.v2.json");
+    fs::copy(&input, &versioned).unwrap();
+    let status = schematic()
+        .args(["convert", "-f", "rust", "--out-dir"])
+        .arg(&out_dir)
+        .arg(&versioned)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert!(out_dir.join("config.v2.rs").is_file());
+
+    // Multiple formats can't all be written to stdout.
+    let output = schematic()
+        .args(["convert", "-f", "rust", "-f", "toml"])
+        .arg(&input)
+        .output()
+        .unwrap();
+    assert_eq!(Some(2), output.status.code());
+}
+
+#[test]
+fn report_uninhabited_types() {
+    let (dir, _) = setup("uninhabited");
+    let input = dir.join("param.json");
+    fs::write(
+        &input,
+        serde_json::to_string(&Schema::TypeParam("T".into())).unwrap(),
+    )
+    .unwrap();
+
+    let output = schematic()
+        .args(["convert", "--format", "yaml"])
+        .arg(&input)
+        .output()
+        .unwrap();
+    assert_eq!(Some(2), output.status.code());
+    assert_eq!(
+        "error: type `T` has no values at `/`\n",
+        String::from_utf8(output.stderr).unwrap(),
+    );
+}
+
+#[test]
+fn check_snapshot() {
+    let (dir, input) = setup("check");
+    let snapshot = dir.join("config.yaml");
+
+    fs::write(&snapshot, "# Settings for the server.\nport: 0\ntags: []\n").unwrap();
+    let status = schematic()
+        .args(["check", "--format", "yaml"])
+        .arg(&input)
+        .arg(&snapshot)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    fs::write(
+        &snapshot,
+        "# Settings for the server.\nport: 0\nlabels: []\n",
+    )
+    .unwrap();
+    let output = schematic()
+        .args(["diff", "--format", "yaml"])
+        .arg(&input)
+        .arg(&snapshot)
+        .output()
+        .unwrap();
+    assert_eq!(Some(1), output.status.code());
+
+    assert_eq!(
+        format!(
+            "--- {}\n+++ {}\n@@\n # Settings for the server.\n port: 0\n-labels: []\n+tags: []\n",
+            snapshot.display(),
+            input.display(),
+        ),
+        String::from_utf8(output.stdout).unwrap(),
+    );
+}
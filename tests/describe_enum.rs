@@ -26,17 +26,23 @@ fn describe_simple_enum() {
 
     let expected = Schema::Enum(Enum {
         name: type_name!(Simple),
+        docs: None,
         repr: None,
         variants: vec![
             Variant::Unit {
                 name: "Foo".into(),
+                docs: None,
+                attributes: Attributes::new(),
                 discriminant: None,
             },
             Variant::Unit {
                 name: "Bar".into(),
+                docs: None,
+                attributes: Attributes::new(),
                 discriminant: None,
             },
         ],
+        layout: None,
     });
 
     assert_eq!(expected, actual);
@@ -83,25 +89,96 @@ fn describe_data_enum() {
 
     let expected = Schema::Enum(Enum {
         name: type_name!(WithData),
+        docs: None,
         repr: None,
         variants: vec![
             Variant::Unit {
                 name: "Foo".into(),
+                docs: None,
+                attributes: Attributes::new(),
                 discriminant: None,
             },
             Variant::Tuple {
                 name: "Bar".into(),
+                docs: None,
+                attributes: Attributes::new(),
                 elements: vec![Schema::USize, Schema::U32],
             },
             Variant::Struct {
                 name: "Baz".into(),
+                docs: None,
+                attributes: Attributes::new(),
                 fields: vec![
-                    ("first".into(), Schema::Bool),
-                    ("second".into(), Schema::I8),
+                    NamedField::new("first", Schema::Bool),
+                    NamedField::new("second", Schema::I8),
                 ],
             },
         ],
+        layout: None,
     });
 
     assert_eq!(expected, actual);
 }
+
+#[allow(dead_code)]
+enum Shape {
+    Circle { radius: f32 },
+    Point,
+}
+
+impl Describe for Shape {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Shape)
+    }
+
+    fn describe<D: Describer>(describer: D) -> std::result::Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_enum(Self::type_name())?;
+
+        {
+            let info = VariantInfo {
+                attributes: schematic::attributes! { "editor.label" => "Circle (2D)" },
+                ..Default::default()
+            };
+            let mut variant_describer = describer.start_struct_variant_with("Circle", info)?;
+            variant_describer.describe_field_with::<f32>(
+                "radius",
+                FieldInfo {
+                    attributes: schematic::attributes! { "editor.widget" => "slider" },
+                    ..Default::default()
+                },
+            )?;
+            describer.end_struct_variant(variant_describer)?;
+        }
+
+        let info = VariantInfo {
+            attributes: schematic::attributes! { "editor.hidden" => true },
+            ..Default::default()
+        };
+        describer.describe_unit_variant_with("Point", None, info)?;
+
+        describer.end()
+    }
+}
+
+#[test]
+fn describe_variant_attributes() {
+    let actual = schematic::describe::<Shape>();
+    let variants = &actual.as_enum().unwrap().variants;
+
+    assert_eq!(
+        &schematic::attributes! { "editor.label" => "Circle (2D)" },
+        variants[0].attributes(),
+    );
+    assert_eq!(
+        Some(&Attribute::Bool(true)),
+        variants[1].attributes().get("editor.hidden"),
+    );
+
+    match &variants[0] {
+        Variant::Struct { fields, .. } => assert_eq!(
+            &schematic::attributes! { "editor.widget" => "slider" },
+            &fields[0].attributes,
+        ),
+        _ => panic!("expected a struct variant"),
+    }
+}
@@ -0,0 +1,135 @@
+use pretty_assertions::assert_eq;
+use schematic::*;
+use std::collections::BTreeMap;
+
+/// A type that can't be represented in a schema.
+pub struct Handle;
+
+impl Describe for Handle {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Handle)
+    }
+
+    fn describe<D: Describer>(_: D) -> Result<D::Ok, D::Error> {
+        Err(D::Error::custom("handles can't be described"))
+    }
+}
+
+pub struct Window {
+    pub title: String,
+    pub children: Vec<Widget>,
+}
+
+impl Describe for Window {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Window)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<String>("title")?;
+        describer.describe_field::<Vec<Widget>>("children")?;
+        describer.end()
+    }
+}
+
+pub enum Widget {
+    Label(String),
+    Canvas { size: (u32, u32), handle: Handle },
+}
+
+impl Describe for Widget {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Widget)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_enum(Self::type_name())?;
+
+        let mut variant = describer.start_tuple_variant("Label")?;
+        variant.describe_element::<String>()?;
+        describer.end_tuple_variant(variant)?;
+
+        let mut variant = describer.start_struct_variant("Canvas")?;
+        variant.describe_field::<(u32, u32)>("size")?;
+        variant.describe_field::<Handle>("handle")?;
+        describer.end_struct_variant(variant)?;
+
+        describer.end()
+    }
+}
+
+#[test]
+fn custom_error_path() {
+    assert_eq!(
+        SchemaError {
+            path: "/children/*/Canvas/handle".into(),
+            message: "handles can't be described".into(),
+        },
+        schematic::try_describe::<Window>().unwrap_err()
+    );
+
+    // Tuple elements are identified by index, and options add nothing to the path.
+    let error = schematic::try_describe::<(u8, Option<Handle>)>().unwrap_err();
+    assert_eq!("handles can't be described at `/1`", error.to_string());
+
+    let error = schematic::try_describe::<Handle>().unwrap_err();
+    assert_eq!("handles can't be described", error.to_string());
+}
+
+#[test]
+fn map_error_paths() {
+    let error = schematic::try_describe::<BTreeMap<Handle, u32>>().unwrap_err();
+    assert_eq!("/<key>", error.path);
+
+    let error = schematic::try_describe::<BTreeMap<String, Vec<Handle>>>().unwrap_err();
+    assert_eq!("/<value>/*", error.path);
+}
+
+#[test]
+#[should_panic(
+    expected = "failed to describe `Window`: handles can't be described at `/children/*/Canvas/handle`"
+)]
+fn describe_panics_on_error() {
+    schematic::describe::<Window>();
+}
+
+pub struct Index {
+    pub by_id: BTreeMap<u32, Vec<String>>,
+    pub by_name: Vec<(u8, BTreeMap<String, u32>)>,
+}
+
+impl Describe for Index {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Index)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<BTreeMap<u32, Vec<String>>>("by_id")?;
+        describer.describe_field::<Vec<(u8, BTreeMap<String, u32>)>>("by_name")?;
+        describer.end()
+    }
+}
+
+fn integer_keys(schema: &Schema) -> Result<(), String> {
+    match schema {
+        Schema::Map(map) if map.key != Schema::U32 => {
+            Err(format!("expected `u32` keys, found `{}`", map.key))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[test]
+fn check_rejects_nested_schema() {
+    let mut describer = SchemaDescriber::with_check(integer_keys);
+    let error = Index::describe(&mut describer).unwrap_err();
+    assert_eq!(
+        "expected `u32` keys, found `String` at `/by_name/*/1`",
+        error.to_string()
+    );
+
+    // Without a check, every type is accepted.
+    assert!(Index::describe(&mut SchemaDescriber::new()).is_ok());
+}
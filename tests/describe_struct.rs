@@ -25,16 +25,19 @@ fn describe_struct() {
 
     let expected = Schema::Struct(Struct {
         name: schematic::type_name!(ManualStruct),
+        docs: None,
+        attributes: Attributes::new(),
         fields: vec![
-            ("field".into(), Schema::Bool),
-            ("another".into(), Schema::U32),
+            NamedField::new("field", Schema::Bool),
+            NamedField::new("another", Schema::U32),
         ],
+        layout: None,
     });
 
     assert_eq!(expected, actual);
 }
 
-pub struct ManualTupleStruct(bool, u32);
+pub struct ManualTupleStruct(pub bool, pub u32);
 
 impl Describe for ManualTupleStruct {
     fn type_name() -> TypeName {
@@ -63,7 +66,9 @@ fn describe_tuple_struct() {
 
     let expected = Schema::TupleStruct(TupleStruct {
         name: schematic::type_name!(ManualTupleStruct),
+        docs: None,
         elements: vec![Schema::Bool, Schema::U32],
+        layout: None,
     });
 
     assert_eq!(expected, actual);
@@ -93,26 +98,235 @@ fn test_nested_struct() {
 
     let expected = Schema::Struct(Struct {
         name: schematic::type_name!(NestedStruct),
+        docs: None,
+        attributes: Attributes::new(),
         fields: vec![
-            (
-                "manual_struct".into(),
+            NamedField::new(
+                "manual_struct",
                 Schema::Struct(Struct {
                     name: schematic::type_name!(ManualStruct),
+                    docs: None,
+                    attributes: Attributes::new(),
                     fields: vec![
-                        ("field".into(), Schema::Bool),
-                        ("another".into(), Schema::U32),
+                        NamedField::new("field", Schema::Bool),
+                        NamedField::new("another", Schema::U32),
                     ],
+                    layout: None,
                 }),
             ),
-            (
-                "tuple_struct".into(),
+            NamedField::new(
+                "tuple_struct",
                 Schema::TupleStruct(TupleStruct {
                     name: schematic::type_name!(ManualTupleStruct),
+                    docs: None,
                     elements: vec![Schema::Bool, Schema::U32],
+                    layout: None,
                 }),
             ),
         ],
+        layout: None,
     });
 
     assert_eq!(expected, actual);
 }
+
+/// A struct with documentation.
+pub struct DocumentedStruct {
+    /// The only field.
+    pub field: bool,
+}
+
+impl Describe for DocumentedStruct {
+    fn type_name() -> TypeName {
+        schematic::type_name!(DocumentedStruct)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct_with(
+            Self::type_name(),
+            TypeInfo {
+                docs: Some(" A struct with documentation."),
+                ..Default::default()
+            },
+        )?;
+        describer.describe_field_with::<bool>(
+            "field",
+            FieldInfo {
+                docs: Some(" The only field."),
+                ..Default::default()
+            },
+        )?;
+        describer.end()
+    }
+}
+
+#[test]
+fn describe_documented_struct() {
+    let actual = schematic::describe::<DocumentedStruct>();
+
+    let expected = Schema::Struct(Struct {
+        name: schematic::type_name!(DocumentedStruct),
+        docs: Some(" A struct with documentation.".into()),
+        attributes: Attributes::new(),
+        fields: vec![NamedField {
+            name: "field".into(),
+            docs: Some(" The only field.".into()),
+            attributes: Attributes::new(),
+            schema: Schema::Bool,
+            constraints: Constraints::default(),
+            default: None,
+            skip_serializing_if: false,
+        }],
+        layout: None,
+    });
+
+    assert_eq!(expected, actual);
+}
+
+/// A struct with hints for an editor.
+pub struct Light {
+    pub color: [u8; 3],
+}
+
+impl Describe for Light {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Light)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct_with(
+            Self::type_name(),
+            TypeInfo {
+                attributes: schematic::attributes! { "editor.icon" => "bulb" },
+                ..Default::default()
+            },
+        )?;
+        describer.describe_field_with::<[u8; 3]>(
+            "color",
+            FieldInfo {
+                attributes: schematic::attributes! {
+                    "editor.widget" => "color",
+                    "editor.order" => 1,
+                },
+                ..Default::default()
+            },
+        )?;
+        describer.end()
+    }
+}
+
+#[test]
+fn describe_struct_attributes() {
+    let actual = schematic::describe::<Light>();
+
+    let light = actual.as_struct().unwrap();
+    assert_eq!(
+        Some(&Attribute::String("bulb".into())),
+        light.attributes.get("editor.icon"),
+    );
+    assert_eq!(
+        Some(&Attribute::Number(Number::Int(1))),
+        light.fields[0].attributes.get("editor.order"),
+    );
+
+    // Attributes are only serialized where they're set.
+    let json = serde_json::to_value(&actual).unwrap();
+    assert_eq!(
+        serde_json::json!({ "editor.icon": { "String": "bulb" } }),
+        json["Struct"]["attributes"],
+    );
+    assert_eq!(
+        None,
+        json["Struct"]["fields"][0]["schema"]["Array"].get("attributes")
+    );
+    assert_eq!(actual, serde_json::from_value(json).unwrap());
+}
+
+pub struct Retry {
+    pub attempts: u8,
+    pub backoff: Option<u32>,
+    pub policy: Policy,
+}
+
+pub enum Policy {
+    Fixed { delay: u32, jitter: Option<u32> },
+}
+
+impl Describe for Retry {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Retry)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field_with::<u8>(
+            "attempts",
+            FieldInfo {
+                default: Some(FieldDefault::of(&3u8)),
+                ..Default::default()
+            },
+        )?;
+        describer.describe_field::<Option<u32>>("backoff")?;
+        describer.describe_field::<Policy>("policy")?;
+        describer.end()
+    }
+}
+
+impl Describe for Policy {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Policy)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_enum(Self::type_name())?;
+        let mut variant = describer.start_struct_variant("Fixed")?;
+        variant.describe_field::<u32>("delay")?;
+        variant.describe_field_with::<Option<u32>>(
+            "jitter",
+            FieldInfo {
+                default: Some(FieldDefault::of_default::<Option<u32>>()),
+                skip_serializing_if: true,
+                ..Default::default()
+            },
+        )?;
+        describer.end_struct_variant(variant)?;
+        describer.end()
+    }
+}
+
+#[test]
+fn describe_field_presence() {
+    let actual = schematic::describe::<Retry>();
+    let retry = actual.as_struct().unwrap();
+
+    // Presence doesn't depend on whether the field is an `Option`.
+    fn required(fields: &[NamedField]) -> Vec<&str> {
+        fields
+            .iter()
+            .filter(|field| field.is_required())
+            .map(|field| field.name.as_ref())
+            .collect()
+    }
+    assert_eq!(vec!["backoff", "policy"], required(&retry.fields));
+    assert!(retry.fields.iter().all(NamedField::is_always_serialized));
+
+    let policy = retry.fields[2].schema.as_enum().unwrap();
+    let fields = match &policy.variants[0] {
+        Variant::Struct { fields, .. } => fields,
+        _ => panic!("expected a struct variant"),
+    };
+    assert_eq!(vec!["delay"], required(fields));
+    assert_eq!(
+        Some(FieldDefault::Value(serde_json::Value::Null)),
+        fields[1].default
+    );
+    assert!(!fields[1].is_always_serialized());
+
+    // Presence is only serialized for fields that aren't required.
+    let json = serde_json::to_value(&actual).unwrap();
+    let fields = &json["Struct"]["fields"];
+    assert_eq!(serde_json::json!({ "Value": 3 }), fields[0]["default"]);
+    assert_eq!(None, fields[1].get("default"));
+    assert_eq!(None, fields[1].get("skip_serializing_if"));
+    assert_eq!(actual, serde_json::from_value(json).unwrap());
+}
@@ -0,0 +1,152 @@
+use pretty_assertions::assert_eq;
+use schematic::*;
+use std::collections::BTreeMap;
+
+pub struct Config {
+    pub name: String,
+    pub servers: Vec<Server>,
+    pub limits: BTreeMap<String, Limit>,
+}
+
+impl Describe for Config {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Config)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<String>("name")?;
+        describer.describe_field::<Vec<Server>>("servers")?;
+        describer.describe_field::<BTreeMap<String, Limit>>("limits")?;
+        describer.end()
+    }
+}
+
+pub struct Server {
+    pub address: ([u8; 4], u16),
+    pub backup: Option<Port>,
+}
+
+impl Describe for Server {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Server)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<([u8; 4], u16)>("address")?;
+        describer.describe_field::<Option<Port>>("backup")?;
+        describer.end()
+    }
+}
+
+pub struct Port(pub u16);
+
+impl Describe for Port {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Port)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        describer.describe_newtype_struct::<u16>(Self::type_name())
+    }
+}
+
+pub struct Limit(pub u32);
+
+impl Describe for Limit {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Limit)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_tuple_struct(Self::type_name())?;
+        describer.describe_element::<u32>()?;
+        describer.end()
+    }
+}
+
+#[test]
+fn display_definition() {
+    let schema = schematic::describe::<Config>();
+    assert_eq!(
+        "struct Config { name: String, servers: Vec<Server>, limits: BTreeMap<String, Limit> }",
+        schema.to_string(),
+    );
+
+    // Types that aren't user-defined render as a reference.
+    let schema = schematic::describe::<Vec<Option<Limit>>>();
+    assert_eq!("Vec<Option<Limit>>", schema.to_string());
+}
+
+#[test]
+fn display_expanded() {
+    let schema = schematic::describe::<Config>();
+    assert_eq!(
+        "struct Config { \
+         name: String, \
+         servers: Vec<struct Server { address: ([u8; 4], u16), backup: Option<struct Port(u16)> }>, \
+         limits: BTreeMap<String, struct Limit(u32,)> \
+         }",
+        format!("{:#}", schema),
+    );
+}
+
+#[test]
+fn display_enum() {
+    let schema = Schema::Enum(Enum {
+        name: TypeName::generic("Message", "display", vec![TypeName::new("Port", "display")]),
+        docs: None,
+        repr: None,
+        variants: vec![
+            Variant::Unit {
+                name: "Ping".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                discriminant: None,
+            },
+            Variant::Tuple {
+                name: "Connect".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                elements: vec![schematic::describe::<Port>()],
+            },
+            Variant::Struct {
+                name: "Move".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                fields: vec![NamedField::new("to", Schema::Tuple(vec![Schema::I32]))],
+            },
+        ],
+        layout: None,
+    });
+    assert_eq!(
+        "enum Message<Port> { Ping, Connect(Port), Move { to: (i32,) } }",
+        schema.to_string(),
+    );
+
+    let schema = Schema::Enum(Enum {
+        name: TypeName::new("Level", "display"),
+        docs: None,
+        repr: Some(Primitive::U8),
+        variants: vec![
+            Variant::Unit {
+                name: "Low".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                discriminant: Some(PrimitiveValue::U8(1)),
+            },
+            Variant::Unit {
+                name: "High".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                discriminant: Some(PrimitiveValue::U8(10)),
+            },
+        ],
+        layout: None,
+    });
+    assert_eq!(
+        "#[repr(u8)] enum Level { Low = 1, High = 10 }",
+        schema.to_string(),
+    );
+}
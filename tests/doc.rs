@@ -0,0 +1,192 @@
+#![cfg(feature = "std")]
+
+use pretty_assertions::assert_eq;
+use schematic::{doc::Format, *};
+
+pub struct Config {
+    pub server: Server,
+    pub tags: Vec<String>,
+    pub timeout: Option<u32>,
+}
+
+impl Describe for Config {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Config)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct_with(
+            Self::type_name(),
+            TypeInfo {
+                docs: Some(" Top-level configuration.\n\n Loaded from `config.toml`."),
+                ..Default::default()
+            },
+        )?;
+        describer.describe_field::<Server>("server")?;
+        describer.describe_field::<Vec<String>>("tags")?;
+        describer.describe_field_with::<Option<u32>>(
+            "timeout",
+            FieldInfo {
+                docs: Some(" Request timeout in seconds.\n\n Defaults to no timeout."),
+                default: Some(FieldDefault::of_default::<Option<u32>>()),
+                ..Default::default()
+            },
+        )?;
+        describer.end()
+    }
+}
+
+pub struct Server {
+    pub host: String,
+    pub mode: Mode,
+}
+
+impl Describe for Server {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Server)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<String>("host")?;
+        describer.describe_field::<Mode>("mode")?;
+        describer.end()
+    }
+}
+
+#[allow(dead_code)]
+pub enum Mode {
+    Local,
+    Remote(u16),
+}
+
+impl Describe for Mode {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Mode)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_enum(Self::type_name())?;
+        describer.describe_unit_variant("Local", None)?;
+        let mut variant = describer.start_tuple_variant_with(
+            "Remote",
+            VariantInfo {
+                docs: Some(" Listen on the given port."),
+                attributes: Attributes::new(),
+            },
+        )?;
+        variant.describe_element::<u16>()?;
+        describer.end_tuple_variant(variant)?;
+        describer.end()
+    }
+}
+
+#[test]
+fn markdown() {
+    let page = doc::generate(&schematic::describe::<Config>(), Format::Markdown);
+
+    let expected = r#"<a id="doc-Config"></a>
+## Config
+
+Struct in `doc`
+
+Top-level configuration.
+
+Loaded from `config.toml`.
+
+| Field | Type | Optional | Description |
+| --- | --- | --- | --- |
+| `server` | [Server](#doc-Server) | no |  |
+| `tags` | Vec&lt;String&gt; | no |  |
+| `timeout` | Option&lt;u32&gt; | yes | Request timeout in seconds. Defaults to no timeout. |
+
+<a id="doc-Server"></a>
+## Server
+
+Struct in `doc`
+
+| Field | Type | Optional | Description |
+| --- | --- | --- | --- |
+| `host` | String | no |  |
+| `mode` | [Mode](#doc-Mode) | no |  |
+
+<a id="doc-Mode"></a>
+## Mode
+
+Enum in `doc`
+
+| Variant | Data | Description |
+| --- | --- | --- |
+| `Local` |  |  |
+| `Remote` | (u16) | Listen on the given port. |
+"#;
+
+    assert_eq!(expected, page);
+}
+
+#[test]
+fn html() {
+    let page = doc::generate(&schematic::describe::<Server>(), Format::Html);
+
+    let expected = r##"<h2 id="doc-Server">Server</h2>
+<p>Struct in <code>doc</code></p>
+<table>
+<tr><th>Field</th><th>Type</th><th>Optional</th><th>Description</th></tr>
+<tr><td><code>host</code></td><td>String</td><td>no</td><td></td></tr>
+<tr><td><code>mode</code></td><td><a href="#doc-Mode">Mode</a></td><td>no</td><td></td></tr>
+</table>
+<h2 id="doc-Mode">Mode</h2>
+<p>Enum in <code>doc</code></p>
+<table>
+<tr><th>Variant</th><th>Data</th><th>Description</th></tr>
+<tr><td><code>Local</code></td><td></td><td></td></tr>
+<tr><td><code>Remote</code></td><td>(u16)</td><td>Listen on the given port.</td></tr>
+</table>
+"##;
+
+    assert_eq!(expected, page);
+}
+
+#[test]
+fn generic_anchor() {
+    let name = TypeName::generic("Page", "api::paging", vec![type_name!(User)]);
+    assert_eq!("api-paging-Page_3c_doc-User_3e_", doc::anchor(&name));
+}
+
+#[test]
+fn distinct_anchors() {
+    // Underscores are escaped, so they can't be mistaken for path separators.
+    assert_eq!("a-b__c-X", doc::anchor(&TypeName::new("X", "a::b_c")));
+    assert_eq!("a__b-c-X", doc::anchor(&TypeName::new("X", "a_b::c")));
+
+    // Type parameters are nested, so they can't be mistaken for one another.
+    let nested = TypeName::generic(
+        "Pair",
+        "",
+        vec![TypeName::generic("A", "", vec![type_name!(B)])],
+    );
+    let flat = TypeName::generic("Pair", "", vec![TypeName::new("A", ""), type_name!(B)]);
+    assert_ne!(doc::anchor(&nested), doc::anchor(&flat));
+}
+
+#[test]
+fn optional_follows_default() {
+    // An `Option` without a default is required, and any field with a default isn't.
+    let schema = Schema::Struct(Struct {
+        name: type_name!(Limits),
+        docs: None,
+        attributes: Attributes::new(),
+        fields: vec![
+            NamedField::new("burst", schematic::describe::<Option<u32>>()),
+            NamedField {
+                default: Some(FieldDefault::of(&60u32)),
+                ..NamedField::new("rate", Schema::U32)
+            },
+        ],
+        layout: None,
+    });
+
+    let page = doc::generate(&schema, Format::Markdown);
+    assert!(page.contains("| `burst` | Option&lt;u32&gt; | no |  |\n"));
+    assert!(page.contains("| `rate` | u32 | yes |  |\n"));
+}
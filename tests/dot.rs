@@ -0,0 +1,101 @@
+#![cfg(feature = "std")]
+
+use pretty_assertions::assert_eq;
+use schematic::*;
+use std::collections::HashMap;
+
+pub struct Message {
+    pub id: u64,
+    pub author: Option<User>,
+    pub reactions: HashMap<String, Vec<User>>,
+    pub body: Body,
+}
+
+impl Describe for Message {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Message)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<u64>("id")?;
+        describer.describe_field::<Option<User>>("author")?;
+        describer.describe_field::<HashMap<String, Vec<User>>>("reactions")?;
+        describer.describe_field::<Body>("body")?;
+        describer.end()
+    }
+}
+
+pub struct User(pub String);
+
+impl Describe for User {
+    fn type_name() -> TypeName {
+        schematic::type_name!(User)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        describer.describe_newtype_struct::<String>(Self::type_name())
+    }
+}
+
+#[allow(dead_code)]
+pub enum Body {
+    Empty,
+    Text(String),
+    Mention { user: User, offset: usize },
+}
+
+impl Describe for Body {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Body)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_enum(Self::type_name())?;
+        describer.describe_unit_variant("Empty", None)?;
+
+        let mut variant = describer.start_tuple_variant("Text")?;
+        variant.describe_element::<String>()?;
+        describer.end_tuple_variant(variant)?;
+
+        let mut variant = describer.start_struct_variant("Mention")?;
+        variant.describe_field::<User>("user")?;
+        variant.describe_field::<usize>("offset")?;
+        describer.end_struct_variant(variant)?;
+
+        describer.end()
+    }
+}
+
+#[test]
+fn dependency_graph() {
+    let dot = dot::generate(&schematic::describe::<Message>());
+
+    let expected = r#"digraph schema {
+    node [shape=record];
+
+    "dot::User" [label="{User|<0> 0: String}"];
+
+    "dot::Body" [label="{Body|<Empty> Empty|<Text> Text(String)|<Mention> Mention \{ user: User, offset: usize \}}"];
+    "dot::Body":Mention -> "dot::User";
+
+    "dot::Message" [label="{Message|<id> id: u64|<author> author: Option\<User\>|<reactions> reactions: HashMap\<String, Vec\<User\>\>|<body> body: Body}"];
+    "dot::Message":author -> "dot::User" [label="Option"];
+    "dot::Message":reactions -> "dot::User" [label="Map value / Seq"];
+    "dot::Message":body -> "dot::Body";
+}
+"#;
+
+    assert_eq!(expected, dot);
+}
+
+#[test]
+fn generic_node_id() {
+    let schema = Schema::UnitStruct(UnitStruct {
+        name: TypeName::generic("Marker", "dot", vec![type_name!(User)]),
+        docs: None,
+    });
+
+    let dot = dot::generate(&schema);
+    assert!(dot.contains(r#""dot::Marker<dot::User>" [label="{Marker\<User\>}"];"#));
+}
@@ -1,24 +1,32 @@
-use schematic::{type_name, Enum, Field, Schema, Variant};
+use schematic::{type_name, Attributes, Enum, Field, NamedField, Schema, Variant};
 
 #[test]
 fn test_empty_enum() {
     let schema = Enum {
         name: type_name!(MyEnum),
+        docs: None,
         repr: None,
         variants: vec![
             Variant::Unit {
                 name: "Foo".into(),
+                docs: None,
+                attributes: Attributes::new(),
                 discriminant: None,
             },
             Variant::Struct {
                 name: "Bar".into(),
+                docs: None,
+                attributes: Attributes::new(),
                 fields: Vec::new(),
             },
             Variant::Tuple {
                 name: "Baz".into(),
+                docs: None,
+                attributes: Attributes::new(),
                 elements: Vec::new(),
             },
         ],
+        layout: None,
     };
 
     assert!(!schema.has_data());
@@ -32,21 +40,29 @@ fn test_empty_enum() {
 fn test_non_empty_enum() {
     let schema = Enum {
         name: type_name!(MyEnum),
+        docs: None,
         repr: None,
         variants: vec![
             Variant::Unit {
                 name: "Foo".into(),
+                docs: None,
+                attributes: Attributes::new(),
                 discriminant: None,
             },
             Variant::Struct {
                 name: "Bar".into(),
+                docs: None,
+                attributes: Attributes::new(),
                 fields: Vec::new(),
             },
             Variant::Tuple {
                 name: "Baz".into(),
+                docs: None,
+                attributes: Attributes::new(),
                 elements: vec![Schema::I8],
             },
         ],
+        layout: None,
     };
 
     assert!(schema.has_data());
@@ -57,6 +73,8 @@ fn test_non_empty_enum() {
 fn unit_variant_fields() {
     let variant = Variant::Unit {
         name: "Foo".into(),
+        docs: None,
+        attributes: Attributes::new(),
         discriminant: None,
     };
 
@@ -68,7 +86,12 @@ fn unit_variant_fields() {
 fn named_variant_fields() {
     let variant = Variant::Struct {
         name: "Bar".into(),
-        fields: vec![("foo".into(), Schema::I8), ("bar".into(), Schema::Bool)],
+        docs: None,
+        attributes: Attributes::new(),
+        fields: vec![
+            NamedField::new("foo", Schema::I8),
+            NamedField::new("bar", Schema::Bool),
+        ],
     };
 
     let mut fields = variant.fields();
@@ -93,6 +116,8 @@ fn named_variant_fields() {
 fn unnamed_variant_fields() {
     let variant = Variant::Tuple {
         name: "Bar".into(),
+        docs: None,
+        attributes: Attributes::new(),
         elements: vec![Schema::I8, Schema::Bool],
     };
 
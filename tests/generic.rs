@@ -0,0 +1,88 @@
+#![cfg(feature = "std")]
+
+use pretty_assertions::assert_eq;
+use schematic::*;
+
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<u32>,
+}
+
+impl<T: Describe> Describe for Page<T> {
+    fn type_name() -> TypeName {
+        TypeName::generic("Page", module_path!(), vec![T::type_name()])
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<Vec<T>>("items")?;
+        describer.describe_field::<Option<u32>>("next")?;
+        describer.end()
+    }
+}
+
+schematic::type_param!(T);
+
+fn page(param: Schema, param_name: TypeName) -> Schema {
+    Schema::Struct(Struct {
+        name: TypeName::generic("Page", module_path!(), vec![param_name.clone()]),
+        docs: None,
+        attributes: Attributes::new(),
+        fields: vec![
+            NamedField::new(
+                "items",
+                Schema::Seq(Box::new(Sequence {
+                    name: TypeName::generic("Vec", "alloc::vec", vec![param_name]),
+                    element: param,
+                    len: None,
+                })),
+            ),
+            NamedField::new("next", Schema::Option(Box::new(Schema::U32))),
+        ],
+        layout: None,
+    })
+}
+
+#[test]
+fn describe_generic_definition() {
+    let definition = schematic::describe::<Page<T>>();
+    assert_eq!(
+        page(Schema::TypeParam("T".into()), TypeName::new("T", "")),
+        definition
+    );
+    assert!(definition.is_generic_definition());
+    assert_eq!("Page<T>", definition.type_name().unwrap().to_string());
+
+    let instance = schematic::describe::<Page<bool>>();
+    assert_eq!(page(Schema::Bool, bool::type_name()), instance);
+    assert!(!instance.is_generic_definition());
+}
+
+#[test]
+fn parse_generic_definition() {
+    let definition = schematic::describe::<Page<T>>();
+    let source = format!("{:#}", definition);
+    assert_eq!(
+        "struct Page<T> { items: Vec<T>, next: Option<u32> }",
+        source
+    );
+
+    // Parsed types have an empty module.
+    let parsed = schematic::parse::parse(&source).unwrap();
+    assert_eq!(
+        Schema::TypeParam("T".into()),
+        parsed.as_struct().unwrap().fields[0].schema.children()[0].clone(),
+    );
+    assert!(parsed.is_generic_definition());
+    assert_eq!(source, format!("{:#}", parsed));
+}
+
+#[test]
+fn generate_generic_definition() {
+    let source = rust::generate(&schematic::describe::<Page<T>>()).unwrap();
+
+    assert!(source.contains("pub struct Page<T> {\n    pub items: ::std::vec::Vec<T>,\n"));
+    assert!(source.contains("impl<T: ::schematic::Describe> ::schematic::Describe for Page<T> {"));
+    assert!(source.contains("vec![T::type_name()]"));
+    assert!(source.contains("describer.describe_field::<::std::vec::Vec<T>>(\"items\")?;"));
+}
@@ -0,0 +1,151 @@
+#![cfg(feature = "std")]
+
+use pretty_assertions::assert_eq;
+use schematic::{graph::*, *};
+use std::collections::BTreeMap;
+
+pub struct User {
+    pub name: String,
+    pub role: Role,
+}
+
+impl Describe for User {
+    fn type_name() -> TypeName {
+        schematic::type_name!(User)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field_with::<String>(
+            "name",
+            FieldInfo {
+                docs: Some("The display name."),
+                constraints: Constraints {
+                    max_len: Some(64),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )?;
+        describer.describe_field::<Role>("role")?;
+        describer.end()
+    }
+}
+
+pub enum Role {
+    Member,
+    Admin { since: u64 },
+    Guest(Option<u64>),
+}
+
+impl Describe for Role {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Role)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_enum(Self::type_name())?;
+        describer.describe_unit_variant("Member", None)?;
+
+        let mut variant = describer.start_struct_variant("Admin")?;
+        variant.describe_field_with::<u64>(
+            "since",
+            FieldInfo {
+                docs: Some("Seconds since the epoch."),
+                ..Default::default()
+            },
+        )?;
+        describer.end_struct_variant(variant)?;
+
+        let mut variant = describer.start_tuple_variant("Guest")?;
+        variant.describe_element::<Option<u64>>()?;
+        describer.end_tuple_variant(variant)?;
+
+        describer.end()
+    }
+}
+
+pub struct Team {
+    pub lead: User,
+    pub members: Vec<User>,
+    pub by_name: BTreeMap<String, User>,
+}
+
+impl Describe for Team {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Team)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<User>("lead")?;
+        describer.describe_field::<Vec<User>>("members")?;
+        describer.describe_field::<BTreeMap<String, User>>("by_name")?;
+        describer.end()
+    }
+}
+
+#[test]
+fn graph_round_trip() {
+    let mut graph = SchemaGraph::new();
+    assert!(graph.is_empty());
+
+    let team = graph.insert(&schematic::describe::<Team>());
+    assert_eq!(schematic::describe::<Team>(), graph.schema(team));
+
+    let user = graph.insert(&schematic::describe::<User>());
+    assert_eq!(schematic::describe::<User>(), graph.schema(user));
+
+    // Inserting a schema again reuses the existing nodes.
+    let len = graph.len();
+    assert_eq!(team, graph.insert(&schematic::describe::<Team>()));
+    assert_eq!(len, graph.len());
+}
+
+#[test]
+fn graph_shares_definitions() {
+    let mut graph = SchemaGraph::new();
+    let team = graph.insert(&schematic::describe::<Team>());
+    let user = graph.definition(&User::type_name()).unwrap();
+    assert_eq!(Some(team), graph.definition(&Team::type_name()));
+    assert_eq!(None, graph.definition(&u64::type_name()));
+
+    let fields = match graph.node(team) {
+        Node::Struct { fields, .. } => fields,
+        node => panic!("expected a struct, found {:?}", node),
+    };
+    assert_eq!("lead", graph.str(fields[0].name));
+    assert_eq!(user, fields[0].schema);
+    assert!(matches!(
+        graph.node(fields[1].schema),
+        Node::Seq { element, .. } if *element == user,
+    ));
+
+    // Each distinct node is stored once, even though `User` is used three times:
+    // `Team`, `User`, `String`, `Role`, `u64`, `Option<u64>`, `Vec<User>` and
+    // `BTreeMap<String, User>`.
+    assert_eq!(8, graph.len());
+    assert_eq!(
+        Some("User"),
+        graph
+            .node(user)
+            .type_name()
+            .map(|name| &*graph.type_name(name).name)
+    );
+}
+
+#[test]
+fn export_from_graph() {
+    let mut graph = SchemaGraph::new();
+    let team = graph.insert(&schematic::describe::<Team>());
+
+    let schema = graph.schema(team);
+    assert_eq!(
+        rust::generate(&schematic::describe::<Team>()).unwrap(),
+        rust::generate(&schema).unwrap()
+    );
+    assert_eq!(
+        doc::generate(&schematic::describe::<Team>(), doc::Format::Markdown),
+        doc::generate(&schema, doc::Format::Markdown)
+    );
+}
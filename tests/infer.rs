@@ -0,0 +1,150 @@
+#![cfg(feature = "std")]
+
+use pretty_assertions::assert_eq;
+use schematic::{infer::Error, *};
+use serde_json::json;
+use std::collections::HashMap;
+
+/// A field that is missing from some samples.
+fn missing(name: &'static str, schema: Schema) -> NamedField {
+    NamedField {
+        default: Some(FieldDefault::Value(serde_json::Value::Null)),
+        skip_serializing_if: true,
+        ..NamedField::new(name, schema)
+    }
+}
+
+#[test]
+fn merge_samples() {
+    let samples = [
+        json!({
+            "id": 1,
+            "score": -3,
+            "name": "Alice",
+            "address": { "city": "Paris" },
+            "tags": [],
+        }),
+        json!({
+            "id": 70000,
+            "score": 2.5,
+            "name": null,
+            "address": { "city": "Oslo", "zip": "0150" },
+            "tags": ["admin"],
+            "nickname": "bob",
+        }),
+    ];
+
+    let expected = Schema::Struct(Struct {
+        name: TypeName::new("User", ""),
+        docs: None,
+        attributes: Attributes::new(),
+        fields: vec![
+            NamedField::new("id", Schema::U32),
+            NamedField::new("score", Schema::F64),
+            NamedField::new("name", schematic::describe::<Option<String>>()),
+            NamedField::new(
+                "address",
+                Schema::Struct(Struct {
+                    name: TypeName::new("UserAddress", ""),
+                    docs: None,
+                    attributes: Attributes::new(),
+                    fields: vec![
+                        NamedField::new("city", schematic::describe::<String>()),
+                        missing("zip", schematic::describe::<Option<String>>()),
+                    ],
+                    layout: None,
+                }),
+            ),
+            NamedField::new("tags", schematic::describe::<Vec<String>>()),
+            missing("nickname", schematic::describe::<Option<String>>()),
+        ],
+        layout: None,
+    });
+
+    let schema = infer::infer("User", &samples).unwrap();
+    assert_eq!(expected, schema);
+
+    // Fields that are sometimes `null` must still be present.
+    let fields = &schema.as_struct().unwrap().fields;
+    assert!(fields[2].is_required());
+    assert!(!fields[5].is_required());
+}
+
+#[test]
+fn unify_array_elements() {
+    let sample = json!([
+        { "x": 1, "y": -1 },
+        { "x": 2 },
+        null,
+    ]);
+
+    let point = Schema::Struct(Struct {
+        name: TypeName::new("Points", ""),
+        docs: None,
+        attributes: Attributes::new(),
+        fields: vec![
+            NamedField::new("x", Schema::U8),
+            missing("y", Schema::Option(Box::new(Schema::I8))),
+        ],
+        layout: None,
+    });
+    let element = Schema::Option(Box::new(point));
+
+    let expected = Schema::Seq(Box::new(Sequence {
+        name: TypeName::generic(
+            "Vec",
+            "alloc::vec",
+            vec![TypeName::generic(
+                "Option",
+                "core::option",
+                vec![TypeName::new("Points", "")],
+            )],
+        ),
+        element,
+        len: None,
+    }));
+
+    assert_eq!(Ok(expected), infer::infer("Points", &[sample]));
+}
+
+#[test]
+fn detect_maps() {
+    let sample = json!({
+        "scores": { "1042": 12, "1043": null },
+        "labels": { "en.title": "Hello", "fr.title": "Bonjour" },
+    });
+
+    let schema = infer::infer("Report", &[sample]).unwrap();
+    let fields = &schema.as_struct().unwrap().fields;
+
+    assert_eq!(
+        schematic::describe::<HashMap<String, Option<u8>>>(),
+        fields[0].schema,
+    );
+    assert_eq!(
+        schematic::describe::<HashMap<String, String>>(),
+        fields[1].schema,
+    );
+}
+
+#[test]
+fn report_conflicts() {
+    let mut inferrer = infer::Inferrer::new("Event");
+    inferrer.add(&json!({ "data": [1, 2] })).unwrap();
+
+    assert_eq!(
+        Err(Error::Conflict {
+            sample: 1,
+            pointer: "/data/1".into(),
+            expected: "number",
+            found: "string",
+        }),
+        inferrer.add(&json!({ "data": [3, "four"] })),
+    );
+
+    // The conflicting sample isn't merged.
+    assert_eq!(
+        infer::infer("Event", &[json!({ "data": [1, 2] })]),
+        Ok(inferrer.schema()),
+    );
+}
@@ -0,0 +1,608 @@
+#![cfg(feature = "std")]
+
+use pretty_assertions::assert_eq;
+use schematic::{json_schema::Error, *};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+
+fn named(name: &'static str) -> TypeName {
+    TypeName::new(name, "")
+}
+
+#[test]
+fn import_object() {
+    let document = json!({
+        "title": "User",
+        "description": "A registered user.",
+        "type": "object",
+        "properties": {
+            "id": { "$ref": "#/$defs/UserId" },
+            "age": { "type": "integer", "minimum": 0, "exclusiveMaximum": 256 },
+            "offset": { "type": "integer", "minimum": -40000, "maximum": 40000 },
+            "nickname": { "type": ["string", "null"] },
+            "tags": { "type": "array", "items": { "type": "string" } },
+            "position": {
+                "type": "array",
+                "items": { "type": "number" },
+                "minItems": 2,
+                "maxItems": 2,
+            },
+            "address": {
+                "description": "Where the user lives.",
+                "type": "object",
+                "properties": { "city": { "type": "string" } },
+                "required": ["city"],
+            },
+            "scores": { "type": "object", "additionalProperties": { "type": "integer" } },
+        },
+        "required": ["id", "age", "offset", "nickname", "tags", "position", "address"],
+        "$defs": {
+            "UserId": { "type": "integer", "minimum": 1 },
+        },
+    });
+
+    let expected = Schema::Struct(Struct {
+        name: named("User"),
+        docs: Some("A registered user.".into()),
+        attributes: Attributes::new(),
+        fields: vec![
+            NamedField::new(
+                "id",
+                Schema::NewtypeStruct(Box::new(NewtypeStruct {
+                    name: named("UserId"),
+                    docs: None,
+                    inner: Schema::U64,
+                    constraints: Constraints {
+                        min: Some(Number::Int(1)),
+                        ..Default::default()
+                    },
+                })),
+            ),
+            NamedField::new("age", Schema::U8),
+            NamedField {
+                constraints: Constraints {
+                    min: Some(Number::Int(-40000)),
+                    max: Some(Number::Int(40000)),
+                    ..Default::default()
+                },
+                ..NamedField::new("offset", Schema::I32)
+            },
+            NamedField::new("nickname", schematic::describe::<Option<String>>()),
+            NamedField::new("tags", schematic::describe::<Vec<String>>()),
+            NamedField::new("position", schematic::describe::<[f64; 2]>()),
+            NamedField {
+                name: "address".into(),
+                docs: Some("Where the user lives.".into()),
+                attributes: Attributes::new(),
+                schema: Schema::Struct(Struct {
+                    name: named("UserAddress"),
+                    docs: Some("Where the user lives.".into()),
+                    attributes: Attributes::new(),
+                    fields: vec![NamedField::new("city", schematic::describe::<String>())],
+                    layout: None,
+                }),
+                constraints: Constraints::default(),
+                default: None,
+                skip_serializing_if: false,
+            },
+            NamedField {
+                default: Some(FieldDefault::Value(json!(null))),
+                skip_serializing_if: true,
+                ..NamedField::new(
+                    "scores",
+                    schematic::describe::<Option<HashMap<String, i64>>>(),
+                )
+            },
+        ],
+        layout: None,
+    });
+
+    assert_eq!(Ok(expected), Schema::from_json_schema(&document));
+}
+
+#[test]
+fn import_tagged_union() {
+    let document = json!({
+        "title": "Shape",
+        "oneOf": [
+            { "$ref": "#/definitions/Circle" },
+            { "$ref": "#/definitions/Square" },
+            {
+                "type": "object",
+                "properties": { "kind": { "const": "empty" } },
+                "required": ["kind"],
+            },
+        ],
+        "discriminator": {
+            "propertyName": "kind",
+            "mapping": { "square": "#/definitions/Square" },
+        },
+        "definitions": {
+            "Circle": {
+                "type": "object",
+                "properties": {
+                    "kind": { "type": "string", "enum": ["circle"] },
+                    "radius": { "type": "number" },
+                },
+                "required": ["kind", "radius"],
+            },
+            "Square": {
+                "description": "A square with sides of equal length.",
+                "type": "object",
+                "properties": { "side": { "type": "number" } },
+                "required": ["side"],
+            },
+        },
+    });
+
+    let expected = Schema::Enum(Enum {
+        name: named("Shape"),
+        docs: None,
+        repr: None,
+        variants: vec![
+            Variant::Struct {
+                name: "circle".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                fields: vec![NamedField::new("radius", Schema::F64)],
+            },
+            Variant::Struct {
+                name: "square".into(),
+                docs: Some("A square with sides of equal length.".into()),
+                attributes: Attributes::new(),
+                fields: vec![NamedField::new("side", Schema::F64)],
+            },
+            Variant::Unit {
+                name: "empty".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                discriminant: None,
+            },
+        ],
+        layout: None,
+    });
+
+    assert_eq!(Ok(expected), Schema::from_json_schema(&document));
+}
+
+#[test]
+fn report_unsupported_keywords() {
+    let document = json!({
+        "type": "object",
+        "properties": {
+            "names": {
+                "type": "array",
+                "items": { "type": "string", "pattern": "^[a-z]+$" },
+            },
+        },
+    });
+    assert_eq!(
+        Err(Error::Unsupported {
+            pointer: "/properties/names/items".into(),
+            keyword: "pattern".into(),
+        }),
+        Schema::from_json_schema(&document),
+    );
+
+    let document = json!({
+        "allOf": [{ "type": "object" }],
+    });
+    assert_eq!(
+        Err(Error::Unsupported {
+            pointer: "".into(),
+            keyword: "allOf".into(),
+        }),
+        Schema::from_json_schema(&document),
+    );
+}
+
+#[test]
+fn import_constraints() {
+    let document = json!({
+        "type": "object",
+        "properties": {
+            "name": { "type": "string", "pattern": "^[a-z]+$", "minLength": 1, "maxLength": 32 },
+            "volume": { "type": "number", "minimum": 0, "maximum": 1.5 },
+            "level": { "$ref": "#/definitions/Level" },
+        },
+        "required": ["name", "volume", "level"],
+        "definitions": {
+            "Level": { "type": "integer", "minimum": 1, "maximum": 10 },
+        },
+    });
+
+    let schema = Schema::from_json_schema(&document).unwrap();
+    let fields = &schema.as_struct().unwrap().fields;
+
+    assert_eq!(
+        Constraints {
+            min_len: Some(1),
+            max_len: Some(32),
+            pattern: Some("^[a-z]+$".into()),
+            ..Default::default()
+        },
+        fields[0].constraints
+    );
+    assert_eq!(
+        Constraints {
+            min: Some(Number::Int(0)),
+            max: Some(Number::Float(1.5)),
+            ..Default::default()
+        },
+        fields[1].constraints
+    );
+
+    // The integer type only covers part of the bounds, so the rest are kept too.
+    assert_eq!(
+        Schema::NewtypeStruct(Box::new(NewtypeStruct {
+            name: named("Level"),
+            docs: None,
+            inner: Schema::U8,
+            constraints: Constraints {
+                min: Some(Number::Int(1)),
+                max: Some(Number::Int(10)),
+                ..Default::default()
+            },
+        })),
+        fields[2].schema
+    );
+}
+
+#[test]
+fn reject_recursive_ref() {
+    let document = json!({
+        "$ref": "#/$defs/Node",
+        "$defs": {
+            "Node": {
+                "type": "object",
+                "properties": {
+                    "children": { "type": "array", "items": { "$ref": "#/$defs/Node" } },
+                },
+            },
+        },
+    });
+
+    assert_eq!(
+        Err(Error::RecursiveRef {
+            pointer: "/$defs/Node/properties/children/items".into(),
+            reference: "#/$defs/Node".into(),
+        }),
+        Schema::from_json_schema(&document),
+    );
+}
+
+#[test]
+fn import_defaults() {
+    let document = json!({
+        "type": "object",
+        "properties": {
+            "port": { "type": "integer", "minimum": 0, "maximum": 65535, "default": 8080 },
+            "host": { "type": "string" },
+        },
+    });
+
+    let schema = Schema::from_json_schema(&document).unwrap();
+    let fields = &schema.as_struct().unwrap().fields;
+
+    // Properties with a default keep their type instead of becoming optional.
+    assert_eq!(Schema::U16, fields[0].schema);
+    assert_eq!(Some(FieldDefault::Value(json!(8080))), fields[0].default);
+    assert_eq!(schematic::describe::<Option<String>>(), fields[1].schema);
+    assert_eq!(Some(FieldDefault::Value(json!(null))), fields[1].default);
+    assert!(fields.iter().all(|field| !field.is_required()));
+    assert!(fields.iter().all(|field| !field.is_always_serialized()));
+}
+
+pub struct Job {
+    pub id: JobId,
+    pub labels: BTreeMap<u32, String>,
+    pub state: State,
+    pub priority: Option<Priority>,
+}
+
+impl Describe for Job {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Job)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct_with(
+            Self::type_name(),
+            TypeInfo {
+                docs: Some(" A queued job."),
+                ..Default::default()
+            },
+        )?;
+        describer.describe_field::<JobId>("id")?;
+        describer.describe_field::<BTreeMap<u32, String>>("labels")?;
+        describer.describe_field_with::<State>(
+            "state",
+            FieldInfo {
+                docs: Some(" Where the job is at."),
+                ..Default::default()
+            },
+        )?;
+        describer.describe_field_with::<Option<Priority>>(
+            "priority",
+            FieldInfo {
+                default: Some(FieldDefault::Unknown),
+                skip_serializing_if: true,
+                ..Default::default()
+            },
+        )?;
+        describer.end()
+    }
+}
+
+pub struct JobId(pub u64);
+
+impl Describe for JobId {
+    fn type_name() -> TypeName {
+        schematic::type_name!(JobId)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        describer.describe_newtype_struct::<u64>(Self::type_name())
+    }
+}
+
+pub enum State {
+    Queued,
+    Running { worker: u8 },
+    Failed(String, i16),
+}
+
+impl Describe for State {
+    fn type_name() -> TypeName {
+        schematic::type_name!(State)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_enum(Self::type_name())?;
+        describer.describe_unit_variant("Queued", None)?;
+
+        let mut variant = describer.start_struct_variant("Running")?;
+        variant.describe_field::<u8>("worker")?;
+        describer.end_struct_variant(variant)?;
+
+        let mut variant = describer.start_tuple_variant("Failed")?;
+        variant.describe_element::<String>()?;
+        variant.describe_element::<i16>()?;
+        describer.end_tuple_variant(variant)?;
+
+        describer.end()
+    }
+}
+
+pub enum Priority {
+    Low,
+    High,
+}
+
+impl Describe for Priority {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Priority)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_enum(Self::type_name())?;
+        describer.describe_unit_variant("Low", None)?;
+        describer.describe_unit_variant("High", None)?;
+        describer.end()
+    }
+}
+
+#[test]
+fn export_document() {
+    let expected = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Job",
+        "type": "object",
+        "properties": {
+            "id": { "$ref": "#/$defs/JobId" },
+            "labels": {
+                "type": "object",
+                "propertyNames": { "pattern": "^[0-9]+$" },
+                "additionalProperties": { "type": "string" },
+            },
+            "state": { "$ref": "#/$defs/State", "description": "Where the job is at." },
+            // The default of `priority` isn't known, so it isn't written.
+            "priority": { "oneOf": [{ "$ref": "#/$defs/Priority" }, { "type": "null" }] },
+        },
+        "required": ["id", "labels", "state"],
+        "description": "A queued job.",
+        "$defs": {
+            "JobId": { "type": "integer", "minimum": 0, "maximum": u64::MAX },
+            "State": {
+                "oneOf": [
+                    { "const": "Queued" },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "Running": {
+                                "type": "object",
+                                "properties": {
+                                    "worker": { "type": "integer", "minimum": 0, "maximum": 255 },
+                                },
+                                "required": ["worker"],
+                            },
+                        },
+                        "required": ["Running"],
+                        "additionalProperties": false,
+                    },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "Failed": {
+                                "type": "array",
+                                "prefixItems": [
+                                    { "type": "string" },
+                                    { "type": "integer", "minimum": -32768, "maximum": 32767 },
+                                ],
+                                "items": false,
+                                "minItems": 2,
+                            },
+                        },
+                        "required": ["Failed"],
+                        "additionalProperties": false,
+                    },
+                ],
+            },
+            "Priority": { "type": "string", "enum": ["Low", "High"] },
+        },
+    });
+
+    assert_eq!(Ok(expected), schematic::describe::<Job>().to_json_schema());
+}
+
+#[test]
+fn export_round_trip() {
+    let schema = Schema::Struct(Struct {
+        name: named("Server"),
+        docs: Some("A server to connect to.".into()),
+        attributes: Attributes::new(),
+        fields: vec![
+            NamedField {
+                docs: Some("The port to listen on.".into()),
+                ..NamedField::new("port", Schema::U16)
+            },
+            NamedField::new("host", schematic::describe::<Option<String>>()),
+            NamedField::new(
+                "tls",
+                Schema::Struct(Struct {
+                    name: named("Tls"),
+                    docs: None,
+                    attributes: Attributes::new(),
+                    fields: vec![NamedField::new("cert", schematic::describe::<String>())],
+                    layout: None,
+                }),
+            ),
+            NamedField {
+                default: Some(FieldDefault::Value(json!(null))),
+                skip_serializing_if: true,
+                ..NamedField::new("tags", schematic::describe::<Option<Vec<String>>>())
+            },
+            NamedField {
+                default: Some(FieldDefault::of(&3u8)),
+                skip_serializing_if: true,
+                ..NamedField::new("retries", Schema::U8)
+            },
+        ],
+        layout: None,
+    });
+
+    let document = schema.to_json_schema().unwrap();
+    assert_eq!(json!(3), document["properties"]["retries"]["default"]);
+    assert_eq!(Ok(schema), Schema::from_json_schema(&document));
+}
+
+#[test]
+fn export_unrepresentable() {
+    let schema = schematic::describe::<Vec<(u8, BTreeMap<bool, u8>)>>();
+    assert_eq!(
+        Err(Error::Unrepresentable(Box::new(Schema::Bool))),
+        schema.to_json_schema(),
+    );
+
+    let error = Schema::Option(Box::new(Schema::TypeParam("T".into())))
+        .to_json_schema()
+        .unwrap_err();
+    assert_eq!(
+        "TypeParam(\"T\") has no JSON Schema representation",
+        error.to_string()
+    );
+}
+
+#[test]
+fn export_constraints() {
+    let constrained = |name, schema, constraints| NamedField {
+        constraints,
+        ..NamedField::new(name, schema)
+    };
+
+    let schema = Schema::Struct(Struct {
+        name: named("Profile"),
+        docs: None,
+        attributes: Attributes::new(),
+        fields: vec![
+            constrained(
+                "handle",
+                schematic::describe::<String>(),
+                Constraints {
+                    min_len: Some(1),
+                    max_len: Some(32),
+                    pattern: Some("^[a-z]+$".into()),
+                    ..Default::default()
+                },
+            ),
+            constrained(
+                "volume",
+                Schema::F64,
+                Constraints {
+                    min: Some(Number::Int(0)),
+                    max: Some(Number::Float(1.5)),
+                    ..Default::default()
+                },
+            ),
+            constrained(
+                "level",
+                Schema::U8,
+                Constraints {
+                    min: Some(Number::Int(-5)),
+                    max: Some(Number::Int(10)),
+                    ..Default::default()
+                },
+            ),
+            constrained(
+                "friends",
+                schematic::describe::<Option<Vec<String>>>(),
+                Constraints {
+                    max_len: Some(100),
+                    ..Default::default()
+                },
+            ),
+        ],
+        layout: None,
+    });
+
+    let document = schema.to_json_schema().unwrap();
+    let properties = &document["properties"];
+    assert_eq!(
+        json!({ "type": "string", "minLength": 1, "maxLength": 32, "pattern": "^[a-z]+$" }),
+        properties["handle"],
+    );
+    assert_eq!(
+        json!({ "type": "number", "minimum": 0, "maximum": 1.5 }),
+        properties["volume"],
+    );
+
+    // Bounds looser than the range of the type don't replace it.
+    assert_eq!(
+        json!({ "type": "integer", "minimum": 0, "maximum": 10 }),
+        properties["level"],
+    );
+    assert_eq!(
+        json!({
+            "oneOf": [{ "type": "array", "items": { "type": "string" } }, { "type": "null" }],
+            "maxItems": 100,
+        }),
+        properties["friends"],
+    );
+
+    // Constraints on newtypes are part of their definition, and are imported again.
+    let document = json!({
+        "$ref": "#/$defs/Level",
+        "$defs": { "Level": { "type": "integer", "minimum": 1, "maximum": 10 } },
+    });
+    let level = Schema::from_json_schema(&document).unwrap();
+    assert_eq!(
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Level",
+            "type": "integer",
+            "minimum": 1,
+            "maximum": 10,
+        }),
+        level.to_json_schema().unwrap(),
+    );
+}
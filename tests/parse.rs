@@ -0,0 +1,171 @@
+#![cfg(feature = "std")]
+
+use pretty_assertions::assert_eq;
+use schematic::{parse::Error, *};
+use std::collections::HashMap;
+
+pub struct Config {
+    pub name: String,
+    pub servers: Vec<Server>,
+    pub limits: HashMap<String, Limit>,
+}
+
+impl Describe for Config {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Config)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<String>("name")?;
+        describer.describe_field::<Vec<Server>>("servers")?;
+        describer.describe_field::<HashMap<String, Limit>>("limits")?;
+        describer.end()
+    }
+}
+
+pub struct Server {
+    pub address: ([u8; 4], u16),
+    pub backup: Option<Port>,
+}
+
+impl Describe for Server {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Server)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<([u8; 4], u16)>("address")?;
+        describer.describe_field::<Option<Port>>("backup")?;
+        describer.end()
+    }
+}
+
+pub struct Port(pub u16);
+
+impl Describe for Port {
+    fn type_name() -> TypeName {
+        TypeName::new("Port", "")
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        describer.describe_newtype_struct::<u16>(Self::type_name())
+    }
+}
+
+pub struct Limit(pub u32);
+
+impl Describe for Limit {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Limit)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_tuple_struct(Self::type_name())?;
+        describer.describe_element::<u32>()?;
+        describer.end()
+    }
+}
+
+#[test]
+fn parse_round_trip() {
+    let schema = schematic::describe::<Config>();
+    let parsed = schematic::parse::parse(&format!("{:#}", schema)).unwrap();
+    assert_eq!(format!("{:#}", schema), format!("{:#}", parsed));
+
+    // Types without a module are parsed back exactly.
+    let schema = schematic::describe::<HashMap<String, Vec<Option<Port>>>>();
+    assert_eq!(schema, format!("{:#}", schema).parse().unwrap());
+}
+
+#[test]
+fn parse_definitions() {
+    let schema: Schema = "
+        Vec<Event>
+
+        /// Something that happened.
+        enum Event {
+            /// A key was pressed.
+            Key(KeyCode),
+            Resize { width: u16, height: u16 },
+        }
+
+        // Key codes match the platform's.
+        #[repr(u8)]
+        enum KeyCode { Enter = 13, Escape = 27 }
+    "
+    .parse()
+    .unwrap();
+
+    let key_code = Schema::Enum(Enum {
+        name: TypeName::new("KeyCode", ""),
+        docs: None,
+        repr: Some(Primitive::U8),
+        variants: vec![
+            Variant::Unit {
+                name: "Enter".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                discriminant: Some(PrimitiveValue::U8(13)),
+            },
+            Variant::Unit {
+                name: "Escape".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                discriminant: Some(PrimitiveValue::U8(27)),
+            },
+        ],
+        layout: None,
+    });
+    let event = Schema::Enum(Enum {
+        name: TypeName::new("Event", ""),
+        docs: Some(" Something that happened.".into()),
+        repr: None,
+        variants: vec![
+            Variant::Tuple {
+                name: "Key".into(),
+                docs: Some(" A key was pressed.".into()),
+                attributes: Attributes::new(),
+                elements: vec![key_code],
+            },
+            Variant::Struct {
+                name: "Resize".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                fields: vec![
+                    NamedField::new("width", Schema::U16),
+                    NamedField::new("height", Schema::U16),
+                ],
+            },
+        ],
+        layout: None,
+    });
+
+    let Schema::Seq(seq) = schema else {
+        panic!("expected a sequence, found {}", schema);
+    };
+    assert_eq!("Vec<Event>", seq.name.to_string());
+    assert_eq!(None, seq.len);
+    assert_eq!(event, seq.element);
+}
+
+#[test]
+fn parse_errors() {
+    let error = schematic::parse::parse("struct Foo {\n    bar: Baz,\n}").unwrap_err();
+    assert_eq!("unknown type `Baz` at 2:10", error.to_string());
+    assert!(matches!(error, Error::UnknownType { .. }));
+
+    let error = schematic::parse::parse("struct Foo { bar: u8 baz: u8 }").unwrap_err();
+    assert_eq!(
+        "expected `,` or `}`, found `baz` at 1:22",
+        error.to_string()
+    );
+
+    let error = schematic::parse::parse("struct Node { next: Option<Node> }").unwrap_err();
+    assert_eq!("type `Node` contains itself at 1:28", error.to_string());
+
+    let error = schematic::parse::parse("#[repr(u8)] enum Big { A = 256 }").unwrap_err();
+    assert_eq!("number `256` is out of range at 1:28", error.to_string());
+    assert_eq!(27..30, error.span().start..error.span().end);
+}
@@ -0,0 +1,160 @@
+#![cfg(feature = "std")]
+
+use pretty_assertions::assert_eq;
+use schematic::{record::*, *};
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Serialize)]
+pub enum Event {
+    Started,
+    Progress(u8),
+    Finished { code: i32, message: Option<String> },
+}
+
+#[derive(Serialize)]
+pub struct Job {
+    pub id: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    pub events: Vec<Event>,
+}
+
+#[test]
+fn merge_observations() {
+    let mut recorder = Recorder::new();
+    recorder
+        .record(&Job {
+            id: 1,
+            tags: Vec::new(),
+            events: vec![Event::Finished {
+                code: 0,
+                message: None,
+            }],
+        })
+        .unwrap();
+    recorder
+        .record(&Job {
+            id: 2,
+            tags: vec!["nightly".into()],
+            events: vec![
+                Event::Started,
+                Event::Finished {
+                    code: 1,
+                    message: Some("failed".into()),
+                },
+            ],
+        })
+        .unwrap();
+
+    let event = Schema::Enum(Enum {
+        name: TypeName::new("Event", ""),
+        docs: None,
+        repr: None,
+        variants: vec![
+            Variant::Unit {
+                name: "Started".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                discriminant: None,
+            },
+            Variant::Struct {
+                name: "Finished".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                fields: vec![
+                    NamedField::new("code", Schema::I32),
+                    NamedField::new("message", schematic::describe::<Option<String>>()),
+                ],
+            },
+        ],
+        layout: None,
+    });
+
+    let expected = Schema::Struct(Struct {
+        name: TypeName::new("Job", ""),
+        docs: None,
+        attributes: Attributes::new(),
+        fields: vec![
+            NamedField::new("id", Schema::U64),
+            NamedField {
+                default: Some(FieldDefault::Value(serde_json::Value::Null)),
+                skip_serializing_if: true,
+                ..NamedField::new(
+                    "tags",
+                    Schema::Option(Box::new(schematic::describe::<Vec<String>>())),
+                )
+            },
+            NamedField::new(
+                "events",
+                Schema::Seq(Box::new(Sequence {
+                    name: TypeName::generic("Vec", "alloc::vec", vec![TypeName::new("Event", "")]),
+                    element: event,
+                    len: None,
+                })),
+            ),
+        ],
+        layout: None,
+    });
+
+    let schema = recorder.schema();
+    assert_eq!(expected, schema);
+
+    // Skipped fields may be missing, so they aren't required.
+    let fields = &schema.as_struct().unwrap().fields;
+    assert!(fields[0].is_required());
+    assert!(!fields[1].is_required());
+}
+
+#[test]
+fn variants_in_declaration_order() {
+    let mut recorder = Recorder::new();
+    recorder.record(&Event::Progress(50)).unwrap();
+    recorder.record(&Event::Started).unwrap();
+
+    let schema = recorder.schema();
+    let names = schema
+        .as_enum()
+        .unwrap()
+        .variants
+        .iter()
+        .map(Variant::name)
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["Started", "Progress"], names);
+}
+
+#[test]
+fn record_collections() {
+    let mut scores = HashMap::new();
+    scores.insert("alice".to_string(), vec![(1_u8, 'a')]);
+
+    assert_eq!(
+        Ok(schematic::describe::<HashMap<String, Vec<(u8, char)>>>()),
+        schema_of(&scores),
+    );
+}
+
+#[test]
+fn report_conflicts() {
+    let values = vec![serde_json::json!(1), serde_json::json!("one")];
+
+    assert_eq!(
+        Err(Error::Conflict {
+            path: "/*".into(),
+            expected: "`u64`".into(),
+            found: "`String`".into(),
+        }),
+        schema_of(&values),
+    );
+
+    let values = serde_json::json!({ "a": 1, "b": "two" });
+
+    assert_eq!(
+        Err(Error::Conflict {
+            path: "/<value>".into(),
+            expected: "`u64`".into(),
+            found: "`String`".into(),
+        }),
+        schema_of(&values),
+    );
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "registry")]
+
+use pretty_assertions::assert_eq;
+use schematic::*;
+
+pub mod api {
+    use schematic::*;
+
+    pub struct User {
+        pub id: UserId,
+        pub name: String,
+    }
+
+    impl Describe for User {
+        fn type_name() -> TypeName {
+            schematic::type_name!(User)
+        }
+
+        fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+            let mut describer = describer.describe_struct(Self::type_name())?;
+            describer.describe_field::<UserId>("id")?;
+            describer.describe_field::<String>("name")?;
+            describer.end()
+        }
+    }
+
+    pub struct UserId(pub u64);
+
+    impl Describe for UserId {
+        fn type_name() -> TypeName {
+            schematic::type_name!(UserId)
+        }
+
+        fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+            describer.describe_newtype_struct::<u64>(Self::type_name())
+        }
+    }
+
+    schematic::export!(UserId, User);
+}
+
+pub struct Event;
+
+impl Describe for Event {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Event)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        describer.describe_unit_struct(Self::type_name())
+    }
+}
+
+schematic::export!(Event);
+
+// Registering a type twice doesn't list it twice.
+schematic::export!(api::User);
+
+pub struct Page<T>(pub Vec<T>);
+
+impl<T: Describe> Describe for Page<T> {
+    fn type_name() -> TypeName {
+        TypeName::generic("Page", module_path!(), vec![T::type_name()])
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        describer.describe_newtype_struct::<Vec<T>>(Self::type_name())
+    }
+}
+
+// Instantiations of a generic type are listed separately, but only once each, even
+// when other registrations come between them.
+schematic::export!(Page<Event>, Page<api::User>, Page<api::UserId>);
+schematic::export!(Page<api::UserId>, Page<Event>, Page<api::User>);
+
+#[test]
+fn all_registered_types() {
+    let names = schematic::registry::all()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            TypeName::new("Event", "registry"),
+            TypeName::generic("Page", "registry", vec![Event::type_name()]),
+            TypeName::generic("Page", "registry", vec![api::User::type_name()]),
+            TypeName::generic("Page", "registry", vec![api::UserId::type_name()]),
+            TypeName::new("User", "registry::api"),
+            TypeName::new("UserId", "registry::api"),
+        ],
+        names,
+    );
+}
+
+#[test]
+fn describe_registered_types() {
+    for (name, describe) in schematic::registry::all() {
+        let schema = describe();
+        assert_eq!(Some(&name), schema.type_name());
+    }
+
+    let (_, describe) = schematic::registry::all()
+        .find(|(name, _)| name.name == "User")
+        .unwrap();
+    assert_eq!(schematic::describe::<api::User>(), describe());
+}
@@ -0,0 +1,183 @@
+#![cfg(feature = "std")]
+
+use pretty_assertions::assert_eq;
+use schematic::*;
+
+mod generated {
+    include!("rust/generated.rs");
+}
+
+fn module() -> std::borrow::Cow<'static, str> {
+    "remote::api".into()
+}
+
+/// A schema as it might be received from another service, including names that
+/// aren't valid Rust identifiers.
+fn schema() -> Schema {
+    let user_id = Schema::NewtypeStruct(Box::new(NewtypeStruct {
+        name: TypeName::new("UserId", module()),
+        docs: None,
+        inner: Schema::U64,
+        constraints: user_id_constraints(),
+    }));
+
+    let role = Schema::Enum(Enum {
+        name: TypeName::new("Role", module()),
+        docs: Some(" The permissions granted to a user.".into()),
+        repr: Some(Primitive::U8),
+        variants: vec![
+            Variant::Unit {
+                name: "guest".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                discriminant: Some(PrimitiveValue::U8(1)),
+            },
+            Variant::Tuple {
+                name: "Member".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                elements: vec![Schema::U32, schematic::describe::<String>()],
+            },
+            Variant::Struct {
+                name: "Admin".into(),
+                docs: Some(" Full access.".into()),
+                attributes: attributes! { "editor.color" => "red" },
+                fields: vec![NamedField::new("since", Schema::I64)],
+            },
+        ],
+        layout: None,
+    });
+
+    let page = Schema::Struct(Struct {
+        name: TypeName::generic("Page", module(), vec![TypeName::new("UserId", module())]),
+        docs: None,
+        attributes: Attributes::new(),
+        fields: vec![
+            NamedField::new("items", schematic::describe::<Vec<UserId>>()),
+            NamedField {
+                default: Some(FieldDefault::Value(serde_json::Value::Null)),
+                skip_serializing_if: true,
+                ..NamedField::new("next", Schema::Option(Box::new(Schema::U32)))
+            },
+        ],
+        layout: None,
+    });
+
+    Schema::Struct(Struct {
+        name: TypeName::new("User", module()),
+        docs: Some(" A registered user.\n\n Users are created on sign-up.".into()),
+        attributes: attributes! { "editor.order" => 1 },
+        fields: vec![
+            NamedField {
+                name: "id".into(),
+                docs: Some(" The unique ID of the user.".into()),
+                attributes: attributes! { "editor.hidden" => true, "editor.step" => 0.5 },
+                schema: user_id,
+                constraints: Constraints::default(),
+                default: None,
+                skip_serializing_if: false,
+            },
+            NamedField {
+                name: "displayName".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                schema: schematic::describe::<String>(),
+                constraints: Constraints {
+                    min_len: Some(1),
+                    pattern: Some("^\\S".into()),
+                    ..Default::default()
+                },
+                default: Some(FieldDefault::of("anonymous")),
+                skip_serializing_if: false,
+            },
+            NamedField::new("type", role),
+            NamedField::new("friends", page),
+            NamedField::new(
+                "tags",
+                schematic::describe::<std::collections::BTreeMap<String, [u8; 2]>>(),
+            ),
+        ],
+        layout: None,
+    })
+}
+
+fn user_id_constraints() -> Constraints {
+    Constraints {
+        min: Some(Number::Int(1)),
+        ..Default::default()
+    }
+}
+
+/// Stand-in for `generated::UserId`, used to build the expected schema.
+struct UserId;
+
+impl Describe for UserId {
+    fn type_name() -> TypeName {
+        TypeName::new("UserId", module())
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let info = TypeInfo {
+            docs: None,
+            attributes: Attributes::new(),
+            constraints: user_id_constraints(),
+        };
+        describer.describe_newtype_struct_with::<u64>(Self::type_name(), info)
+    }
+}
+
+#[test]
+fn generate_rust() {
+    let source = rust::generate(&schema()).unwrap();
+    assert_eq!(include_str!("rust/generated.rs"), source);
+}
+
+#[test]
+fn round_trip() {
+    assert_eq!(schema(), schematic::describe::<generated::User>());
+}
+
+#[test]
+fn reject_discriminants_without_repr() {
+    let schema = Schema::Enum(Enum {
+        name: type_name!(Shape),
+        docs: None,
+        repr: None,
+        variants: vec![
+            Variant::Unit {
+                name: "Empty".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                discriminant: Some(PrimitiveValue::Isize(1)),
+            },
+            Variant::Tuple {
+                name: "Circle".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                elements: vec![Schema::F32],
+            },
+        ],
+        layout: None,
+    });
+
+    assert_eq!(
+        Err(rust::Error::Unsupported(Box::new(schema.clone()))),
+        rust::generate(&schema),
+    );
+}
+
+#[test]
+fn reject_borrowed() {
+    let schema = Schema::Struct(Struct {
+        name: type_name!(Borrowed),
+        docs: None,
+        attributes: Attributes::new(),
+        fields: vec![NamedField::new("name", Schema::Str)],
+        layout: None,
+    });
+
+    assert_eq!(
+        Err(rust::Error::Unsupported(Box::new(Schema::Str))),
+        rust::generate(&schema),
+    );
+}
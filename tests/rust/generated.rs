@@ -0,0 +1,105 @@
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+pub struct UserId(pub u64);
+
+impl ::schematic::Describe for UserId {
+    fn type_name() -> ::schematic::TypeName {
+        ::schematic::TypeName::new("UserId", "remote::api")
+    }
+
+    fn describe<D: ::schematic::Describer>(describer: D) -> ::std::result::Result<D::Ok, D::Error> {
+        describer.describe_newtype_struct_with::<u64>(Self::type_name(), ::schematic::TypeInfo { constraints: ::schematic::Constraints { min: Some(::schematic::Number::Int(1)), ..::std::default::Default::default() }, ..::std::default::Default::default() })
+    }
+}
+
+/// The permissions granted to a user.
+#[repr(u8)]
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+pub enum Role {
+    #[serde(rename = "guest")]
+    Guest = 1,
+    Member(u32, ::std::string::String),
+    /// Full access.
+    Admin {
+        since: i64,
+    },
+}
+
+impl ::schematic::Describe for Role {
+    fn type_name() -> ::schematic::TypeName {
+        ::schematic::TypeName::new("Role", "remote::api")
+    }
+
+    fn describe<D: ::schematic::Describer>(describer: D) -> ::std::result::Result<D::Ok, D::Error> {
+        use ::schematic::DescribeEnum as _;
+        use ::schematic::DescribeStructVariant as _;
+        use ::schematic::DescribeTupleVariant as _;
+        let mut describer = describer.describe_enum_with(Self::type_name(), ::schematic::TypeInfo { docs: Some(" The permissions granted to a user."), ..::std::default::Default::default() })?;
+        describer.describe_repr(::schematic::Primitive::U8)?;
+        describer.describe_unit_variant("guest", Some(::schematic::PrimitiveValue::U8(1)))?;
+        {
+            let mut variant = describer.start_tuple_variant("Member")?;
+            variant.describe_element::<u32>()?;
+            variant.describe_element::<::std::string::String>()?;
+            describer.end_tuple_variant(variant)?;
+        }
+        {
+            let mut variant = describer.start_struct_variant_with("Admin", ::schematic::VariantInfo { docs: Some(" Full access."), attributes: ::schematic::attributes! { "editor.color" => "red" } })?;
+            variant.describe_field::<i64>("since")?;
+            describer.end_struct_variant(variant)?;
+        }
+        describer.end()
+    }
+}
+
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[serde(rename = "Page")]
+pub struct PageUserId {
+    pub items: ::std::vec::Vec<UserId>,
+    #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+    pub next: ::std::option::Option<u32>,
+}
+
+impl ::schematic::Describe for PageUserId {
+    fn type_name() -> ::schematic::TypeName {
+        ::schematic::TypeName::generic("Page", "remote::api", vec![::schematic::TypeName::new("UserId", "remote::api")])
+    }
+
+    fn describe<D: ::schematic::Describer>(describer: D) -> ::std::result::Result<D::Ok, D::Error> {
+        use ::schematic::DescribeStruct as _;
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<::std::vec::Vec<UserId>>("items")?;
+        describer.describe_field_with::<::std::option::Option<u32>>("next", ::schematic::FieldInfo { default: Some(::schematic::FieldDefault::Value(::schematic::__private::serde_json::json!(null))), skip_serializing_if: true, ..::std::default::Default::default() })?;
+        describer.end()
+    }
+}
+
+/// A registered user.
+///
+/// Users are created on sign-up.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+pub struct User {
+    /// The unique ID of the user.
+    pub id: UserId,
+    #[serde(rename = "displayName")]
+    pub display_name: ::std::string::String,
+    pub r#type: Role,
+    pub friends: PageUserId,
+    pub tags: ::std::collections::BTreeMap<::std::string::String, [u8; 2]>,
+}
+
+impl ::schematic::Describe for User {
+    fn type_name() -> ::schematic::TypeName {
+        ::schematic::TypeName::new("User", "remote::api")
+    }
+
+    fn describe<D: ::schematic::Describer>(describer: D) -> ::std::result::Result<D::Ok, D::Error> {
+        use ::schematic::DescribeStruct as _;
+        let mut describer = describer.describe_struct_with(Self::type_name(), ::schematic::TypeInfo { docs: Some(" A registered user.\n\n Users are created on sign-up."), attributes: ::schematic::attributes! { "editor.order" => ::schematic::Number::Int(1) }, ..::std::default::Default::default() })?;
+        describer.describe_field_with::<UserId>("id", ::schematic::FieldInfo { docs: Some(" The unique ID of the user."), attributes: ::schematic::attributes! { "editor.hidden" => true, "editor.step" => ::schematic::Number::Float(0.5) }, ..::std::default::Default::default() })?;
+        describer.describe_field_with::<::std::string::String>("displayName", ::schematic::FieldInfo { constraints: ::schematic::Constraints { min_len: Some(1), pattern: Some(::std::borrow::Cow::Borrowed("^\\S")), ..::std::default::Default::default() }, default: Some(::schematic::FieldDefault::Value(::schematic::__private::serde_json::json!("anonymous"))), ..::std::default::Default::default() })?;
+        describer.describe_field::<Role>("type")?;
+        describer.describe_field::<PageUserId>("friends")?;
+        describer.describe_field::<::std::collections::BTreeMap<::std::string::String, [u8; 2]>>("tags")?;
+        describer.end()
+    }
+}
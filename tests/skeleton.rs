@@ -0,0 +1,364 @@
+#![cfg(feature = "std")]
+
+use pretty_assertions::assert_eq;
+use schematic::{skeleton::Error, *};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct Config {
+    pub name: String,
+    pub workers: u8,
+    pub ratio: f64,
+    pub mode: Mode,
+    pub timeout: Option<u32>,
+    pub tags: Vec<String>,
+    pub key: [u8; 2],
+    pub server: Server,
+    pub limits: BTreeMap<String, u64>,
+}
+
+impl Describe for Config {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Config)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct_with(
+            Self::type_name(),
+            TypeInfo {
+                docs: Some(" Top-level configuration."),
+                ..Default::default()
+            },
+        )?;
+        describer.describe_field_with::<String>(
+            "name",
+            FieldInfo {
+                docs: Some(" The name of the service."),
+                ..Default::default()
+            },
+        )?;
+        describer.describe_field::<u8>("workers")?;
+        describer.describe_field::<f64>("ratio")?;
+        describer.describe_field::<Mode>("mode")?;
+        describer.describe_field_with::<Option<u32>>(
+            "timeout",
+            FieldInfo {
+                docs: Some(" Request timeout in seconds.\n\n Defaults to no timeout."),
+                ..Default::default()
+            },
+        )?;
+        describer.describe_field::<Vec<String>>("tags")?;
+        describer.describe_field::<[u8; 2]>("key")?;
+        describer.describe_field_with::<Server>(
+            "server",
+            FieldInfo {
+                docs: Some(" Where to listen for requests."),
+                ..Default::default()
+            },
+        )?;
+        describer.describe_field::<BTreeMap<String, u64>>("limits")?;
+        describer.end()
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct Server {
+    pub host: String,
+    pub backup: Option<Box<Server>>,
+}
+
+impl Describe for Server {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Server)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<String>("host")?;
+        describer.describe_field::<Option<Address>>("backup")?;
+        describer.end()
+    }
+}
+
+/// Stands in for `Box<Server>`, which would be recursive.
+pub struct Address;
+
+impl Describe for Address {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Address)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<String>("host")?;
+        describer.end()
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+pub enum Mode {
+    Fast,
+    Slow { delay: u32 },
+}
+
+impl Describe for Mode {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Mode)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_enum(Self::type_name())?;
+        describer.describe_unit_variant("Fast", None)?;
+        let mut variant = describer.start_struct_variant("Slow")?;
+        variant.describe_field::<u32>("delay")?;
+        describer.end_struct_variant(variant)?;
+        describer.end()
+    }
+}
+
+fn expected() -> Config {
+    Config {
+        name: String::new(),
+        workers: 0,
+        ratio: 0.0,
+        mode: Mode::Fast,
+        timeout: None,
+        tags: Vec::new(),
+        key: [0, 0],
+        server: Server {
+            host: String::new(),
+            backup: None,
+        },
+        limits: BTreeMap::new(),
+    }
+}
+
+#[test]
+fn skeleton_value() {
+    let schema = schematic::describe::<Mode>();
+    assert_eq!(
+        Value::Enum {
+            name: "Mode".into(),
+            variant_index: 0,
+            variant: "Fast".into(),
+            data: VariantValue::Unit,
+        },
+        schematic::skeleton(&schema).unwrap(),
+    );
+
+    let schema = schematic::describe::<Server>();
+    assert_eq!(
+        Value::Struct {
+            name: "Server".into(),
+            fields: vec![
+                ("host".into(), Value::String(String::new())),
+                ("backup".into(), Value::Option(None)),
+            ],
+        },
+        schematic::skeleton(&schema).unwrap(),
+    );
+}
+
+#[test]
+fn starter_toml() {
+    let schema = schematic::describe::<Config>();
+    let toml = skeleton::to_toml(&schema, &schematic::skeleton(&schema).unwrap()).unwrap();
+
+    let expected_toml = r#"# Top-level configuration.
+
+# The name of the service.
+name = ""
+workers = 0
+ratio = 0.0
+mode = "Fast"
+# Request timeout in seconds.
+#
+# Defaults to no timeout.
+# timeout = 0
+tags = []
+key = [0, 0]
+
+# Where to listen for requests.
+[server]
+host = ""
+# backup = { host = "" }
+
+[limits]
+"#;
+    assert_eq!(expected_toml, toml);
+    assert_eq!(expected(), toml::from_str::<Config>(&toml).unwrap());
+}
+
+#[test]
+fn starter_yaml() {
+    let schema = schematic::describe::<Config>();
+    let yaml = skeleton::to_yaml(&schema, &schematic::skeleton(&schema).unwrap()).unwrap();
+
+    let expected_yaml = r#"# Top-level configuration.
+# The name of the service.
+name: ""
+workers: 0
+ratio: 0.0
+mode: "Fast"
+# Request timeout in seconds.
+#
+# Defaults to no timeout.
+# timeout: 0
+tags: []
+key:
+  - 0
+  - 0
+# Where to listen for requests.
+server:
+  host: ""
+  # backup: {host: ""}
+limits: {}
+"#;
+    assert_eq!(expected_yaml, yaml);
+    assert_eq!(expected(), serde_yaml::from_str::<Config>(&yaml).unwrap());
+}
+
+#[test]
+fn unsupported_values() {
+    let schema = schematic::describe::<Vec<u8>>();
+    assert_eq!(
+        Err(Error::Unsupported {
+            path: "".into(),
+            value: "a value that isn't a table",
+        }),
+        skeleton::to_toml(&schema, &schematic::skeleton(&schema).unwrap()),
+    );
+
+    let schema = schematic::describe::<BTreeMap<String, u64>>();
+    let value = Value::Map(vec![(Value::String("max".into()), Value::U64(u64::MAX))]);
+    assert_eq!(
+        Err(Error::Unsupported {
+            path: "/max".into(),
+            value: "an integer outside the range of `i64`",
+        }),
+        skeleton::to_toml(&schema, &value),
+    );
+
+    assert_eq!(
+        Err(Error::Mismatch {
+            path: "/max".into()
+        }),
+        skeleton::to_yaml(
+            &schema,
+            &Value::Map(vec![(Value::String("max".into()), Value::Bool(true))]),
+        ),
+    );
+}
+
+pub struct Defaults;
+
+impl Describe for Defaults {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Defaults)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let default = |default| FieldInfo {
+            default: Some(default),
+            ..Default::default()
+        };
+
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field_with::<String>("host", default(FieldDefault::of("localhost")))?;
+        describer.describe_field_with::<u16>("port", default(FieldDefault::of(&8080u16)))?;
+        describer.describe_field_with::<u8>("retries", default(FieldDefault::Unknown))?;
+        describer.describe_field_with::<u8>("workers", default(FieldDefault::of("many")))?;
+        describer.describe_field_with::<Mode>(
+            "mode",
+            default(FieldDefault::of(
+                &serde_json::json!({ "Slow": { "delay": 5 } }),
+            )),
+        )?;
+        describer.describe_field_with::<BTreeMap<String, u64>>(
+            "limits",
+            default(FieldDefault::of(&serde_json::json!({ "requests": 100 }))),
+        )?;
+        describer.end()
+    }
+}
+
+#[test]
+fn skeleton_defaults() {
+    let schema = schematic::describe::<Defaults>();
+    let toml = skeleton::to_toml(&schema, &schematic::skeleton(&schema).unwrap()).unwrap();
+
+    // Unknown defaults and ones that don't match the schema fall back to zero.
+    let expected_toml = r#"host = "localhost"
+port = 8080
+retries = 0
+workers = 0
+
+[mode]
+
+[mode.Slow]
+delay = 5
+
+[limits]
+requests = 100
+"#;
+    assert_eq!(expected_toml, toml);
+}
+
+#[test]
+fn uninhabited_types() {
+    let never = Schema::Enum(Enum {
+        name: TypeName::new("Never", "skeleton"),
+        docs: None,
+        repr: None,
+        variants: Vec::new(),
+        layout: None,
+    });
+
+    let schema = Schema::Struct(Struct {
+        name: TypeName::new("Job", "skeleton"),
+        docs: None,
+        attributes: Attributes::new(),
+        fields: vec![
+            NamedField::new("id", Schema::U64),
+            NamedField::new("input", Schema::Tuple(vec![Schema::U8, never.clone()])),
+        ],
+        layout: None,
+    });
+    assert_eq!(
+        Err(Error::Uninhabited {
+            path: "/input/1".into(),
+            ty: "Never".into(),
+        }),
+        schematic::skeleton(&schema),
+    );
+
+    let error = schematic::skeleton(&Schema::TypeParam("T".into())).unwrap_err();
+    assert_eq!("type `T` has no values at `/`", error.to_string());
+
+    // Options and empty collections don't need a value of their type, and options
+    // are left without an example.
+    let schema = Schema::Struct(Struct {
+        name: TypeName::new("Job", "skeleton"),
+        docs: None,
+        attributes: Attributes::new(),
+        fields: vec![
+            NamedField::new("result", Schema::Option(Box::new(never.clone()))),
+            NamedField::new(
+                "errors",
+                Schema::Seq(Box::new(Sequence {
+                    name: TypeName::generic(
+                        "Vec",
+                        "alloc::vec",
+                        vec![TypeName::new("Never", "skeleton")],
+                    ),
+                    element: never,
+                    len: None,
+                })),
+            ),
+        ],
+        layout: None,
+    });
+    let value = schematic::skeleton(&schema).unwrap();
+    assert_eq!("errors = []\n", skeleton::to_toml(&schema, &value).unwrap());
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "std")]
+
+use schematic::*;
+use std::{env, fs, panic};
+
+pub struct Config {
+    pub port: u16,
+    pub mode: Mode,
+}
+
+impl Describe for Config {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Config)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<u16>("port")?;
+        describer.describe_field::<Mode>("mode")?;
+        describer.end()
+    }
+}
+
+#[allow(dead_code)]
+pub enum Mode {
+    Fast,
+    Slow(u32),
+}
+
+impl Describe for Mode {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Mode)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_enum(Self::type_name())?;
+        describer.describe_unit_variant("Fast", None)?;
+        let mut variant = describer.start_tuple_variant("Slow")?;
+        variant.describe_element::<u32>()?;
+        describer.end_tuple_variant(variant)?;
+        describer.end()
+    }
+}
+
+#[test]
+fn matching_snapshots() {
+    schematic::assert_schema_snapshot!(Config);
+    schematic::assert_schema_snapshot!(Vec<Mode>, "modes");
+}
+
+#[test]
+fn mismatched_snapshot() {
+    // The snapshot would be overwritten instead of compared.
+    if env::var_os(snapshot::UPDATE_VAR).is_some() {
+        return;
+    }
+
+    let dir = env::temp_dir().join("schematic-snapshot-mismatch");
+    let path = dir.join("Mode.json");
+    let new_path = dir.join("Mode.json.new");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let schema = schematic::describe::<Mode>();
+    let stale = snapshot::to_snapshot(&schema).replace("Slow", "Sluggish");
+    fs::write(&path, &stale).unwrap();
+
+    let result = panic::catch_unwind(|| snapshot::assert_snapshot(&schema, &path));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert!(
+        message.contains("-          \"name\": \"Sluggish\",\n+          \"name\": \"Slow\",\n")
+    );
+
+    // The snapshot itself is left alone, and the new schema can be accepted by
+    // renaming the `.new` file.
+    assert_eq!(stale, fs::read_to_string(&path).unwrap());
+    fs::rename(&new_path, &path).unwrap();
+    snapshot::assert_snapshot(&schema, &path);
+    assert!(!new_path.exists());
+}
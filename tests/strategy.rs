@@ -0,0 +1,164 @@
+#![cfg(feature = "proptest")]
+
+use proptest::prelude::*;
+use schematic::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    pub name: String,
+    pub port: u16,
+    pub ratio: f64,
+    pub key: [u8; 3],
+    pub initial: Option<char>,
+    pub limits: HashMap<String, i64>,
+    pub modes: Vec<Mode>,
+}
+
+impl Describe for Config {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Config)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field::<String>("name")?;
+        describer.describe_field::<u16>("port")?;
+        describer.describe_field::<f64>("ratio")?;
+        describer.describe_field::<[u8; 3]>("key")?;
+        describer.describe_field::<Option<char>>("initial")?;
+        describer.describe_field::<HashMap<String, i64>>("limits")?;
+        describer.describe_field::<Vec<Mode>>("modes")?;
+        describer.end()
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum Mode {
+    Off,
+    Level(u8),
+    Range(i32, i32),
+    Custom { label: String, enabled: bool },
+}
+
+impl Describe for Mode {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Mode)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_enum(Self::type_name())?;
+        describer.describe_unit_variant("Off", None)?;
+
+        let mut variant = describer.start_tuple_variant("Level")?;
+        variant.describe_element::<u8>()?;
+        describer.end_tuple_variant(variant)?;
+
+        let mut variant = describer.start_tuple_variant("Range")?;
+        variant.describe_element::<i32>()?;
+        variant.describe_element::<i32>()?;
+        describer.end_tuple_variant(variant)?;
+
+        let mut variant = describer.start_struct_variant("Custom")?;
+        variant.describe_field::<String>("label")?;
+        variant.describe_field::<bool>("enabled")?;
+        describer.end_struct_variant(variant)?;
+
+        describer.end()
+    }
+}
+
+proptest! {
+    #[test]
+    fn values_deserialize_as_type(value in strategy::strategy(&schematic::describe::<Config>()).unwrap()) {
+        let json = serde_json::to_string(&value).unwrap();
+        let from_json = serde_json::from_str::<Config>(&json).unwrap();
+
+        let bytes = bincode::serialize(&value).unwrap();
+        let from_bincode = bincode::deserialize::<Config>(&bytes).unwrap();
+        prop_assert_eq!(&from_json, &from_bincode);
+
+        // Serializing the typed value gives back the same data.
+        prop_assert_eq!(bytes.len(), bincode::serialized_size(&from_bincode).unwrap() as usize);
+        prop_assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            serde_json::to_value(&from_json).unwrap(),
+        );
+    }
+
+    #[test]
+    fn respect_lengths(value in strategy::strategy(&Schema::Seq(Box::new(Sequence {
+        name: TypeName::new("Triple", "strategy"),
+        element: Schema::Array(Box::new(Array { element: Schema::U8, len: 2 })),
+        len: Some(3),
+    }))).unwrap()) {
+        match value {
+            Value::Seq(elements) => {
+                prop_assert_eq!(3, elements.len());
+                for element in elements {
+                    match element {
+                        Value::Array(bytes) => prop_assert_eq!(2, bytes.len()),
+                        other => prop_assert!(false, "expected array, found {:?}", other),
+                    }
+                }
+            }
+            other => prop_assert!(false, "expected sequence, found {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn skip_uninhabited_types() {
+    let never = Schema::Enum(Enum {
+        name: TypeName::new("Never", "strategy"),
+        docs: None,
+        repr: None,
+        variants: Vec::new(),
+        layout: None,
+    });
+    assert!(strategy::strategy(&never).is_none());
+    assert!(strategy::strategy(&Schema::TypeParam("T".into())).is_none());
+    assert!(strategy::strategy(&Schema::Tuple(vec![Schema::U8, never.clone()])).is_none());
+
+    // Only the variants without `Never` can be generated, and an `Option<Never>` or
+    // `Vec<Never>` can still be empty.
+    let schema = Schema::Enum(Enum {
+        name: TypeName::new("Partial", "strategy"),
+        docs: None,
+        repr: None,
+        variants: vec![
+            Variant::Tuple {
+                name: "Impossible".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                elements: vec![never.clone()],
+            },
+            Variant::Tuple {
+                name: "Possible".into(),
+                docs: None,
+                attributes: Attributes::new(),
+                elements: vec![
+                    Schema::Option(Box::new(never.clone())),
+                    Schema::Slice(Box::new(never)),
+                ],
+            },
+        ],
+        layout: None,
+    });
+
+    let mut runner = proptest::test_runner::TestRunner::default();
+    let tree = strategy::strategy(&schema)
+        .unwrap()
+        .new_tree(&mut runner)
+        .unwrap();
+    assert_eq!(
+        Value::Enum {
+            name: "Partial".into(),
+            variant_index: 1,
+            variant: "Possible".into(),
+            data: VariantValue::Tuple(vec![Value::Option(None), Value::Seq(Vec::new())]),
+        },
+        tree.current(),
+    );
+}
@@ -15,3 +15,85 @@ mod nested {
         assert_eq!("type_name::nested", name.module);
     }
 }
+
+pub struct Page<T>(pub Vec<T>);
+
+#[test]
+fn generic_struct_name() {
+    let name = schematic::type_name!(Page<Foo>);
+    assert_eq!("Page", name.name);
+    assert_eq!("type_name", name.module);
+    assert_eq!("type_name::Page<type_name::Foo>", name.qualified());
+}
+
+impl schematic::Describe for Foo {
+    fn type_name() -> schematic::TypeName {
+        schematic::type_name!(Foo)
+    }
+
+    fn describe<D: schematic::Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        describer.describe_unit_struct(Self::type_name())
+    }
+}
+
+#[cfg(feature = "std")]
+macro_rules! assert_names {
+    ($($ty:ty => $qualified:literal,)*) => {
+        $(
+            let name = <$ty as schematic::Describe>::type_name();
+            assert_eq!($qualified, name.qualified(), "{}", stringify!($ty));
+            assert_eq!(Ok(name), $qualified.parse(), "{}", stringify!($ty));
+        )*
+    };
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn builtin_names() {
+    use std::collections::*;
+
+    assert_names! {
+        u8 => "u8",
+        f64 => "f64",
+        char => "char",
+        &str => "str",
+        () => "()",
+        String => "alloc::string::String",
+        Option<bool> => "core::option::Option<bool>",
+        (u8,) => "(u8,)",
+        (u8, (), [i32; 3]) => "(u8, (), [i32; 3])",
+        &[u16] => "[u16]",
+        [[u8; 2]; 0] => "[[u8; 2]; 0]",
+        Vec<u8> => "alloc::vec::Vec<u8>",
+        VecDeque<u8> => "alloc::collections::vec_deque::VecDeque<u8>",
+        HashSet<u8> => "std::collections::hash_set::HashSet<u8>",
+        BTreeSet<u8> => "alloc::collections::btree_set::BTreeSet<u8>",
+        BinaryHeap<u8> => "alloc::collections::binary_heap::BinaryHeap<u8>",
+        LinkedList<u8> => "alloc::collections::linked_list::LinkedList<u8>",
+        HashMap<String, Foo> => "std::collections::hash_map::HashMap<alloc::string::String, type_name::Foo>",
+        BTreeMap<u8, Vec<u8>> => "alloc::collections::btree_map::BTreeMap<u8, alloc::vec::Vec<u8>>",
+    }
+
+    // The short form leaves out modules, but keeps the syntax of structural types.
+    let name = <HashMap<String, [u8; 2]> as schematic::Describe>::type_name();
+    assert_eq!("HashMap<String, [u8; 2]>", name.to_string());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn parse_errors() {
+    let error = schematic::parse::parse_type_name("Vec<u8").unwrap_err();
+    assert_eq!(
+        "expected `,` or `>`, found end of input at 1:7",
+        error.to_string()
+    );
+
+    let error = schematic::parse::parse_type_name("alloc:vec::Vec").unwrap_err();
+    assert_eq!("expected `::`, found `vec` at 1:7", error.to_string());
+
+    let error = "[u8; 2] extra".parse::<schematic::TypeName>().unwrap_err();
+    assert_eq!(
+        "expected end of input, found `extra` at 1:9",
+        error.to_string()
+    );
+}
@@ -0,0 +1,193 @@
+#![cfg(feature = "std")]
+
+use pretty_assertions::assert_eq;
+use schematic::{validate::Error, *};
+
+pub struct Sound {
+    pub name: Option<String>,
+    pub volume: f32,
+    pub tags: Vec<String>,
+    pub priority: Priority,
+}
+
+impl Describe for Sound {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Sound)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let mut describer = describer.describe_struct(Self::type_name())?;
+        describer.describe_field_with::<Option<String>>(
+            "name",
+            FieldInfo {
+                constraints: Constraints {
+                    min_len: Some(1),
+                    pattern: Some("^[a-z_]+$".into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )?;
+        describer.describe_field_with::<f32>(
+            "volume",
+            FieldInfo {
+                constraints: Constraints {
+                    min: Some(Number::Float(0.0)),
+                    max: Some(Number::Float(1.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )?;
+        describer.describe_field_with::<Vec<String>>(
+            "tags",
+            FieldInfo {
+                constraints: Constraints {
+                    max_len: Some(2),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )?;
+        describer.describe_field::<Priority>("priority")?;
+        describer.end()
+    }
+}
+
+pub struct Priority(pub u8);
+
+impl Describe for Priority {
+    fn type_name() -> TypeName {
+        schematic::type_name!(Priority)
+    }
+
+    fn describe<D: Describer>(describer: D) -> Result<D::Ok, D::Error> {
+        let info = TypeInfo {
+            docs: None,
+            attributes: Attributes::new(),
+            constraints: Constraints {
+                min: Some(Number::Int(1)),
+                max: Some(Number::Int(10)),
+                ..Default::default()
+            },
+        };
+        describer.describe_newtype_struct_with::<u8>(Self::type_name(), info)
+    }
+}
+
+fn sound(name: Option<&str>, volume: f32, tags: &[&str], priority: u8) -> Value {
+    Value::Struct {
+        name: "Sound".into(),
+        fields: vec![
+            (
+                "name".into(),
+                Value::Option(name.map(|name| Box::new(Value::String(name.into())))),
+            ),
+            ("volume".into(), Value::F32(volume)),
+            (
+                "tags".into(),
+                Value::Seq(
+                    tags.iter()
+                        .map(|tag| Value::String(tag.to_string()))
+                        .collect(),
+                ),
+            ),
+            (
+                "priority".into(),
+                Value::NewtypeStruct {
+                    name: "Priority".into(),
+                    inner: Box::new(Value::U8(priority)),
+                },
+            ),
+        ],
+    }
+}
+
+#[test]
+fn validate_valid() {
+    let schema = schematic::describe::<Sound>();
+    assert_eq!(
+        Ok(()),
+        validate::validate(&schema, &sound(Some("explosion"), 1.0, &[], 1))
+    );
+
+    // Constraints on optional fields only apply to present values.
+    assert_eq!(
+        Ok(()),
+        validate::validate(&schema, &sound(None, 0.0, &["a", "b"], 10))
+    );
+}
+
+#[test]
+fn validate_constraints() {
+    let schema = schematic::describe::<Sound>();
+    assert_eq!(
+        Err(vec![
+            Error::TooShort {
+                path: "/name".into(),
+                min_len: 1,
+                len: 0,
+            },
+            Error::NoMatch {
+                path: "/name".into(),
+                pattern: "^[a-z_]+$".into(),
+            },
+            Error::TooSmall {
+                path: "/volume".into(),
+                min: Number::Float(0.0),
+            },
+            Error::TooLong {
+                path: "/tags".into(),
+                max_len: 2,
+                len: 3,
+            },
+            Error::TooLarge {
+                path: "/priority".into(),
+                max: Number::Int(10),
+            },
+        ]),
+        validate::validate(&schema, &sound(Some(""), -0.5, &["a", "b", "c"], 11)),
+    );
+
+    let error = validate::validate(&schema, &sound(None, f32::NAN, &[], 1)).unwrap_err();
+    assert_eq!(
+        "value at `/volume` is less than the minimum of 0.0",
+        error[0].to_string(),
+    );
+}
+
+#[test]
+fn validate_mismatch() {
+    let schema = schematic::describe::<Sound>();
+    let value = Value::Struct {
+        name: "Sound".into(),
+        fields: vec![
+            ("name".into(), Value::Option(None)),
+            ("volume".into(), Value::F64(0.5)),
+            ("tags".into(), Value::Seq(vec![Value::Bool(true)])),
+            (
+                "priority".into(),
+                Value::NewtypeStruct {
+                    name: "Priority".into(),
+                    inner: Box::new(Value::U8(0)),
+                },
+            ),
+        ],
+    };
+
+    assert_eq!(
+        Err(vec![
+            Error::Mismatch {
+                path: "/volume".into(),
+            },
+            Error::Mismatch {
+                path: "/tags/0".into(),
+            },
+            Error::TooSmall {
+                path: "/priority".into(),
+                min: Number::Int(1),
+            },
+        ]),
+        validate::validate(&schema, &value),
+    );
+}